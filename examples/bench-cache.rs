@@ -0,0 +1,30 @@
+use std::time::{Instant, SystemTime};
+
+fn main() {
+    let iterations = std::env::args()
+        .nth(1)
+        .map(|iterations| iterations.parse::<u32>().expect("invalid number of iterations"))
+        .unwrap_or(1000);
+
+    for (name, page) in zomato::bench_fixtures::ALL {
+        let menus = zomato::parse_daily_menu(page).expect("failed to parse fixture");
+        let json = serde_json::to_vec(&menus).expect("menus are always serializable");
+        let binary = zomato::cache::encode(&menus, SystemTime::now());
+        // sanity check
+        zomato::cache::decode(&binary).expect("failed to decode menus");
+
+        let start = Instant::now();
+        for _ in 0..iterations {
+            serde_json::from_slice::<Vec<zomato::Menu>>(&json).expect("failed to decode menus");
+        }
+        let json_elapsed = start.elapsed() / iterations;
+
+        let start = Instant::now();
+        for _ in 0..iterations {
+            zomato::cache::decode(&binary).expect("failed to decode menus");
+        }
+        let binary_elapsed = start.elapsed() / iterations;
+
+        println!("{:>6}: json ({:>6} bytes) {:?}, binary ({:>6} bytes) {:?} per iteration", name, json.len(), json_elapsed, binary.len(), binary_elapsed);
+    }
+}
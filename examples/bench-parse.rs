@@ -0,0 +1,30 @@
+use std::time::{Duration, Instant};
+
+fn measure<T>(iterations: u32, mut f: impl FnMut() -> T) -> Duration {
+    // warm-up
+    f();
+
+    let start = Instant::now();
+    for _ in 0..iterations {
+        f();
+    }
+    start.elapsed() / iterations
+}
+
+fn main() {
+    let iterations = std::env::args()
+        .nth(1)
+        .map(|iterations| iterations.parse::<u32>().expect("invalid number of iterations"))
+        .unwrap_or(100);
+
+    println!("JSON parser: {}", if cfg!(feature = "simd") { "simd-json" } else { "serde_json" });
+    for (name, page) in zomato::bench_fixtures::ALL {
+        // sanity check
+        zomato::parse_daily_menu(page).expect("failed to parse fixture");
+
+        let extract = measure(iterations, || zomato_core::extract_preloaded_state(page).expect("failed to extract data"));
+        let parse = measure(iterations, || zomato::parse_daily_menu(page).expect("failed to parse fixture"));
+        let borrowed = measure(iterations, || zomato::parse_daily_menu_borrowed(page).expect("failed to parse fixture"));
+        println!("{:>6} ({:>7} bytes): {:?} per iteration, {:?} of it extracting the data, {:?} borrowed", name, page.len(), parse, extract, borrowed);
+    }
+}
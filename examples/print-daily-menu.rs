@@ -1,26 +1,40 @@
+use zomato::format::{TableFormatter, Truncation};
+
 #[tokio::main]
 async fn main() -> Result<(), zomato::Error> {
     #[cfg(feature = "debug-log")]
     simple_logger::init().unwrap();
 
-    let mut args = std::env::args();
-    args.next();
+    let mut formatter = TableFormatter::default();
+    let mut positional = Vec::new();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match &*arg {
+            "--max-width" => {
+                let max_width = args.next().expect("missing value of --max-width");
+                formatter.max_width = Some(max_width.parse().expect("invalid width"));
+                if formatter.truncation == Truncation::None {
+                    formatter.truncation = Truncation::End;
+                }
+            },
+            "--truncate" => formatter.truncation = match args.next().expect("missing value of --truncate").parse() {
+                Ok(truncation) => truncation,
+                Err(error) => {
+                    eprintln!("{}", error);
+                    std::process::exit(1);
+                },
+            },
+            _ => positional.push(arg),
+        }
+    }
+
+    let mut args = positional.into_iter();
     let city = args.next().expect("missing city and restaurant");
     let restaurant = args.next().expect("missing restaurant");
 
     let days = zomato::get_daily_menu(&city, &restaurant).await?;
-    let width = days.iter().flat_map(|day| &day.items).map(|food| food.description.chars().count()).max();
-    if let Some(width) = width {
-        for menu in days {
-            println!("{}", menu.date);
-            for food in menu.items {
-                print!("{}", food.description);
-                for _ in 0..(width - food.description.chars().count()) {
-                    print!(" ")
-                }
-                println!(" | {}", food.price);
-            }
-        }
-    }
+    let mut table = String::new();
+    formatter.format(&days, &mut table).expect("writing to String never fails");
+    print!("{}", table);
     Ok(())
 }
@@ -1,97 +1,31 @@
 use std::io;
-use std::fmt;
-
-trait TtsEngine {
-    fn speak(&self, f: &mut dyn FnMut(&mut dyn fmt::Write) -> fmt::Result) -> io::Result<()>;
-}
-
-struct Festival {
-    language: Option<String>,
-}
-
-impl TtsEngine for Festival {
-    fn speak(&self, f: &mut dyn FnMut(&mut dyn fmt::Write) -> fmt::Result) -> io::Result<()> {
-        use io::Write;
-
-        let mut cmd = std::process::Command::new("festival");
-        if let Some(language) = &self.language {
-            cmd.arg("--language");
-            cmd.arg(language);
-        }
-        cmd.arg("--tts");
-        cmd.stdin(std::process::Stdio::piped());
-        let mut child = cmd.spawn()?;
-        let mut stdin = io::BufWriter::new(child.stdin.take().expect("bug in std"));
-        fmt2io::write(&mut stdin, |mut w| f(&mut w))?;
-        stdin.flush()?;
-        // close
-        std::mem::drop(stdin);
-        child.wait()?;
-        Ok(())
-    }
-}
-
-struct Espeak {
-    language: Option<String>,
-    speed: Option<String>,
-}
-
-impl TtsEngine for Espeak {
-    fn speak(&self, f: &mut dyn FnMut(&mut dyn fmt::Write) -> fmt::Result) -> io::Result<()> {
-        use io::Write;
-
-        let mut cmd = std::process::Command::new("espeak");
-        if let Some(language) = &self.language {
-            cmd.arg("-v");
-            cmd.arg(language);
-        }
-        if let Some(speed) = &self.speed {
-            cmd.arg("-s");
-            cmd.arg(speed);
-        }
-        cmd.arg("--stdin");
-        cmd.stdin(std::process::Stdio::piped());
-        let mut child = cmd.spawn()?;
-        let mut stdin = io::BufWriter::new(child.stdin.take().expect("bug in std"));
-        fmt2io::write(&mut stdin, |mut w| f(&mut w))?;
-        stdin.flush()?;
-        // close
-        std::mem::drop(stdin);
-        child.wait()?;
-        Ok(())
-    }
-}
-
-struct Pico2Wave {
-    language: Option<String>,
-}
-
-impl TtsEngine for Pico2Wave {
-    fn speak(&self, f: &mut dyn FnMut(&mut dyn fmt::Write) -> fmt::Result) -> io::Result<()> {
-        use mktemp::Temp;
-
-        let mut text = String::new();
-        f(&mut text).expect("writing to String never fails");
-
-        let tmp_dir = Temp::new_dir()?;
-        let tmp_file = tmp_dir.join("message.wav");
-
-        let mut cmd = std::process::Command::new("pico2wave");
-        if let Some(language) = &self.language {
-            cmd.arg("-l");
-            cmd.arg(language);
+use std::sync::Arc;
+use zomato::reservation::{NoReservations, ReservationProvider, WebhookReservations};
+use zomato::tts::{Briefing, Control, Espeak, Festival, Pico2Wave, SpeechTemplate, TtsEngine};
+
+/// Reads commands from stdin, one per line.
+///
+/// This runs in a separate thread since reading stdin blocks.
+fn read_commands(commands: futures_channel::mpsc::UnboundedSender<Control>) {
+    use io::BufRead;
+
+    for line in io::stdin().lock().lines() {
+        let command = match line.as_deref().map(str::trim) {
+            Ok("skip") | Ok("s") => Control::Skip,
+            Ok("abort") | Ok("q") => Control::Abort,
+            Ok("") => continue,
+            Ok(other) => {
+                eprintln!("Unknown command '{}', use 'skip' or 'abort'", other);
+                continue;
+            },
+            Err(error) => {
+                eprintln!("Failed to read command: {}", error);
+                break;
+            },
+        };
+        if commands.unbounded_send(command).is_err() {
+            break;
         }
-        cmd.arg("-w");
-        cmd.arg(&tmp_file);
-        cmd.arg(&text);
-        eprintln!("{:?}", cmd);
-        let mut child = cmd.spawn()?;
-        child.wait()?;
-        let mut cmd = std::process::Command::new("aplay");
-        cmd.arg(&tmp_file);
-        let mut child = cmd.spawn()?;
-        child.wait()?;
-        Ok(())
     }
 }
 
@@ -100,34 +34,68 @@ async fn main() -> Result<(), zomato::Error> {
     #[cfg(feature = "debug-log")]
     simple_logger::init().unwrap();
 
-    let mut args = std::env::args().fuse();
-    args.next();
+    let mut template = SpeechTemplate::default();
+    let mut output = None;
+    let mut reservations: Arc<dyn ReservationProvider> = Arc::new(NoReservations);
+    let mut more_restaurants = Vec::new();
+    let mut positional = Vec::new();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match &*arg {
+            "--intro" => template.intro = Some(args.next().expect("missing value of --intro")),
+            "--template" => template.item = args.next().expect("missing value of --template"),
+            "--no-prices" => template.skip_prices = true,
+            "--announce-date" => template.announce_date = true,
+            "--pause" => template.pause_between_dishes = true,
+            "--output" => output = Some(std::path::PathBuf::from(args.next().expect("missing value of --output"))),
+            "--reservations" => reservations = Arc::new(WebhookReservations::new(args.next().expect("missing value of --reservations"))),
+            "--also" => {
+                let value = args.next().expect("missing value of --also");
+                let (city, restaurant) = value.split_once('/').expect("--also expects city/restaurant");
+                more_restaurants.push((city.to_owned(), restaurant.to_owned()));
+            },
+            _ => positional.push(arg),
+        }
+    }
+
+    let mut args = positional.into_iter().fuse();
     let city = args.next().expect("missing city, restaurant, and text-to-speech engine");
     let restaurant = args.next().expect("missing restaurant and text-to-speech engine");
     let tts_engine = args.next().expect("missing text-to-speech engine");
 
-    let engine: Box<dyn TtsEngine> = match &*tts_engine {
-        "festival" => Box::new(Festival { language: args.next() }),
+    let engine: Arc<dyn TtsEngine + Send + Sync> = match &*tts_engine {
+        "festival" => Arc::new(Festival { language: args.next() }),
         "espeak" => {
             let language = args.next();
             let speed = args.next();
-            Box::new(Espeak { language, speed })
+            Arc::new(Espeak { language, speed })
         },
-        "pico2wave" => Box::new(Pico2Wave { language: args.next() }),
+        "pico2wave" => Arc::new(Pico2Wave { language: args.next() }),
         x => {
             eprintln!("Unknown text-to-speech engine: '{}'", x);
             std::process::exit(1);
         },
     };
 
-    if let Some(today) = zomato::get_daily_menu(&city, &restaurant).await?.into_iter().next() {
-        engine.speak(&mut |writer| {
-            for food in &today.items {
-                write!(writer, "{} {} ", food.description, food.price)?;
-            }
-            Ok(())
-        })
-        .expect("Failed to speak");
+    let mut briefing = Briefing::new(template)
+        .restaurant(city, restaurant)
+        .reservations(reservations);
+    for (city, restaurant) in more_restaurants {
+        briefing = briefing.restaurant(city, restaurant);
+    }
+
+    let client = zomato::Client::new()?;
+    let failures = match &output {
+        // Recording can't be interrupted, so the whole briefing goes into a single file
+        Some(path) => briefing.record(&client, &*engine, path).await?,
+        None => {
+            let (command_sender, commands) = futures_channel::mpsc::unbounded();
+            std::thread::spawn(move || read_commands(command_sender));
+            briefing.speak(&client, engine, commands).await?
+        },
+    };
+    for failure in failures {
+        eprintln!("Failed to fetch menu of {}/{}: {}", failure.city, failure.restaurant, failure.error);
     }
     Ok(())
 }
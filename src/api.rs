@@ -0,0 +1,317 @@
+//! Client of the official Zomato API for users with a key from developers.zomato.com.
+//!
+//! The API identifies restaurants and cities by numeric IDs instead of the names used in the URLs
+//! of the pages, but it returns the same [`Menu`] and [`RestaurantInfo`] types as [`Client`], so
+//! code processing the menus doesn't care where they came from. Restaurants found using the API
+//! carry a [`RestaurantSummary`] too, so switching to scraping only requires changing the calls.
+//!
+//! The requests go through a [`Client`], so rate limits, timeouts, proxies and custom transports
+//! work the same way as when scraping.
+//!
+//! ```
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), zomato::Error> {
+//! use zomato::api::ApiClient;
+//! use zomato::testing::MockTransport;
+//!
+//! let response = r#"{"daily_menus": [{"daily_menu": {
+//!     "start_date": "2026-10-15 11:00:00",
+//!     "dishes": [{"dish": {"name": "Hovädzí vývar (1,3,9)", "price": "1,20 €"}}]
+//! }}]}"#;
+//! let transport = MockTransport::new().with_page("https://developers.zomato.com/api/v2.1/dailymenu?res_id=16508052", response);
+//! // let client = ApiClient::new("your key")?;
+//! let client = ApiClient::from_client(ApiClient::builder("your key").transport(transport).build()?);
+//! let menus = client.get_daily_menu(16508052).await?;
+//! assert_eq!(menus[0].date, "2026-10-15");
+//! assert_eq!(menus[0].items[0].allergens, [1, 3, 9]);
+//! # Ok(())
+//! # }
+//! ```
+
+use anyhow::Context;
+use serde_derive::Deserialize;
+use crate::client::status_error;
+use crate::search::{parse_restaurant_path, RestaurantSummary};
+use crate::{Client, ClientBuilder, Error, Menu, Parser, RestaurantInfo};
+
+/// Base URL of the official API used by default.
+pub const DEFAULT_API_URL: &str = "https://developers.zomato.com/api/v2.1";
+
+/// Message the API responds with when the restaurant didn't publish any daily menu.
+const NO_DAILY_MENU: &str = "No Daily Menu Available";
+
+/// Error returned by the API.
+///
+/// Obtain it from [`Error::api_error`](crate::Error::api_error), e.g. to detect invalid key or
+/// exhausted daily limit of requests.
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("the API returned error {code}: {message}")]
+#[non_exhaustive]
+pub struct ApiError {
+    /// Code of the error, same as the HTTP status.
+    pub code: u16,
+    /// Human-readable description of the error, e.g. "Invalid API Key".
+    pub message: String,
+}
+
+/// Restaurant returned by the API.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct ApiRestaurant {
+    /// ID of the restaurant, pass it to [`ApiClient::get_daily_menu`].
+    pub id: u32,
+    /// City and restaurant names used in the URLs of pages, pass them to
+    /// [`Client::get_daily_menu`].
+    pub summary: RestaurantSummary,
+    /// Basic information about the restaurant.
+    pub info: RestaurantInfo,
+}
+
+/// Client of the official API.
+///
+/// Cloning the client is cheap and clones share the connections and the rate limit.
+#[derive(Clone)]
+pub struct ApiClient {
+    client: Client,
+}
+
+impl ApiClient {
+    /// Creates client sending given key with default settings.
+    pub fn new(key: &str) -> Result<Self, Error> {
+        Self::builder(key).build().map(Self::from_client)
+    }
+
+    /// Returns builder configured to send requests to [`DEFAULT_API_URL`] with given key.
+    ///
+    /// The key is sent in a header, so it's not sent by custom transports.
+    pub fn builder(key: &str) -> ClientBuilder {
+        Client::builder()
+            .base_url(DEFAULT_API_URL)
+            .header("Accept", "application/json")
+            .header("user-key", key)
+    }
+
+    /// Wraps the client, which has to be created by a builder returned from
+    /// [`builder`](Self::builder).
+    pub fn from_client(client: Client) -> Self {
+        ApiClient {
+            client,
+        }
+    }
+
+    /// Returns the wrapped client.
+    pub fn as_client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Fetches daily menus of the restaurant with given ID.
+    ///
+    /// The dates of the menus are in `YYYY-MM-DD` format, which is understood by
+    /// [`MaxMenuAge`](crate::MaxMenuAge). Restaurants which didn't publish any menu return an
+    /// empty list.
+    pub async fn get_daily_menu(&self, restaurant_id: u32) -> Result<Vec<Menu>, Error> {
+        self.get_daily_menu_with_parser(restaurant_id, &mut Parser::new()).await
+    }
+
+    /// Fetches daily menus of the restaurant using custom parser.
+    ///
+    /// The API doesn't return any unknown sections, so only the settings of the parser are used.
+    pub async fn get_daily_menu_with_parser(&self, restaurant_id: u32, parser: &mut Parser<'_>) -> Result<Vec<Menu>, Error> {
+        self.get_daily_menu_internal(restaurant_id, parser).await.map_err(Error)
+    }
+
+    async fn get_daily_menu_internal(&self, restaurant_id: u32, parser: &mut Parser<'_>) -> Result<Vec<Menu>, anyhow::Error> {
+        let response = match self.request::<DailyMenus>("dailymenu", &[("res_id", &restaurant_id.to_string())]).await {
+            Ok(response) => response,
+            Err(error) if error.downcast_ref::<ApiError>().is_some_and(|error| error.message == NO_DAILY_MENU) => return Ok(Vec::new()),
+            Err(error) => return Err(error),
+        };
+        let menus = response
+            .daily_menus
+            .into_iter()
+            .map(|menu| {
+                let menu = menu.daily_menu;
+                Menu {
+                    // "2026-10-15 11:00:00"
+                    date: menu.start_date.split_whitespace().next().unwrap_or_default().to_owned(),
+                    items: menu.dishes.into_iter().map(|dish| parser.menu_item(dish.dish.name, dish.dish.price)).collect(),
+                    ocr: false,
+                    valid_from: None,
+                    valid_to: None,
+                    is_current: false,
+                }
+            })
+            .collect();
+        Ok(menus)
+    }
+
+    /// Fetches the restaurant with given ID.
+    ///
+    /// ```
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), zomato::Error> {
+    /// use zomato::api::ApiClient;
+    /// use zomato::testing::MockTransport;
+    ///
+    /// let response = r#"{
+    ///     "id": "16508052",
+    ///     "name": "U Zlatého Bažanta",
+    ///     "url": "https://www.zomato.com/bratislava/u-zlateho-bazanta?utm_source=api_basic_user",
+    ///     "cuisines": "Slovak, International",
+    ///     "user_rating": { "aggregate_rating": "4.1", "votes": "120" }
+    /// }"#;
+    /// let transport = MockTransport::new().with_page("https://developers.zomato.com/api/v2.1/restaurant?res_id=16508052", response);
+    /// let client = ApiClient::from_client(ApiClient::builder("your key").transport(transport).build()?);
+    /// let restaurant = client.restaurant(16508052).await?;
+    /// assert_eq!(restaurant.summary.restaurant, "u-zlateho-bazanta");
+    /// assert_eq!(restaurant.info.cuisines, ["Slovak", "International"]);
+    /// assert_eq!(restaurant.info.votes, Some(120));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn restaurant(&self, restaurant_id: u32) -> Result<ApiRestaurant, Error> {
+        self.restaurant_internal(restaurant_id).await.map_err(Error)
+    }
+
+    async fn restaurant_internal(&self, restaurant_id: u32) -> Result<ApiRestaurant, anyhow::Error> {
+        let restaurant = self.request::<serde_json::Value>("restaurant", &[("res_id", &restaurant_id.to_string())]).await?;
+        parse_restaurant(&restaurant).ok_or_else(|| anyhow::anyhow!("invalid restaurant {}", restaurant_id))
+    }
+
+    /// Searches for restaurants in the city with given ID.
+    ///
+    /// Returns at most 20 best matching restaurants, the entries the crate doesn't understand are
+    /// skipped.
+    pub async fn search(&self, city_id: u32, query: &str) -> Result<Vec<ApiRestaurant>, Error> {
+        self.search_internal(city_id, query).await.map_err(Error)
+    }
+
+    async fn search_internal(&self, city_id: u32, query: &str) -> Result<Vec<ApiRestaurant>, anyhow::Error> {
+        let params = [("entity_id", &*city_id.to_string()), ("entity_type", "city"), ("q", query)];
+        let results = self.request::<serde_json::Value>("search", &params).await?;
+        let restaurants = results["restaurants"]
+            .as_array()
+            .map(|restaurants| restaurants.iter().filter_map(|result| parse_restaurant(&result["restaurant"])).collect())
+            .unwrap_or_default();
+        Ok(restaurants)
+    }
+
+    /// Finds the ID of the city with given name, `None` if there's no such city.
+    ///
+    /// ```
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), zomato::Error> {
+    /// use zomato::api::ApiClient;
+    /// use zomato::testing::MockTransport;
+    ///
+    /// let transport = MockTransport::new()
+    ///     .with_page("https://developers.zomato.com/api/v2.1/cities?q=bratislava", r#"{"location_suggestions": [{"id": 111, "name": "Bratislava"}]}"#)
+    ///     .with_page("https://developers.zomato.com/api/v2.1/search?entity_id=111&entity_type=city&q=lunch", r#"{"restaurants": []}"#);
+    /// let client = ApiClient::from_client(ApiClient::builder("your key").transport(transport).build()?);
+    /// let city_id = client.city_id("bratislava").await?.unwrap();
+    /// assert!(client.search(city_id, "lunch").await?.is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn city_id(&self, name: &str) -> Result<Option<u32>, Error> {
+        self.city_id_internal(name).await.map_err(Error)
+    }
+
+    async fn city_id_internal(&self, name: &str) -> Result<Option<u32>, anyhow::Error> {
+        let cities = self.request::<Cities>("cities", &[("q", name)]).await?;
+        Ok(cities.location_suggestions.first().map(|city| city.id))
+    }
+
+    /// Requests the endpoint, turning the errors described in the response into [`ApiError`].
+    async fn request<T: serde::de::DeserializeOwned>(&self, endpoint: &str, params: &[(&str, &str)]) -> Result<T, anyhow::Error> {
+        let url = url::Url::parse_with_params(&format!("{}/{}", self.client.base_url(), endpoint), params)?;
+        let response = self.client.fetch_response(url.as_str()).await?;
+        if !response.is_success() {
+            return Err(match serde_json::from_str::<ErrorResponse>(&response.body) {
+                Ok(error) => ApiError { code: response.status, message: error.message }.into(),
+                Err(_) => status_error(&response, url.as_str()),
+            });
+        }
+        serde_json::from_str(&response.body).with_context(|| format!("invalid response of the API endpoint {}", endpoint))
+    }
+}
+
+fn parse_restaurant(restaurant: &serde_json::Value) -> Option<ApiRestaurant> {
+    // Numbers are sometimes sent as strings
+    fn number(value: &serde_json::Value) -> Option<f64> {
+        value.as_f64().or_else(|| value.as_str()?.trim().parse().ok())
+    }
+
+    let id = number(&restaurant["id"])? as u32;
+    let name = restaurant["name"].as_str()?.to_owned();
+    let url = url::Url::parse(restaurant["url"].as_str()?).ok()?;
+    let (city, slug) = parse_restaurant_path(url.path())?;
+    let cuisines = restaurant["cuisines"]
+        .as_str()
+        .map(|cuisines| cuisines.split(',').map(str::trim).filter(|cuisine| !cuisine.is_empty()).map(ToOwned::to_owned).collect())
+        .unwrap_or_default();
+
+    Some(ApiRestaurant {
+        id,
+        summary: RestaurantSummary {
+            city: city.to_owned(),
+            restaurant: slug.to_owned(),
+            name: name.clone(),
+            distance: None,
+        },
+        info: RestaurantInfo {
+            name,
+            rating: number(&restaurant["user_rating"]["aggregate_rating"]).map(|rating| rating as f32),
+            votes: number(&restaurant["user_rating"]["votes"]).map(|votes| votes as u32),
+            opening_hours: restaurant["timings"].as_str().map(ToOwned::to_owned),
+            cuisines,
+        },
+    })
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct DailyMenus {
+    #[serde(default)]
+    daily_menus: Vec<DailyMenuWrapper>,
+}
+
+#[derive(Deserialize)]
+struct DailyMenuWrapper {
+    daily_menu: DailyMenu,
+}
+
+#[derive(Deserialize)]
+struct DailyMenu {
+    #[serde(default)]
+    start_date: String,
+    #[serde(default)]
+    dishes: Vec<DishWrapper>,
+}
+
+#[derive(Deserialize)]
+struct DishWrapper {
+    dish: Dish,
+}
+
+#[derive(Deserialize)]
+struct Dish {
+    name: String,
+    #[serde(default)]
+    price: String,
+}
+
+#[derive(Deserialize)]
+struct Cities {
+    #[serde(default)]
+    location_suggestions: Vec<City>,
+}
+
+#[derive(Deserialize)]
+struct City {
+    id: u32,
+}
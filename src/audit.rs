@@ -0,0 +1,138 @@
+//! Audit log of outbound requests.
+//!
+//! Some organizations require tools scraping third-party sites to keep a record of every request
+//! they make. Wrap the transport of the client in [`AuditedTransport`] to append a JSON line
+//! describing each request into a file:
+//!
+//! ```json
+//! {"timestamp":"2020-06-01T10:15:00Z","url":"https://www.zomato.com/...","status":200,"bytes":183274}
+//! ```
+//!
+//! Requests which failed before receiving a response have `null` status and contain `error`
+//! field instead.
+//!
+//! ```no_run
+//! use zomato::audit::{AuditLog, AuditedTransport};
+//! use zomato::transport::HttpTransport;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let log = AuditLog::open("requests.jsonl")?;
+//! let client = zomato::Client::with_transport(AuditedTransport::new(HttpTransport::new()?, log));
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::SystemTime;
+use serde_derive::Serialize;
+use crate::timestamp;
+use crate::transport::{BytesFuture, Response, Transport, TransportError, TransportFuture};
+
+/// Append-only log file.
+pub struct AuditLog {
+    file: Mutex<File>,
+}
+
+impl AuditLog {
+    /// Opens the log at given path for appending, creating it if it doesn't exist.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(path)?;
+
+        Ok(AuditLog {
+            file: Mutex::new(file),
+        })
+    }
+
+    fn append(&self, entry: &Entry) -> io::Result<()> {
+        let mut line = serde_json::to_vec(entry).map_err(io::Error::other)?;
+        line.push(b'\n');
+        let mut file = self.file.lock().unwrap_or_else(|error| error.into_inner());
+        // Single write call so that lines aren't interleaved.
+        file.write_all(&line)?;
+        file.flush()
+    }
+}
+
+#[derive(Serialize)]
+struct Entry<'a> {
+    timestamp: String,
+    url: &'a str,
+    status: Option<u16>,
+    bytes: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Transport recording all requests made by the inner transport into [`AuditLog`].
+///
+/// If the entry can't be written the request fails, so that no request goes unrecorded.
+pub struct AuditedTransport<T> {
+    inner: T,
+    log: AuditLog,
+}
+
+impl<T: Transport> AuditedTransport<T> {
+    /// Wraps the transport.
+    pub fn new(inner: T, log: AuditLog) -> Self {
+        AuditedTransport {
+            inner,
+            log,
+        }
+    }
+
+    /// Records the result of the request into the log.
+    fn record<B: AsRef<[u8]>>(&self, timestamp: String, url: &str, result: &Result<Response<B>, TransportError>) -> io::Result<()> {
+        let entry = match result {
+            Ok(response) => Entry {
+                timestamp,
+                url,
+                status: Some(response.status),
+                bytes: Some(response.body.as_ref().len()),
+                error: None,
+            },
+            Err(error) => Entry {
+                timestamp,
+                url,
+                status: None,
+                bytes: None,
+                error: Some(error.to_string()),
+            },
+        };
+        self.log.append(&entry)
+    }
+}
+
+impl<T: Transport> Transport for AuditedTransport<T> {
+    fn get<'a>(&'a self, url: &'a str) -> TransportFuture<'a> {
+        Box::pin(async move {
+            let timestamp = timestamp::format(SystemTime::now());
+            let result = self.inner.get(url).await;
+            self.record(timestamp, url, &result)?;
+            result
+        })
+    }
+
+    fn get_daily_menu_page<'a>(&'a self, url: &'a str) -> TransportFuture<'a> {
+        Box::pin(async move {
+            let timestamp = timestamp::format(SystemTime::now());
+            let result = self.inner.get_daily_menu_page(url).await;
+            self.record(timestamp, url, &result)?;
+            result
+        })
+    }
+
+    fn get_bytes<'a>(&'a self, url: &'a str, limit: usize) -> BytesFuture<'a> {
+        Box::pin(async move {
+            let timestamp = timestamp::format(SystemTime::now());
+            let result = self.inner.get_bytes(url, limit).await;
+            self.record(timestamp, url, &result)?;
+            result
+        })
+    }
+}
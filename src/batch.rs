@@ -0,0 +1,48 @@
+use crate::transport::TimeoutError;
+use crate::{Error, RestaurantRef};
+
+/// Result of an operation fetching information about multiple restaurants.
+///
+/// A failure of one restaurant doesn't discard the results of the others, so the outcome contains
+/// both successful results and failures. Restaurants which didn't finish before the deadline of
+/// the operation are listed separately. Use [`into_result`](Self::into_result) if you want to
+/// treat any failure or timeout as fatal.
+pub struct BatchOutcome<T> {
+    /// Successfully fetched results, ordered as documented by the operation.
+    pub ok: Vec<T>,
+    /// Restaurants which failed, in the order they were requested.
+    pub failed: Vec<BatchFailure>,
+    /// Restaurants which didn't finish before the deadline, in the order they were requested.
+    pub timed_out: Vec<RestaurantRef>,
+}
+
+/// Restaurant for which the batch operation failed.
+pub struct BatchFailure {
+    /// City in which the restaurant is located.
+    pub city: String,
+    /// Name of the restaurant as used in the URL.
+    pub restaurant: String,
+    /// The reason of the failure.
+    pub error: Error,
+}
+
+impl<T> BatchOutcome<T> {
+    /// Returns `true` if nothing failed or timed out.
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty() && self.timed_out.is_empty()
+    }
+
+    /// Returns the successful results if nothing failed or timed out.
+    ///
+    /// Otherwise returns the first failure or, if there is none, an error of
+    /// [`ErrorKind::Timeout`](crate::ErrorKind::Timeout).
+    pub fn into_result(self) -> Result<Vec<T>, Error> {
+        if let Some(failure) = self.failed.into_iter().next() {
+            return Err(failure.error);
+        }
+        match self.timed_out.into_iter().next() {
+            Some(restaurant) => Err(Error(TimeoutError::Deadline(restaurant).into())),
+            None => Ok(self.ok),
+        }
+    }
+}
@@ -0,0 +1,21 @@
+//! Pages used by maintainers to measure parsing performance.
+//!
+//! The pages are synthetic but mimic the structure of real Zomato daily menu pages. Parse them
+//! using [`parse_daily_menu`](crate::parse_daily_menu) to reproduce the numbers in your
+//! environment (see `examples/bench-parse.rs`).
+
+/// A page containing only the preloaded state with a single day and three dishes.
+pub const SMALL: &str = crate::testing::FIXTURE;
+
+/// A page with five days, six dishes each, and a moderate amount of unrelated markup.
+pub const MEDIUM: &str = include_str!("../fixtures/medium.html");
+
+/// A page with a week of long menus and roughly as much unrelated markup as a real page.
+pub const HUGE: &str = include_str!("../fixtures/huge.html");
+
+/// All fixtures along with their names, ordered from the smallest.
+pub const ALL: &[(&str, &str)] = &[
+    ("small", SMALL),
+    ("medium", MEDIUM),
+    ("huge", HUGE),
+];
@@ -0,0 +1,127 @@
+//! Synchronous API for programs which don't use async.
+//!
+//! Available with the `ureq` feature. The requests are made using
+//! [`UreqTransport`](crate::transport::UreqTransport), so neither tokio nor reqwest is needed and
+//! the crate can be built with `default-features = false, features = ["ureq"]` for small binaries
+//! fetching a menu once, e.g. from cron. Rate limits and timeouts of the client work the same way
+//! as in the async API.
+//!
+//! The functions block the current thread, so they must not be called inside an async runtime.
+//!
+//! ```
+//! use zomato::testing::{MockTransport, FIXTURE};
+//!
+//! // without a custom transport the requests are made using ureq
+//! let client = zomato::Client::builder()
+//!     .transport(MockTransport::serving(FIXTURE))
+//!     .build_blocking()?;
+//! let menus = client.get_daily_menu("bratislava", "u-zlateho-bazanta")?;
+//! assert_eq!(menus[0].date, "Today");
+//! # Ok::<_, zomato::Error>(())
+//! ```
+
+use futures_executor::block_on;
+use crate::directory::City;
+use crate::photos::Photo;
+use crate::reviews::Review;
+use crate::search::{PageCursor, RestaurantSummary, SearchPage};
+use crate::{Error, Menu, Parser, RestaurantRef};
+
+/// Client blocking the current thread until the response is received.
+///
+/// Create it using [`Client::new`] or [`ClientBuilder::build_blocking`](crate::ClientBuilder::build_blocking).
+/// Cloning the client is cheap and clones share the connections and the rate limit.
+#[derive(Clone)]
+pub struct Client {
+    inner: crate::Client,
+}
+
+impl Client {
+    /// Creates client with default settings.
+    pub fn new() -> Result<Self, Error> {
+        crate::Client::builder().build_blocking()
+    }
+
+    /// Wraps the async client.
+    ///
+    /// Its transport must not need an async runtime, e.g. the default
+    /// [`HttpTransport`](crate::transport::HttpTransport) needs tokio.
+    pub fn from_async(client: crate::Client) -> Self {
+        Client {
+            inner: client,
+        }
+    }
+
+    /// Returns the wrapped async client.
+    pub fn as_async(&self) -> &crate::Client {
+        &self.inner
+    }
+
+    /// Fetches daily menu of given restaurant, see [`crate::Client::get_daily_menu`].
+    pub fn get_daily_menu(&self, city: &str, restaurant: &str) -> Result<Vec<Menu>, Error> {
+        block_on(self.inner.get_daily_menu(city, restaurant))
+    }
+
+    /// Fetches daily menu of given restaurant using custom parser.
+    pub fn get_daily_menu_with_parser(&self, city: &str, restaurant: &str, parser: &mut Parser<'_>) -> Result<Vec<Menu>, Error> {
+        block_on(self.inner.get_daily_menu_with_parser(city, restaurant, parser))
+    }
+
+    /// Fetches reviews of the restaurant, see [`crate::Client::get_reviews`].
+    pub fn get_reviews(&self, city: &str, restaurant: &str, pages: u32) -> Result<Vec<Review>, Error> {
+        block_on(self.inner.get_reviews(city, restaurant, pages))
+    }
+
+    /// Fetches photos of the restaurant, see [`crate::Client::get_photos`].
+    pub fn get_photos(&self, city: &str, restaurant: &str) -> Result<Vec<Photo>, Error> {
+        block_on(self.inner.get_photos(city, restaurant))
+    }
+
+    /// Fetches photos of the daily menu, see [`crate::Client::get_daily_menu_photos`].
+    pub fn get_daily_menu_photos(&self, city: &str, restaurant: &str) -> Result<Vec<Photo>, Error> {
+        block_on(self.inner.get_daily_menu_photos(city, restaurant))
+    }
+
+    /// Searches for restaurants in given city, see [`crate::Client::search`].
+    pub fn search(&self, city: &str, query: &str) -> Result<SearchPage, Error> {
+        block_on(self.inner.search(city, query))
+    }
+
+    /// Fetches the page of results the cursor points to.
+    pub fn next_page(&self, cursor: &PageCursor) -> Result<SearchPage, Error> {
+        block_on(self.inner.next_page(cursor))
+    }
+
+    /// Finds restaurants with names similar to given one, see [`crate::Client::resolve_restaurant`].
+    pub fn resolve_restaurant(&self, city: &str, name: &str) -> Result<Vec<RestaurantRef>, Error> {
+        block_on(self.inner.resolve_restaurant(city, name))
+    }
+
+    /// Lists the restaurants publishing daily menus, see [`crate::Client::daily_menu_restaurants`].
+    pub fn daily_menu_restaurants(&self, city: &str) -> Result<Vec<RestaurantSummary>, Error> {
+        block_on(self.inner.daily_menu_restaurants(city))
+    }
+
+    /// Lists the restaurants near the coordinates, see [`crate::Client::nearby_restaurants`].
+    pub fn nearby_restaurants(&self, latitude: f64, longitude: f64, radius: u32) -> Result<Vec<RestaurantSummary>, Error> {
+        block_on(self.inner.nearby_restaurants(latitude, longitude, radius))
+    }
+
+    /// Lists the cities in given country, see [`crate::Client::list_cities`].
+    pub fn list_cities(&self, country: &str) -> Result<Vec<City>, Error> {
+        block_on(self.inner.list_cities(country))
+    }
+
+    /// Checks that the city is known, see [`crate::Client::validate_city`].
+    pub fn validate_city(&self, slug: &str) -> Result<City, Error> {
+        block_on(self.inner.validate_city(slug))
+    }
+}
+
+/// Fetches daily menu of given restaurant.
+///
+/// This creates a new [`Client`] for each call, consider reusing the client if you make multiple
+/// requests.
+pub fn get_daily_menu(city: &str, restaurant: &str) -> Result<Vec<Menu>, Error> {
+    Client::new()?.get_daily_menu(city, restaurant)
+}
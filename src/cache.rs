@@ -0,0 +1,116 @@
+//! Compact on-disk cache of parsed menus.
+//!
+//! Menus are stored in the [postcard](https://docs.rs/postcard) binary format which is
+//! considerably faster to load than JSON, so that a long-running process with hundreds of
+//! restaurants can start quickly. Run
+//! `cargo run --release --features bench-fixtures,binary-cache --example bench-cache` to compare.
+//!
+//! The format is internal to this crate and may change between versions, incompatible files are
+//! treated as missing.
+//!
+//! ```
+//! use zomato::cache::DiskCache;
+//!
+//! # let dir = mktemp::Temp::new_dir()?;
+//! let cache = DiskCache::open(dir.as_path())?;
+//! let menus = zomato::parse_daily_menu(zomato::testing::FIXTURE)?;
+//! cache.store("bratislava", "u-zlateho-bazanta", &menus)?;
+//!
+//! let (_fetched_at, cached) = cache.load("bratislava", "u-zlateho-bazanta").expect("just stored");
+//! assert_eq!(cached[0].items[1].price, menus[0].items[1].price);
+//! # Ok::<_, Box<dyn std::error::Error>>(())
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use serde_derive::{Deserialize, Serialize};
+use crate::{Error, Menu};
+
+/// Identifies files written by this version of the crate.
+const MAGIC: &[u8; 8] = b"zomato\x00\x06";
+
+#[derive(Serialize)]
+struct Entry<'a> {
+    fetched_at: u64,
+    menus: &'a [Menu],
+}
+
+#[derive(Deserialize)]
+struct OwnedEntry {
+    fetched_at: u64,
+    menus: Vec<Menu>,
+}
+
+/// Encodes the menus fetched at given time into the cache format.
+pub fn encode(menus: &[Menu], fetched_at: SystemTime) -> Vec<u8> {
+    let entry = Entry {
+        fetched_at: fetched_at.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs(),
+        menus,
+    };
+    let mut bytes = MAGIC.to_vec();
+    bytes.extend(postcard::to_stdvec(&entry).expect("menus are always serializable"));
+    bytes
+}
+
+/// Decodes the menus and the time they were fetched.
+///
+/// Returns `None` if the data is corrupted or written by an incompatible version.
+pub fn decode(bytes: &[u8]) -> Option<(SystemTime, Vec<Menu>)> {
+    let entry = postcard::from_bytes::<OwnedEntry>(bytes.strip_prefix(MAGIC)?).ok()?;
+    Some((UNIX_EPOCH + Duration::from_secs(entry.fetched_at), entry.menus))
+}
+
+/// Directory containing cached menus, one file per restaurant in a directory per city.
+pub struct DiskCache {
+    dir: PathBuf,
+}
+
+impl DiskCache {
+    /// Opens the cache in given directory, creating it if it doesn't exist.
+    pub fn open<P: AsRef<Path>>(dir: P) -> Result<Self, Error> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir).map_err(|error| Error(anyhow::Error::from(error).context(format!("failed to create cache directory {}", dir.display()))))?;
+        Ok(DiskCache {
+            dir: dir.to_owned(),
+        })
+    }
+
+    /// Loads the menus of the restaurant and the time they were fetched.
+    ///
+    /// Returns `None` if the menus are not cached or the file can't be read.
+    pub fn load(&self, city: &str, restaurant: &str) -> Option<(SystemTime, Vec<Menu>)> {
+        decode(&std::fs::read(self.path(city, restaurant)?).ok()?)
+    }
+
+    /// Stores the menus of the restaurant fetched just now.
+    pub fn store(&self, city: &str, restaurant: &str, menus: &[Menu]) -> Result<(), Error> {
+        self.store_at(city, restaurant, menus, SystemTime::now())
+    }
+
+    /// Stores the menus of the restaurant fetched at given time.
+    ///
+    /// The file is replaced atomically so readers never see partially written menus.
+    pub fn store_at(&self, city: &str, restaurant: &str, menus: &[Menu], fetched_at: SystemTime) -> Result<(), Error> {
+        self.store_internal(city, restaurant, menus, fetched_at).map_err(Error)
+    }
+
+    fn store_internal(&self, city: &str, restaurant: &str, menus: &[Menu], fetched_at: SystemTime) -> Result<(), anyhow::Error> {
+        use anyhow::Context;
+
+        crate::policy::check_segment("city", city)?;
+        crate::policy::check_segment("restaurant", restaurant)?;
+        let path = self.path(city, restaurant).expect("segments checked above");
+        std::fs::create_dir_all(self.dir.join(city)).with_context(|| format!("failed to create directory for {}", city))?;
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, encode(menus, fetched_at)).with_context(|| format!("failed to write {}", tmp_path.display()))?;
+        std::fs::rename(&tmp_path, &path).with_context(|| format!("failed to rename {} to {}", tmp_path.display(), path.display()))?;
+        Ok(())
+    }
+
+    /// Returns the path of the file, `None` if the names could escape the directory.
+    fn path(&self, city: &str, restaurant: &str) -> Option<PathBuf> {
+        crate::policy::check_segment("city", city).ok()?;
+        crate::policy::check_segment("restaurant", restaurant).ok()?;
+        Some(self.dir.join(city).join(format!("{}.bin", restaurant)))
+    }
+}
@@ -0,0 +1,117 @@
+//! Days at which restaurants serve lunch.
+
+use std::collections::BTreeSet;
+use chrono::{Datelike, NaiveDate, Weekday};
+
+/// Weekends and holidays determining which days are business days.
+///
+/// Modules working with future days, e.g. [forecasts](crate::history::History::predict_menu) or
+/// calendar exports, should use the same calendar so that they agree on what "the next five
+/// lunch days" means. The default calendar has Saturday and Sunday as weekend and no holidays.
+///
+/// ```
+/// use chrono::{NaiveDate, Weekday};
+/// use zomato::BusinessCalendar;
+///
+/// let date = |day| NaiveDate::from_ymd_opt(2026, 12, day).unwrap();
+/// let calendar = BusinessCalendar::new().holiday(date(24)).holiday(date(25));
+/// let days = calendar.days_from(date(23)).take(3).collect::<Vec<_>>();
+/// // 26th and 27th is weekend
+/// assert_eq!(days, [date(23), date(28), date(29)]);
+///
+/// let six_days = BusinessCalendar::new().weekend([Weekday::Sun]);
+/// assert!(six_days.is_business_day(date(26)));
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BusinessCalendar {
+    /// Indexed by the number of days from Monday.
+    weekend: [bool; 7],
+    holidays: BTreeSet<NaiveDate>,
+}
+
+impl BusinessCalendar {
+    /// Creates calendar with Saturday and Sunday as weekend and no holidays.
+    pub fn new() -> Self {
+        BusinessCalendar::default()
+    }
+
+    /// Replaces the days of week which are not business days.
+    pub fn weekend(mut self, days: impl IntoIterator<Item = Weekday>) -> Self {
+        self.weekend = [false; 7];
+        for day in days {
+            self.weekend[day.num_days_from_monday() as usize] = true;
+        }
+        self
+    }
+
+    /// Adds a day which is not a business day even though it's not at weekend.
+    pub fn holiday(mut self, date: NaiveDate) -> Self {
+        self.holidays.insert(date);
+        self
+    }
+
+    /// Adds multiple holidays.
+    pub fn holidays(mut self, dates: impl IntoIterator<Item = NaiveDate>) -> Self {
+        self.holidays.extend(dates);
+        self
+    }
+
+    /// Returns `true` if the date is neither at weekend nor a holiday.
+    pub fn is_business_day(&self, date: NaiveDate) -> bool {
+        !self.weekend[date.weekday().num_days_from_monday() as usize] && !self.holidays.contains(&date)
+    }
+
+    /// Returns the business days starting at the date, including it if it's a business day.
+    ///
+    /// The iterator ends only when the range of dates is exhausted or if all days of week are
+    /// weekend, so use [`Iterator::take`] to limit it.
+    pub fn days_from(&self, date: NaiveDate) -> BusinessDays {
+        BusinessDays {
+            next: if self.weekend.iter().all(|weekend| *weekend) { None } else { Some(date) },
+            calendar: self.clone(),
+        }
+    }
+}
+
+impl Default for BusinessCalendar {
+    /// Same as [`BusinessCalendar::new`].
+    fn default() -> Self {
+        BusinessCalendar {
+            weekend: [false; 7],
+            holidays: BTreeSet::new(),
+        }
+        .weekend([Weekday::Sat, Weekday::Sun])
+    }
+}
+
+/// Returns the business days starting at the date according to the default calendar.
+///
+/// ```
+/// let monday = chrono::NaiveDate::from_ymd_opt(2026, 10, 12).unwrap();
+/// let friday = chrono::NaiveDate::from_ymd_opt(2026, 10, 16).unwrap();
+/// assert_eq!(zomato::business_days_from(monday).take(5).last(), Some(friday));
+/// ```
+pub fn business_days_from(date: NaiveDate) -> BusinessDays {
+    BusinessCalendar::new().days_from(date)
+}
+
+/// Iterator over business days returned by [`BusinessCalendar::days_from`].
+#[derive(Clone, Debug)]
+pub struct BusinessDays {
+    calendar: BusinessCalendar,
+    next: Option<NaiveDate>,
+}
+
+impl Iterator for BusinessDays {
+    type Item = NaiveDate;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let date = self.next?;
+            self.next = date.succ_opt();
+            if self.calendar.is_business_day(date) {
+                return Some(date);
+            }
+        }
+    }
+}
@@ -0,0 +1,391 @@
+//! Cookies stored across requests and runs.
+//!
+//! Zomato sets cookies remembering the location, the session and the consent, which affect the
+//! responses. A client without cookies looks like a new visitor each time and may be
+//! served consent interstitials instead of the pages. [`CookieJar`] stores the cookies like a
+//! browser does and [`CookieJar::open`] loads them from a file, so that repeated runs of the
+//! program look like a returning browser.
+//!
+//! The jar is used by the default transports, pass it to
+//! [`ClientBuilder::cookie_jar`](crate::ClientBuilder::cookie_jar).
+//!
+//! ```
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # use std::io::{BufRead, BufReader, Write};
+//! use std::sync::Arc;
+//! use zomato::cookies::CookieJar;
+//!
+//! # let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+//! # let base_url = format!("http://{}", listener.local_addr()?);
+//! # let (sender, cookies) = std::sync::mpsc::channel();
+//! # std::thread::spawn(move || {
+//! #     for stream in listener.incoming() {
+//! #         let mut stream = stream.unwrap();
+//! #         let mut reader = BufReader::new(&stream);
+//! #         let mut line = String::new();
+//! #         let mut cookie = None;
+//! #         while reader.read_line(&mut line).unwrap() > 2 {
+//! #             if let Some(value) = line.to_lowercase().strip_prefix("cookie:") {
+//! #                 cookie = Some(value.trim().to_owned());
+//! #             }
+//! #             line.clear();
+//! #         }
+//! #         sender.send(cookie).unwrap();
+//! #         let body = zomato::testing::FIXTURE;
+//! #         write!(stream, "HTTP/1.1 200 OK\r\nSet-Cookie: consent=1; Max-Age=3600; Path=/\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", body.len(), body).unwrap();
+//! #     }
+//! # });
+//! # let dir = mktemp::Temp::new_dir()?;
+//! # let path = dir.as_path().join("cookies.json");
+//! let jar = Arc::new(CookieJar::open(&path)?);
+//! let client = zomato::Client::builder()
+//!     .base_url(&base_url)
+//!     .cookie_jar(Arc::clone(&jar))
+//!     .build()?;
+//! client.get_daily_menu("bratislava", "u-zlateho-bazanta").await?;
+//! client.get_daily_menu("bratislava", "u-zlateho-bazanta").await?;
+//! assert_eq!(cookies.recv()?, None);
+//! assert_eq!(cookies.recv()?.as_deref(), Some("consent=1"));
+//!
+//! jar.save()?;
+//! assert_eq!(CookieJar::open(&path)?.len(), 1);
+//! # #[cfg(unix)]
+//! # assert_eq!(std::os::unix::fs::PermissionsExt::mode(&std::fs::metadata(&path)?.permissions()) & 0o777, 0o600);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use serde_derive::{Deserialize, Serialize};
+use url::Url;
+use crate::Error;
+
+/// Cookies received from the servers.
+///
+/// The jar is either kept in memory or backed by a file. The file is written when the jar is
+/// saved or dropped, call [`save`](Self::save) to handle the errors. Cookies which expire when
+/// the browser is closed are saved too, like browsers restoring the previous session do. On Unix
+/// the file is readable only by its owner.
+pub struct CookieJar {
+    cookies: Mutex<Vec<Cookie>>,
+    path: Option<PathBuf>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct Cookie {
+    name: String,
+    value: String,
+    domain: String,
+    /// The cookie is sent only to the host which set it, not to its subdomains.
+    host_only: bool,
+    path: String,
+    secure: bool,
+    /// Unix timestamp after which the cookie is deleted.
+    expires: Option<i64>,
+}
+
+impl Cookie {
+    /// Parses the value of `Set-Cookie` header received from given URL.
+    ///
+    /// Returns `None` if the cookie is invalid or the server isn't allowed to set it. The cookie
+    /// may be shared with other hosts only if its domain isn't a public suffix and, if
+    /// `allowed_hosts` are given, it's one of them or their parent.
+    fn parse(set_cookie: &str, url: &Url, allowed_hosts: Option<&[String]>, now: i64) -> Option<Self> {
+        let host = url.host_str()?.to_lowercase();
+        let mut attributes = set_cookie.split(';');
+        let (name, value) = attributes.next()?.split_once('=')?;
+        let name = name.trim();
+        if name.is_empty() {
+            return None;
+        }
+
+        let mut cookie = Cookie {
+            name: name.to_owned(),
+            value: value.trim().to_owned(),
+            domain: host.clone(),
+            host_only: true,
+            path: default_path(url),
+            secure: false,
+            expires: None,
+        };
+        let mut max_age = None;
+        for attribute in attributes {
+            let (key, value) = attribute.split_once('=').unwrap_or((attribute, ""));
+            let value = value.trim();
+            match &*key.trim().to_lowercase() {
+                "domain" => {
+                    let domain = value.trim_start_matches('.').to_lowercase();
+                    if !domain.is_empty() {
+                        let is_allowed = allowed_hosts.is_none_or(|hosts| hosts.iter().any(|allowed| domain_matches(&allowed.to_lowercase(), &domain)));
+                        if is_public_suffix(&domain) || !is_allowed || !domain_matches(&host, &domain) {
+                            return None;
+                        }
+                        cookie.domain = domain;
+                        cookie.host_only = false;
+                    }
+                },
+                "path" if value.starts_with('/') => cookie.path = value.to_owned(),
+                "secure" => cookie.secure = true,
+                "max-age" => max_age = value.parse::<i64>().ok(),
+                // Some servers use dashes in the date as Netscape did
+                "expires" => if let Ok(date) = chrono::DateTime::parse_from_rfc2822(&value.replace('-', " ")) {
+                    cookie.expires = Some(date.timestamp());
+                },
+                _ => (),
+            }
+        }
+        // Max-Age takes precedence over Expires
+        if let Some(max_age) = max_age {
+            cookie.expires = Some(now.saturating_add(max_age));
+        }
+        Some(cookie)
+    }
+
+    fn is_expired(&self, now: i64) -> bool {
+        self.expires.is_some_and(|expires| expires <= now)
+    }
+
+    fn matches(&self, url: &Url) -> bool {
+        let host = match url.host_str() {
+            Some(host) => host.to_lowercase(),
+            None => return false,
+        };
+        let domain_ok = if self.host_only { host == self.domain } else { domain_matches(&host, &self.domain) };
+        let path = url.path();
+        let path_ok = path == self.path || (path.starts_with(&self.path) && (self.path.ends_with('/') || path[self.path.len()..].starts_with('/')));
+        domain_ok && path_ok && (!self.secure || url.scheme() == "https")
+    }
+}
+
+/// The [Public Suffix List](https://publicsuffix.org/list/) bundled with the crate.
+const PUBLIC_SUFFIX_LIST: &str = include_str!("../data/public_suffix_list.dat");
+
+/// Checks if the domain is shared by unrelated sites, e.g. `com`, `co.uk` or `github.io`.
+///
+/// Domains in unknown top-level domains are public suffixes too if they have a single label.
+fn is_public_suffix(domain: &str) -> bool {
+    use publicsuffix::Psl;
+
+    static LIST: OnceLock<publicsuffix::List> = OnceLock::new();
+    let list = LIST.get_or_init(|| PUBLIC_SUFFIX_LIST.parse().expect("the bundled list is valid"));
+    let domain = domain.trim_end_matches('.');
+    list.suffix(domain.as_bytes()).is_some_and(|suffix| suffix.as_bytes() == domain.as_bytes())
+}
+
+/// Checks that the host is the domain or its subdomain.
+///
+/// IP addresses match only themselves.
+fn domain_matches(host: &str, domain: &str) -> bool {
+    let is_ip_address = host.parse::<std::net::IpAddr>().is_ok() || host.starts_with('[');
+    host == domain || (!is_ip_address && host.strip_suffix(domain).is_some_and(|prefix| prefix.ends_with('.')))
+}
+
+/// Creates the file readable only by the owner, since the cookies may authenticate the session.
+///
+/// The file is replaced if it exists, so that it doesn't keep permissions of a leftover file.
+fn create_private(path: &Path) -> std::io::Result<File> {
+    match std::fs::remove_file(path) {
+        Err(error) if error.kind() != std::io::ErrorKind::NotFound => return Err(error),
+        _ => (),
+    }
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+
+        options.mode(0o600);
+    }
+    options.open(path)
+}
+
+/// The directory of the requested path, used when the cookie doesn't specify its path.
+fn default_path(url: &Url) -> String {
+    match url.path().rfind('/') {
+        Some(0) | None => "/".to_owned(),
+        Some(end) => url.path()[..end].to_owned(),
+    }
+}
+
+impl CookieJar {
+    /// Creates empty jar which isn't persisted.
+    pub fn in_memory() -> Self {
+        CookieJar {
+            cookies: Default::default(),
+            path: None,
+        }
+    }
+
+    /// Loads the cookies from the file at given path, the jar is empty if it doesn't exist.
+    ///
+    /// The cookies are saved to the same file.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        Self::open_internal(path.as_ref()).map_err(Error)
+    }
+
+    fn open_internal(path: &Path) -> Result<Self, anyhow::Error> {
+        use anyhow::Context;
+
+        let cookies = match std::fs::read(path) {
+            Ok(data) => serde_json::from_slice(&data).with_context(|| format!("invalid cookies in {}", path.display()))?,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(error) => return Err(anyhow::Error::from(error).context(format!("failed to read {}", path.display()))),
+        };
+
+        Ok(CookieJar {
+            cookies: Mutex::new(cookies),
+            path: Some(path.to_owned()),
+        })
+    }
+
+    /// Writes the cookies to the file the jar was opened from.
+    ///
+    /// Expired cookies are removed. Does nothing if the jar isn't persisted.
+    pub fn save(&self) -> Result<(), Error> {
+        self.save_internal().map_err(Error)
+    }
+
+    fn save_internal(&self) -> Result<(), anyhow::Error> {
+        use anyhow::Context;
+
+        let path = match &self.path {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        let data = {
+            let mut cookies = self.lock();
+            let now = chrono::Utc::now().timestamp();
+            cookies.retain(|cookie| !cookie.is_expired(now));
+            serde_json::to_vec_pretty(&*cookies)?
+        };
+        // The file is replaced atomically so that concurrent runs don't read partial cookies
+        let mut tmp_path = path.clone().into_os_string();
+        tmp_path.push(".tmp");
+        let tmp_path = PathBuf::from(tmp_path);
+        create_private(&tmp_path)
+            .and_then(|mut file| file.write_all(&data))
+            .with_context(|| format!("failed to write {}", tmp_path.display()))?;
+        std::fs::rename(&tmp_path, path).with_context(|| format!("failed to rename {} to {}", tmp_path.display(), path.display()))?;
+        Ok(())
+    }
+
+    /// Returns the number of stored cookies, including the expired ones.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns `true` if no cookies are stored.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all cookies, e.g. to start a new session.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Stores the cookies from the values of `Set-Cookie` headers received from the URL.
+    ///
+    /// Cookies for other hosts are accepted only if they are one of the allowed hosts or their
+    /// parent.
+    pub(crate) fn store<'a>(&self, url: &Url, set_cookies: impl IntoIterator<Item = &'a str>, allowed_hosts: Option<&[String]>) {
+        let now = chrono::Utc::now().timestamp();
+        let mut cookies = self.lock();
+        for cookie in set_cookies.into_iter().filter_map(|set_cookie| Cookie::parse(set_cookie, url, allowed_hosts, now)) {
+            cookies.retain(|existing| (&existing.name, &existing.domain, &existing.path) != (&cookie.name, &cookie.domain, &cookie.path));
+            // Servers delete cookies by setting them as expired
+            if !cookie.is_expired(now) {
+                cookies.push(cookie);
+            }
+        }
+    }
+
+    /// Returns the value of `Cookie` header sent to the URL, if any cookies match it.
+    pub(crate) fn header(&self, url: &Url) -> Option<String> {
+        let now = chrono::Utc::now().timestamp();
+        let cookies = self.lock();
+        let mut matching = cookies
+            .iter()
+            .filter(|cookie| !cookie.is_expired(now) && cookie.matches(url))
+            .collect::<Vec<_>>();
+        if matching.is_empty() {
+            return None;
+        }
+        // Browsers send the more specific cookies first
+        matching.sort_by_key(|cookie| std::cmp::Reverse(cookie.path.len()));
+        let pairs = matching.iter().map(|cookie| format!("{}={}", cookie.name, cookie.value)).collect::<Vec<_>>();
+        Some(pairs.join("; "))
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Cookie>> {
+        self.cookies.lock().unwrap_or_else(|error| error.into_inner())
+    }
+}
+
+/// Doesn't show the cookies, which may authenticate the session.
+impl fmt::Debug for CookieJar {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CookieJar")
+            .field("path", &self.path)
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl Default for CookieJar {
+    fn default() -> Self {
+        Self::in_memory()
+    }
+}
+
+/// Saves the cookies, ignoring errors.
+impl Drop for CookieJar {
+    fn drop(&mut self) {
+        let _ = self.save_internal();
+    }
+}
+
+/// The jar used by a transport, accepting cookies only for the hosts the client may access.
+#[derive(Clone)]
+#[cfg_attr(not(any(feature = "reqwest", feature = "isahc", feature = "ureq")), allow(dead_code))]
+pub(crate) struct ScopedCookieJar {
+    jar: Arc<CookieJar>,
+    allowed_hosts: Option<Vec<String>>,
+}
+
+#[cfg_attr(not(any(feature = "reqwest", feature = "isahc", feature = "ureq")), allow(dead_code))]
+impl ScopedCookieJar {
+    pub(crate) fn new(jar: Arc<CookieJar>, allowed_hosts: Option<Vec<String>>) -> Self {
+        ScopedCookieJar {
+            jar,
+            allowed_hosts,
+        }
+    }
+
+    /// Stores the cookies from the values of `Set-Cookie` headers received from the URL.
+    pub(crate) fn store<'a>(&self, url: &Url, set_cookies: impl IntoIterator<Item = &'a str>) {
+        self.jar.store(url, set_cookies, self.allowed_hosts.as_deref());
+    }
+
+    /// Returns the value of `Cookie` header sent to the URL, if any cookies match it.
+    pub(crate) fn header(&self, url: &Url) -> Option<String> {
+        self.jar.header(url)
+    }
+}
+
+/// Allows `reqwest` to handle the cookies of the redirects too.
+#[cfg(all(feature = "reqwest", not(target_arch = "wasm32")))]
+impl reqwest::cookie::CookieStore for ScopedCookieJar {
+    fn set_cookies(&self, cookie_headers: &mut dyn Iterator<Item = &reqwest::header::HeaderValue>, url: &Url) {
+        self.store(url, cookie_headers.filter_map(|value| value.to_str().ok()));
+    }
+
+    fn cookies(&self, url: &Url) -> Option<reqwest::header::HeaderValue> {
+        reqwest::header::HeaderValue::from_str(&self.header(url)?).ok()
+    }
+}
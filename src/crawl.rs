@@ -0,0 +1,151 @@
+//! Crawling menus of many restaurants into [`History`].
+//!
+//! The crawler walks through search results, fetches menus of the found restaurants concurrently
+//! and passes them to a dedicated thread writing them into the history in batches. The channel
+//! between them is bounded, so if the database can't keep up fetching slows down instead of
+//! buffering unlimited amount of menus in memory.
+//!
+//! ```
+//! use zomato::crawl::{crawl, CrawlOptions};
+//! use zomato::history::History;
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), zomato::Error> {
+//! # let client = zomato::testing::search_client();
+//! let history = History::open_in_memory()?;
+//! let (history, report) = crawl(&client, "bratislava", "lunch", history, CrawlOptions::default()).await?;
+//! assert_eq!(report.stored, 3);
+//! assert!(report.failed.is_empty());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::time::SystemTime;
+use futures_util::{SinkExt, StreamExt};
+use crate::{Client, Error, Menu};
+use crate::history::{BatchWriter, History, Record};
+use crate::search::{PageCursor, RestaurantSummary};
+
+/// Configuration of the crawler.
+#[derive(Clone, Debug)]
+pub struct CrawlOptions {
+    /// Number of menus written in a single transaction.
+    pub batch_size: usize,
+    /// Maximum number of fetched menus waiting to be written.
+    pub buffer: usize,
+    /// Maximum number of menus being fetched at the same time.
+    pub concurrency: usize,
+}
+
+impl Default for CrawlOptions {
+    fn default() -> Self {
+        CrawlOptions {
+            batch_size: 64,
+            buffer: 256,
+            concurrency: 4,
+        }
+    }
+}
+
+/// Summary of the crawl.
+pub struct CrawlReport {
+    /// Number of restaurants whose menus were stored.
+    pub stored: usize,
+    /// Restaurants whose menus failed to be fetched.
+    pub failed: Vec<(RestaurantSummary, Error)>,
+    /// If the listing failed in the middle this points to the page that should be fetched next.
+    ///
+    /// Pass it to [`crawl_from`] to resume the crawl.
+    pub resume_from: Option<(PageCursor, Error)>,
+}
+
+/// Crawls menus of all restaurants found by searching for `query` in `city`.
+///
+/// The history is returned back once all the menus are written.
+pub async fn crawl(client: &Client, city: &str, query: &str, history: History, options: CrawlOptions) -> Result<(History, CrawlReport), Error> {
+    crawl_internal(client, Start::Search { city, query }, history, options).await
+}
+
+/// Resumes crawling from given page.
+pub async fn crawl_from(client: &Client, cursor: PageCursor, history: History, options: CrawlOptions) -> Result<(History, CrawlReport), Error> {
+    crawl_internal(client, Start::Cursor(cursor), history, options).await
+}
+
+enum Start<'a> {
+    Search { city: &'a str, query: &'a str },
+    Cursor(PageCursor),
+}
+
+async fn crawl_internal(client: &Client, start: Start<'_>, history: History, options: CrawlOptions) -> Result<(History, CrawlReport), Error> {
+    let mut current = match start {
+        Start::Search { city, query } => client.search(city, query).await?,
+        Start::Cursor(cursor) => client.next_page(&cursor).await?,
+    };
+
+    let (mut sender, receiver) = futures_channel::mpsc::channel::<Record>(options.buffer.max(1));
+    let batch_size = options.batch_size.max(1);
+    let writer = std::thread::spawn(move || {
+        let mut writer = BatchWriter::new(history, batch_size);
+        for record in futures_executor::block_on_stream(receiver) {
+            writer.push(record)?;
+        }
+        writer.into_inner()
+    });
+
+    let mut report = CrawlReport {
+        stored: 0,
+        failed: Vec::new(),
+        resume_from: None,
+    };
+
+    loop {
+        let mut fetches = futures_util::stream::iter(current.restaurants)
+            .map(|restaurant| async move {
+                let menus = client.get_daily_menu(&restaurant.city, &restaurant.restaurant).await;
+                (restaurant, menus, SystemTime::now())
+            })
+            .buffer_unordered(options.concurrency.max(1));
+
+        while let Some((restaurant, menus, fetched_at)) = fetches.next().await {
+            match menus {
+                Ok(menus) => {
+                    // Waits if the writer can't keep up.
+                    if sender.send(record(restaurant, menus, fetched_at)).await.is_err() {
+                        // The writer failed, its error is returned below
+                        break;
+                    }
+                    report.stored += 1;
+                },
+                Err(error) => report.failed.push((restaurant, error)),
+            }
+        }
+
+        if sender.is_closed() {
+            break;
+        }
+
+        match current.next {
+            Some(cursor) => match client.next_page(&cursor).await {
+                Ok(page) => current = page,
+                Err(error) => {
+                    report.resume_from = Some((cursor, error));
+                    break;
+                },
+            },
+            None => break,
+        }
+    }
+
+    drop(sender);
+    let history = writer.join().expect("history writer panicked")?;
+    Ok((history, report))
+}
+
+fn record(restaurant: RestaurantSummary, menus: Vec<Menu>, fetched_at: SystemTime) -> Record {
+    Record {
+        city: restaurant.city,
+        restaurant: restaurant.restaurant,
+        menus,
+        fetched_at,
+    }
+}
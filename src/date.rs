@@ -0,0 +1,189 @@
+//! Interpretation of menu dates.
+
+use chrono::{Datelike, Duration, FixedOffset, NaiveDate, Utc};
+use crate::{Lang, Menu};
+
+/// Words used in the headings of menus in given language.
+struct HeadingWords {
+    language: Lang,
+    today: &'static str,
+    tomorrow: &'static str,
+    yesterday: &'static str,
+    /// Prefixes of the names of the months, long enough to tell them apart.
+    months: [&'static str; 12],
+}
+
+const HEADING_WORDS: &[HeadingWords] = &[
+    HeadingWords {
+        language: Lang::English,
+        today: "today",
+        tomorrow: "tomorrow",
+        yesterday: "yesterday",
+        months: ["jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec"],
+    },
+    HeadingWords {
+        language: Lang::Slovak,
+        today: "dnes",
+        tomorrow: "zajtra",
+        yesterday: "včera",
+        months: ["jan", "feb", "mar", "apr", "máj", "jún", "júl", "aug", "sep", "okt", "nov", "dec"],
+    },
+    HeadingWords {
+        language: Lang::Czech,
+        today: "dnes",
+        tomorrow: "zítra",
+        yesterday: "včera",
+        // června and července differ only after "červ"
+        months: ["led", "úno", "bře", "dub", "kvě", "červn", "červen", "srp", "zář", "říj", "lis", "pro"],
+    },
+];
+
+/// Maximum accepted age of menus.
+///
+/// Zomato sometimes still serves the menu of last Friday on Monday morning, showing its date
+/// instead of "Today". The date of each menu is compared with the current date in the timezone of
+/// the restaurants so that such menus can be flagged or removed. Menus with headings which can't
+/// be interpreted as dates are never considered stale.
+///
+/// Configure it using [`ClientBuilder::max_menu_age`](crate::ClientBuilder::max_menu_age) to
+/// remove stale menus from all fetched menus.
+///
+/// ```
+/// use chrono::{Duration, NaiveDate};
+/// use zomato::MaxMenuAge;
+///
+/// let monday = NaiveDate::from_ymd_opt(2026, 10, 12).unwrap();
+/// let mut menus = zomato::parse_daily_menu(zomato::testing::FIXTURE)?;
+/// menus[0].date = "Friday, 9th October".to_owned();
+///
+/// assert!(MaxMenuAge::days(1).is_stale_on(&menus[0], monday));
+/// assert!(!MaxMenuAge::days(3).is_stale_on(&menus[0], monday));
+/// assert_eq!(zomato::menu_date(&menus[0], monday), Some(monday - Duration::days(3)));
+///
+/// menus[0].date = "Piatok, 9. októbra".to_owned();
+/// assert!(MaxMenuAge::days(1).language(zomato::Lang::Slovak).is_stale_on(&menus[0], monday));
+/// assert!(!MaxMenuAge::days(1).language(zomato::Lang::English).is_stale_on(&menus[0], monday));
+/// # Ok::<_, zomato::Error>(())
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MaxMenuAge {
+    days: u32,
+    utc_offset: Option<FixedOffset>,
+    language: Option<Lang>,
+}
+
+impl MaxMenuAge {
+    /// Accepts menus at most given number of days old.
+    ///
+    /// Zero accepts only the menus of today and the following days.
+    pub fn days(days: u32) -> Self {
+        MaxMenuAge {
+            days,
+            utc_offset: None,
+            language: None,
+        }
+    }
+
+    /// Sets the timezone of the restaurants, the local one is used by default.
+    pub fn utc_offset(mut self, offset: FixedOffset) -> Self {
+        self.utc_offset = Some(offset);
+        self
+    }
+
+    /// Sets the language of the headings of the menus.
+    ///
+    /// Headings in all supported languages are understood by default. If the client is configured
+    /// using [`ClientBuilder::accept_language`](crate::ClientBuilder::accept_language), the
+    /// language requested from Zomato is used unless this was set.
+    pub fn language(mut self, language: Lang) -> Self {
+        self.language = Some(language);
+        self
+    }
+
+    /// Sets the language if none was set.
+    pub(crate) fn or_language(self, language: Option<Lang>) -> Self {
+        MaxMenuAge {
+            language: self.language.or(language),
+            ..self
+        }
+    }
+
+    /// Returns the current date in the timezone of the restaurants.
+    pub fn today(&self) -> NaiveDate {
+        match self.utc_offset {
+            Some(offset) => Utc::now().with_timezone(&offset).date_naive(),
+            None => chrono::Local::now().date_naive(),
+        }
+    }
+
+    /// Checks if the menu is older than accepted today.
+    pub fn is_stale(&self, menu: &Menu) -> bool {
+        self.is_stale_on(menu, self.today())
+    }
+
+    /// Checks if the menu is older than accepted at given date.
+    pub fn is_stale_on(&self, menu: &Menu, today: NaiveDate) -> bool {
+        resolve_heading(&menu.date, today, self.language).is_some_and(|date| today - date > Duration::days(i64::from(self.days)))
+    }
+
+    /// Removes the menus older than accepted today.
+    pub fn retain_fresh(&self, menus: &mut Vec<Menu>) {
+        let today = self.today();
+        menus.retain(|menu| !self.is_stale_on(menu, today));
+    }
+}
+
+/// Computes the calendar date of the menu fetched at given date.
+///
+/// Headings in all supported languages are understood. Returns `None` if the heading of the menu
+/// isn't understood.
+pub fn menu_date(menu: &Menu, fetch_date: NaiveDate) -> Option<NaiveDate> {
+    resolve_heading(&menu.date, fetch_date, None)
+}
+
+/// Computes calendar date from the heading Zomato uses for the menu.
+///
+/// Understands "Today", "Tomorrow", "Yesterday", headings like "Wednesday, 14th October" and
+/// their Slovak and Czech versions, e.g. "Streda, 14. októbra". Dates like "2026-10-14" returned
+/// by the [official API](crate::api) are understood in all languages. If `language` is `None`,
+/// headings in all supported languages are understood.
+pub(crate) fn resolve_heading(heading: &str, fetch_date: NaiveDate, language: Option<Lang>) -> Option<NaiveDate> {
+    let heading = heading.trim().to_lowercase();
+    if let Ok(date) = NaiveDate::parse_from_str(&heading, "%Y-%m-%d") {
+        return Some(date);
+    }
+    HEADING_WORDS
+        .iter()
+        .filter(|words| match language {
+            Some(language) => words.language == language,
+            None => true,
+        })
+        .find_map(|words| resolve_words(&heading, fetch_date, words))
+}
+
+/// Resolves the lower-case heading using the words of a single language.
+fn resolve_words(heading: &str, fetch_date: NaiveDate, words: &HeadingWords) -> Option<NaiveDate> {
+    if heading == words.today {
+        return Some(fetch_date);
+    }
+    if heading == words.tomorrow {
+        return fetch_date.succ_opt();
+    }
+    if heading == words.yesterday {
+        return fetch_date.pred_opt();
+    }
+
+    // skip the day of week
+    let date = heading.rsplit(',').next()?;
+    let mut parts = date.split_whitespace();
+    let day = parts.next()?;
+    let day = day[..day.find(|c: char| !c.is_ascii_digit()).unwrap_or(day.len())].parse::<u32>().ok()?;
+    let month = parts.next()?;
+    let month = words.months.iter().position(|name| month.starts_with(name))? as u32 + 1;
+
+    // The year is not displayed so we pick the one closest to the time of fetching.
+    let year = fetch_date.year();
+    (year - 1..=year + 1)
+        .filter_map(|year| NaiveDate::from_ymd_opt(year, month, day))
+        .min_by_key(|date| (*date - fetch_date).num_days().abs())
+}
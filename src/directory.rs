@@ -0,0 +1,122 @@
+//! Cities in which Zomato operates.
+//!
+//! Requesting a city Zomato doesn't know results in a page without any data, which is reported
+//! as a confusing parse error. Applications can instead present a picker using
+//! [`Client::list_cities`] or check configured cities using [`Client::validate_city`] early.
+//!
+//! ```
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), zomato::Error> {
+//! use zomato::testing::{MockTransport, DIRECTORY_FIXTURE};
+//!
+//! let transport = MockTransport::new().with_page("https://www.zomato.com/directory", DIRECTORY_FIXTURE);
+//! let client = zomato::Client::with_transport(transport);
+//! let cities = client.list_cities("Slovakia").await?;
+//! assert_eq!(cities.iter().map(|city| &*city.slug).collect::<Vec<_>>(), ["bratislava", "kosice"]);
+//!
+//! assert_eq!(client.validate_city("praha").await?.name, "Praha");
+//! let error = client.validate_city("bratislva").await.err().unwrap();
+//! assert_eq!(error.to_string(), "unknown city 'bratislva', did you mean 'bratislava'?");
+//! # Ok(())
+//! # }
+//! ```
+
+use anyhow::Context;
+use crate::{Client, Error};
+
+/// Lowest similarity of a city to the unknown one for it to be suggested.
+const MIN_SUGGESTION_SIMILARITY: f64 = 0.8;
+
+/// City listed in the directory.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct City {
+    /// Name of the city as used in the URLs, pass it to [`Client::get_daily_menu`].
+    pub slug: String,
+    /// Human-readable name of the city.
+    pub name: String,
+    /// Human-readable name of the country in which the city is located.
+    pub country: String,
+}
+
+/// Error returned when the city isn't listed in the directory.
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("unknown city '{city}'{}", .suggestion.as_ref().map(|suggestion| format!(", did you mean '{}'?", suggestion)).unwrap_or_default())]
+#[non_exhaustive]
+pub struct UnknownCityError {
+    /// The rejected city.
+    pub city: String,
+    /// The most similar known city, if any is similar enough.
+    pub suggestion: Option<String>,
+}
+
+impl Client {
+    /// Lists the cities in given country, matched case-insensitively.
+    ///
+    /// The cities are listed in the order of the directory, an empty list means the country is
+    /// unknown.
+    pub async fn list_cities(&self, country: &str) -> Result<Vec<City>, Error> {
+        let mut cities = self.all_cities().await.map_err(Error)?;
+        cities.retain(|city| city.country.to_lowercase() == country.to_lowercase());
+        Ok(cities)
+    }
+
+    /// Checks that the city is listed in the directory, returning the information about it.
+    ///
+    /// The error contains [`UnknownCityError`] suggesting the most similar known city.
+    pub async fn validate_city(&self, slug: &str) -> Result<City, Error> {
+        self.validate_city_internal(slug).await.map_err(Error)
+    }
+
+    async fn validate_city_internal(&self, slug: &str) -> Result<City, anyhow::Error> {
+        let cities = self.all_cities().await?;
+        if let Some(city) = cities.iter().find(|city| city.slug == slug) {
+            return Ok(city.clone());
+        }
+        let similarity = crate::similarity::global();
+        let suggestion = cities
+            .iter()
+            .map(|city| (similarity.similarity(slug, &city.slug), city))
+            .filter(|(similarity, _)| *similarity >= MIN_SUGGESTION_SIMILARITY)
+            .max_by(|(a, _), (b, _)| a.total_cmp(b))
+            .map(|(_, city)| city.slug.clone());
+        Err(UnknownCityError { city: slug.to_owned(), suggestion }.into())
+    }
+
+    async fn all_cities(&self) -> Result<Vec<City>, anyhow::Error> {
+        let page = self.fetch(&self.directory_url()).await?;
+        parse_directory(&page, |url| self.strip_base_url(url))
+    }
+}
+
+/// Parses the directory page.
+///
+/// `strip_base_url` turns absolute URLs of cities into paths.
+fn parse_directory(html: &str, strip_base_url: impl Fn(&str) -> &str) -> Result<Vec<City>, anyhow::Error> {
+    let state = zomato_core::extract_preloaded_state(html)?;
+    let state = serde_json::from_str::<serde_json::Value>(&state).context("failed to parse json")?;
+    let countries = state["pages"]["directory"]["sections"]["SECTION_CITY_LIST"]
+        .as_array()
+        .ok_or_else(|| anyhow::anyhow!("missing list of cities"))?;
+
+    // Entries which can't be understood are skipped like in search results.
+    let mut cities = Vec::new();
+    for country in countries {
+        let country_name = match country["countryName"].as_str() {
+            Some(name) => name,
+            None => continue,
+        };
+        let country_cities = country["cities"].as_array().into_iter().flatten().filter_map(|city| {
+            let slug = strip_base_url(city["url"].as_str()?).trim_matches('/');
+            if slug.is_empty() || slug.contains('/') {
+                return None;
+            }
+            Some(City {
+                slug: slug.to_owned(),
+                name: city["name"].as_str()?.to_owned(),
+                country: country_name.to_owned(),
+            })
+        });
+        cities.extend(country_cities);
+    }
+    Ok(cities)
+}
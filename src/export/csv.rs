@@ -0,0 +1,41 @@
+//! CSV export.
+//!
+//! The menus are flattened into rows with `date`, `description`, `price` and `currency` columns,
+//! one row per dish. The first row is the header. Price and currency are empty if the price
+//! couldn't be parsed.
+//!
+//! ```
+//! use zomato::export::csv;
+//!
+//! let menus = zomato::parse_daily_menu(zomato::testing::FIXTURE)?;
+//! let mut out = Vec::new();
+//! csv::write(&menus, &mut out)?;
+//! let out = String::from_utf8(out)?;
+//! let mut lines = out.lines();
+//! assert_eq!(lines.next(), Some("date,description,price,currency"));
+//! assert_eq!(lines.next(), Some("Today,\"Hovädzí vývar s rezancami (1,3,9)\",1.20,€"));
+//! # Ok::<_, Box<dyn std::error::Error>>(())
+//! ```
+
+use std::io;
+
+/// Writes the menus as CSV.
+///
+/// The fields are quoted only if needed and the lines end with CRLF as required by RFC 4180.
+pub fn write<W: io::Write>(menus: &[crate::Menu], mut writer: W) -> io::Result<()> {
+    writer.write_all(b"date,description,price,currency\r\n")?;
+    for row in super::rows(menus) {
+        let price = row.price.map(|price| format!("{:.2}", price)).unwrap_or_default();
+        let currency = row.currency.unwrap_or_default();
+        write!(writer, "{},{},{},{}\r\n", escape(row.date), escape(row.description), price, escape(&currency))?;
+    }
+    writer.flush()
+}
+
+fn escape(field: &str) -> std::borrow::Cow<'_, str> {
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\"")).into()
+    } else {
+        field.into()
+    }
+}
@@ -0,0 +1,91 @@
+//! Atom feed export.
+//!
+//! Each menu becomes an entry of the feed, so that you can follow the lunch of your favorite
+//! restaurant in a feed reader.
+//!
+//! ```
+//! use zomato::export::feed;
+//!
+//! let menus = zomato::parse_daily_menu(zomato::testing::FIXTURE)?;
+//! let atom = feed::to_atom(&menus, "U Zlatého Bažanta");
+//! assert!(atom.contains("<title>Lunch at U Zlatého Bažanta</title>"));
+//! assert_eq!(atom.matches("<entry>").count(), 1);
+//! # Ok::<_, zomato::Error>(())
+//! ```
+
+use std::fmt::Write;
+use std::time::SystemTime;
+use chrono::{DateTime, Local, NaiveDate, NaiveTime, TimeZone, Utc};
+use crate::Menu;
+use super::html::escape;
+use super::{DatedMenu, Entry};
+
+/// Renders the menus of the restaurant as Atom feed.
+///
+/// The dates of the menus are computed from their headings assuming they were fetched today,
+/// menus with headings that can't be understood are skipped. Use [`to_atom_at`] if the menus
+/// were fetched earlier.
+pub fn to_atom(menus: &[Menu], restaurant_name: &str) -> String {
+    to_atom_at(menus, restaurant_name, Local::now().date_naive())
+}
+
+/// Renders the menus of the restaurant fetched at given date as Atom feed.
+pub fn to_atom_at(menus: &[Menu], restaurant_name: &str, fetched_on: NaiveDate) -> String {
+    let menus = super::entries(menus, fetched_on, |date| Utc.from_utc_datetime(&date.and_time(NaiveTime::MIN)).into());
+    render(&menus, restaurant_name)
+}
+
+/// Renders menus with known dates and revisions as Atom feed.
+///
+/// The entries keep their IDs across regenerations and their `updated` timestamps are the times
+/// the menus were last modified, so feed readers update corrected entries instead of showing them
+/// again. The output only depends on the input unless there are no menus, in which case the feed
+/// is marked as updated now.
+pub fn dated_to_atom(menus: &[DatedMenu], restaurant_name: &str) -> String {
+    render(&menus.iter().map(Entry::from).collect::<Vec<_>>(), restaurant_name)
+}
+
+fn render(menus: &[Entry<'_>], restaurant_name: &str) -> String {
+    let id = restaurant_name
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+        .collect::<String>();
+    let title = format!("Lunch at {}", restaurant_name);
+    let updated = menus.iter().map(|menu| menu.modified).max().unwrap_or_else(SystemTime::now);
+
+    let mut feed = String::new();
+    feed.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    feed.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    // Writing into String never fails
+    let _ = writeln!(feed, "  <title>{}</title>", escape(&title));
+    let _ = writeln!(feed, "  <id>urn:zomato-rs:{}</id>", escape(&id));
+    let _ = writeln!(feed, "  <updated>{}</updated>", format_time(updated));
+    let _ = writeln!(feed, "  <author><name>{}</name></author>", escape(restaurant_name));
+    for Entry { date, menu, modified, .. } in menus {
+        let mut content = String::from("<ul>");
+        for item in &menu.items {
+            content.push_str("<li>");
+            content.push_str(&escape(&item.description));
+            if !item.price.is_empty() {
+                content.push_str(" – ");
+                content.push_str(&escape(&item.price));
+            }
+            content.push_str("</li>");
+        }
+        content.push_str("</ul>");
+
+        feed.push_str("  <entry>\n");
+        let _ = writeln!(feed, "    <title>{}</title>", date.format("Lunch on %A, %-d %B %Y"));
+        let _ = writeln!(feed, "    <id>urn:zomato-rs:{}:{}</id>", escape(&id), date);
+        let _ = writeln!(feed, "    <updated>{}</updated>", format_time(*modified));
+        // The content is HTML, so it's escaped twice
+        let _ = writeln!(feed, "    <content type=\"html\">{}</content>", escape(&content));
+        feed.push_str("  </entry>\n");
+    }
+    feed.push_str("</feed>\n");
+    feed
+}
+
+fn format_time(time: SystemTime) -> impl std::fmt::Display {
+    DateTime::<Utc>::from(time).format("%Y-%m-%dT%H:%M:%SZ")
+}
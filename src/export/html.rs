@@ -0,0 +1,51 @@
+//! HTML export.
+//!
+//! Each menu becomes a heading followed by a table of dishes and prices. The output is a fragment
+//! without `<html>` and `<body>` so that it can be embedded into a page or an e-mail.
+//!
+//! ```
+//! use zomato::export::html;
+//!
+//! let menus = zomato::parse_daily_menu(zomato::testing::FIXTURE)?;
+//! let html = html::to_html(&menus);
+//! assert!(html.starts_with("<h2>Today</h2>\n<table>\n"));
+//! assert!(html.contains("<tr><td>Vyprážaný syr, hranolky, tatárska omáčka (1,3,7)</td><td>€6.00</td></tr>"));
+//! # Ok::<_, zomato::Error>(())
+//! ```
+
+use std::fmt::Write;
+use crate::Menu;
+
+/// Renders the menus as HTML tables.
+pub fn to_html(menus: &[Menu]) -> String {
+    let mut html = String::new();
+    for menu in menus {
+        // Writing into String never fails
+        let _ = writeln!(html, "<h2>{}</h2>", escape(&menu.date));
+        html.push_str("<table>\n");
+        html.push_str("<thead><tr><th>Dish</th><th>Price</th></tr></thead>\n");
+        html.push_str("<tbody>\n");
+        for item in &menu.items {
+            let _ = writeln!(html, "<tr><td>{}</td><td>{}</td></tr>", escape(&item.description), escape(&item.price));
+        }
+        html.push_str("</tbody>\n");
+        html.push_str("</table>\n");
+    }
+    html
+}
+
+/// Escapes the text so that it can be used in HTML or XML content and attribute values.
+pub(crate) fn escape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&apos;"),
+            c => result.push(c),
+        }
+    }
+    result
+}
@@ -0,0 +1,144 @@
+//! iCalendar (`.ics`) export.
+//!
+//! Each menu becomes an all-day event with the dishes listed in its description, so that you can
+//! subscribe to the lunch of your favorite restaurant in your calendar application.
+//!
+//! ```
+//! use zomato::export::ical;
+//!
+//! let menus = zomato::parse_daily_menu(zomato::testing::FIXTURE)?;
+//! let fetched_on = chrono::NaiveDate::from_ymd_opt(2020, 6, 1).unwrap();
+//! let calendar = ical::to_ics("U Zlatého Bažanta", &menus, fetched_on);
+//! assert!(calendar.starts_with("BEGIN:VCALENDAR\r\n"));
+//! assert!(calendar.contains("DTSTART;VALUE=DATE:20200601\r\n"));
+//! assert!(calendar.contains("SUMMARY:Lunch at U Zlatého Bažanta\r\n"));
+//! # Ok::<_, zomato::Error>(())
+//! ```
+
+use std::fmt::Write;
+use std::time::SystemTime;
+use chrono::{DateTime, NaiveDate, Utc};
+use crate::Menu;
+use super::{DatedMenu, Entry};
+
+/// Maximum length of a line in octets, excluding the line break, as required by RFC 5545.
+const MAX_LINE_LENGTH: usize = 75;
+
+/// Creates calendar containing the menus of the restaurant.
+///
+/// `fetched_on` is the date the menus were fetched at, it's needed to compute dates from
+/// headings like "Today". Menus with headings that can't be understood are skipped.
+pub fn to_ics(restaurant: &str, menus: &[Menu], fetched_on: NaiveDate) -> String {
+    let now = SystemTime::now();
+    render(restaurant, &super::entries(menus, fetched_on, |_| now))
+}
+
+/// Creates calendar containing menus with known dates and revisions.
+///
+/// The output only depends on the input, so regenerating the calendar from unchanged history
+/// produces identical file. Events keep their UIDs and get their `SEQUENCE` bumped when the menu
+/// changes, so the calendar clients update them instead of creating duplicates.
+///
+/// ```
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use zomato::export::{ical, DatedMenu};
+///
+/// let menu = zomato::parse_daily_menu(zomato::testing::FIXTURE)?.remove(0);
+/// let corrected = DatedMenu {
+///     date: chrono::NaiveDate::from_ymd_opt(2020, 6, 1).unwrap(),
+///     menu,
+///     sequence: 1,
+///     modified: UNIX_EPOCH + Duration::from_secs(1591002000),
+/// };
+/// let calendar = ical::dated_to_ics("U Zlatého Bažanta", &[corrected]);
+/// assert!(calendar.contains("SEQUENCE:1\r\n"));
+/// assert!(calendar.contains("LAST-MODIFIED:20200601T090000Z\r\n"));
+/// # Ok::<_, zomato::Error>(())
+/// ```
+pub fn dated_to_ics(restaurant: &str, menus: &[DatedMenu]) -> String {
+    render(restaurant, &menus.iter().map(Entry::from).collect::<Vec<_>>())
+}
+
+fn render(restaurant: &str, menus: &[Entry<'_>]) -> String {
+    let uid_suffix = restaurant
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+        .collect::<String>();
+
+    let mut calendar = Calendar::default();
+    calendar.line("BEGIN:VCALENDAR");
+    calendar.line("VERSION:2.0");
+    calendar.line("PRODID:-//zomato-rs//daily menu//EN");
+    calendar.line("CALSCALE:GREGORIAN");
+    calendar.property("X-WR-CALNAME", &format!("Lunch at {}", restaurant));
+    for Entry { date, menu, sequence, modified } in menus {
+        let next_day = date.succ_opt().unwrap_or(*date);
+        let modified = DateTime::<Utc>::from(*modified).format("%Y%m%dT%H%M%SZ");
+        let mut description = String::new();
+        for item in &menu.items {
+            if !description.is_empty() {
+                description.push('\n');
+            }
+            description.push_str(&item.description);
+            if !item.price.is_empty() {
+                let _ = write!(description, " – {}", item.price);
+            }
+        }
+
+        calendar.line("BEGIN:VEVENT");
+        calendar.line(&format!("UID:{}-{}@zomato-rs", date.format("%Y%m%d"), uid_suffix));
+        calendar.line(&format!("DTSTAMP:{}", modified));
+        calendar.line(&format!("LAST-MODIFIED:{}", modified));
+        calendar.line(&format!("SEQUENCE:{}", sequence));
+        calendar.line(&format!("DTSTART;VALUE=DATE:{}", date.format("%Y%m%d")));
+        calendar.line(&format!("DTEND;VALUE=DATE:{}", next_day.format("%Y%m%d")));
+        calendar.property("SUMMARY", &format!("Lunch at {}", restaurant));
+        calendar.property("DESCRIPTION", &description);
+        calendar.line("TRANSP:TRANSPARENT");
+        calendar.line("END:VEVENT");
+    }
+    calendar.line("END:VCALENDAR");
+    calendar.0
+}
+
+#[derive(Default)]
+struct Calendar(String);
+
+impl Calendar {
+    /// Writes property with text value, escaping it.
+    fn property(&mut self, name: &str, value: &str) {
+        let mut line = String::with_capacity(name.len() + 1 + value.len());
+        line.push_str(name);
+        line.push(':');
+        for c in value.chars() {
+            match c {
+                '\\' | ';' | ',' => {
+                    line.push('\\');
+                    line.push(c);
+                },
+                '\n' => line.push_str("\\n"),
+                '\r' => (),
+                c => line.push(c),
+            }
+        }
+        self.line(&line);
+    }
+
+    /// Writes the line, folding it if it's too long.
+    fn line(&mut self, mut line: &str) {
+        let mut limit = MAX_LINE_LENGTH;
+        while line.len() > limit {
+            let mut split = limit;
+            while !line.is_char_boundary(split) {
+                split -= 1;
+            }
+            self.0.push_str(&line[..split]);
+            self.0.push_str("\r\n ");
+            line = &line[split..];
+            // The leading space of continuation lines counts too.
+            limit = MAX_LINE_LENGTH - 1;
+        }
+        self.0.push_str(line);
+        self.0.push_str("\r\n");
+    }
+}
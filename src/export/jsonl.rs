@@ -0,0 +1,28 @@
+//! JSON Lines export.
+//!
+//! The menus are flattened into JSON objects with `date`, `description`, `price` and `currency`
+//! fields, one per line and dish. Price and currency are `null` if the price couldn't be parsed.
+//! This is convenient for appending to archives and processing with line-oriented tools.
+//!
+//! ```
+//! use zomato::export::jsonl;
+//!
+//! let menus = zomato::parse_daily_menu(zomato::testing::FIXTURE)?;
+//! let mut out = Vec::new();
+//! jsonl::write(&menus, &mut out)?;
+//! let out = String::from_utf8(out)?;
+//! assert_eq!(out.lines().count(), 3);
+//! assert_eq!(out.lines().next(), Some(r#"{"date":"Today","description":"Hovädzí vývar s rezancami (1,3,9)","price":1.2,"currency":"€"}"#));
+//! # Ok::<_, Box<dyn std::error::Error>>(())
+//! ```
+
+use std::io;
+
+/// Writes the menus as JSON Lines.
+pub fn write<W: io::Write>(menus: &[crate::Menu], mut writer: W) -> io::Result<()> {
+    for row in super::rows(menus) {
+        serde_json::to_writer(&mut writer, &row)?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()
+}
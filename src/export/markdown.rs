@@ -0,0 +1,80 @@
+//! Markdown export.
+//!
+//! Each menu becomes a heading followed by a table of dishes and prices. The tables use the
+//! GitHub-flavored syntax which is understood by most chat applications and wikis.
+//!
+//! ```
+//! use zomato::export::markdown;
+//!
+//! let menus = zomato::parse_daily_menu(zomato::testing::FIXTURE)?;
+//! let markdown = markdown::to_markdown(&menus);
+//! assert!(markdown.starts_with("## Today\n\n| Dish | Price |\n| --- | ---: |\n"));
+//! assert!(markdown.contains("| Vyprážaný syr, hranolky, tatárska omáčka (1,3,7) | €6.00 |\n"));
+//! # Ok::<_, zomato::Error>(())
+//! ```
+
+use std::fmt::Write;
+use crate::format::{shorten, Truncation};
+use crate::Menu;
+
+/// Renders the menus as Markdown tables.
+///
+/// The menus are separated by an empty line. Use [`MarkdownFormatter`] to shorten the descriptions.
+pub fn to_markdown(menus: &[Menu]) -> String {
+    MarkdownFormatter::default().format(menus)
+}
+
+/// Renders the menus as Markdown tables with descriptions shortened to the maximum width.
+///
+/// The width is measured before escaping, so it's the width of the rendered text.
+///
+/// ```
+/// use zomato::export::markdown::MarkdownFormatter;
+/// use zomato::format::Truncation;
+///
+/// let menus = zomato::parse_daily_menu(zomato::testing::FIXTURE)?;
+/// let formatter = MarkdownFormatter { max_width: Some(20), truncation: Truncation::Middle };
+/// assert_eq!(formatter.format(&menus).lines().nth(4).unwrap(), "| Hovädzí vý…i (1,3,9) | €1.20 |");
+/// # Ok::<_, zomato::Error>(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MarkdownFormatter {
+    /// Maximum width of the descriptions.
+    pub max_width: Option<usize>,
+    /// How to shorten descriptions exceeding `max_width`.
+    pub truncation: Truncation,
+}
+
+impl MarkdownFormatter {
+    /// Renders the menus, separated by an empty line.
+    pub fn format(&self, menus: &[Menu]) -> String {
+        let mut markdown = String::new();
+        for (i, menu) in menus.iter().enumerate() {
+            if i > 0 {
+                markdown.push('\n');
+            }
+            // Writing into String never fails
+            let _ = writeln!(markdown, "## {}", escape(&menu.date));
+            markdown.push('\n');
+            markdown.push_str("| Dish | Price |\n");
+            markdown.push_str("| --- | ---: |\n");
+            for item in &menu.items {
+                let description = shorten(&item.description, self.max_width, self.truncation);
+                let _ = writeln!(markdown, "| {} | {} |", escape(&description), escape(&item.price));
+            }
+        }
+        markdown
+    }
+}
+
+/// Escapes characters which would be interpreted as formatting.
+fn escape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '|' | '*' | '_' | '`' | '[' | ']' | '<' | '>' | '#') {
+            result.push('\\');
+        }
+        result.push(c);
+    }
+    result
+}
@@ -0,0 +1,92 @@
+//! Conversion of menus into formats understood by other applications.
+
+pub mod csv;
+pub mod feed;
+pub mod html;
+pub mod ical;
+pub mod jsonl;
+pub mod markdown;
+
+use std::time::SystemTime;
+use chrono::NaiveDate;
+use serde_derive::Serialize;
+use crate::{date, Menu};
+
+/// Menu with known calendar date and revision.
+///
+/// Calendar and feed exports identify the menus by restaurant and date, so regenerating them
+/// replaces the previous versions in the clients. The revision information allows the clients to
+/// notice that a menu was corrected.
+///
+/// [`History::dated_menus`](crate::history::History::dated_menus) returns these.
+pub struct DatedMenu {
+    /// Calendar date of the menu.
+    pub date: NaiveDate,
+    /// The menu itself.
+    pub menu: Menu,
+    /// Number of times the menu changed since it was first seen, starting at zero.
+    pub sequence: u32,
+    /// When the current version of the menu was first seen.
+    pub modified: SystemTime,
+}
+
+/// Borrowed [`DatedMenu`] so that the exports don't need to copy the menus.
+struct Entry<'a> {
+    date: NaiveDate,
+    menu: &'a Menu,
+    sequence: u32,
+    modified: SystemTime,
+}
+
+impl<'a> From<&'a DatedMenu> for Entry<'a> {
+    fn from(menu: &'a DatedMenu) -> Self {
+        Entry {
+            date: menu.date,
+            menu: &menu.menu,
+            sequence: menu.sequence,
+            modified: menu.modified,
+        }
+    }
+}
+
+/// Resolves the dates of the menus fetched at `fetched_on`, skipping unknown ones.
+fn entries(menus: &[Menu], fetched_on: NaiveDate, modified: impl Fn(NaiveDate) -> SystemTime) -> Vec<Entry<'_>> {
+    menus
+        .iter()
+        .filter_map(|menu| {
+            let date = date::resolve_heading(&menu.date, fetched_on, None)?;
+            Some(Entry {
+                date,
+                menu,
+                sequence: 0,
+                modified: modified(date),
+            })
+        })
+        .collect()
+}
+
+/// Menu item flattened into a single row of tabular formats.
+#[derive(Serialize)]
+struct Row<'a> {
+    date: &'a str,
+    description: &'a str,
+    /// `None` if the price couldn't be parsed.
+    price: Option<f64>,
+    /// `None` if the price couldn't be parsed.
+    currency: Option<String>,
+}
+
+/// Flattens the menus into rows, one per item.
+fn rows(menus: &[Menu]) -> impl Iterator<Item = Row<'_>> {
+    menus.iter().flat_map(|menu| {
+        menu.items.iter().map(move |item| {
+            let price = item.parsed_price();
+            Row {
+                date: &menu.date,
+                description: &item.description,
+                price: price.as_ref().map(|price| price.amount),
+                currency: price.map(|price| price.currency),
+            }
+        })
+    })
+}
@@ -0,0 +1,310 @@
+//! Formatting menus for display.
+//!
+//! Dish descriptions are often very long, so all formatters accept a maximum width and a
+//! [`Truncation`] strategy describing how to shorten the descriptions exceeding it. Besides the
+//! formatters in this module these are
+//! [`MarkdownFormatter`](crate::export::markdown::MarkdownFormatter) and
+//! `TelegramFormatter` in `sink::telegram`, which requires the `telegram` feature. The width is
+//! measured in columns of terminal, so wide characters, e.g. Chinese ones, take two columns and
+//! combining characters none.
+
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+use crate::{Lang, Menu, MenuItem};
+
+const ELLIPSIS: char = '…';
+
+/// Strategy used to shorten texts that are too long.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum Truncation {
+    /// The text is never shortened.
+    #[default]
+    None,
+    /// The end of the text is replaced with ellipsis.
+    End,
+    /// The middle of the text is replaced with ellipsis, keeping both the beginning and the end.
+    Middle,
+    /// Like `End` but the text is cut at the end of the last word that fits.
+    ///
+    /// Falls back to `End` if the first word doesn't fit.
+    WordBoundary,
+}
+
+/// Error returned when the name of truncation strategy is unknown.
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("unknown truncation strategy '{input}', use none, end, middle or word")]
+pub struct ParseTruncationError {
+    input: String,
+}
+
+/// Parses `none`, `end`, `middle` or `word` (for [`Truncation::WordBoundary`]).
+impl FromStr for Truncation {
+    type Err = ParseTruncationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Truncation::None),
+            "end" => Ok(Truncation::End),
+            "middle" => Ok(Truncation::Middle),
+            "word" => Ok(Truncation::WordBoundary),
+            _ => Err(ParseTruncationError { input: s.to_owned() }),
+        }
+    }
+}
+
+impl Truncation {
+    /// Shortens the text to at most `max_width` columns, including the ellipsis.
+    ///
+    /// ```
+    /// use zomato::format::Truncation;
+    ///
+    /// let text = "Bravčový rezeň, zemiakový šalát";
+    /// assert_eq!(Truncation::End.apply(text, 12), "Bravčový re…");
+    /// assert_eq!(Truncation::Middle.apply(text, 12), "Bravčo…šalát");
+    /// assert_eq!(Truncation::WordBoundary.apply(text, 12), "Bravčový…");
+    /// assert_eq!(Truncation::None.apply(text, 12), text);
+    /// assert_eq!(Truncation::End.apply("豚骨拉麺", 6), "豚骨…");
+    /// ```
+    pub fn apply(self, text: &str, max_width: usize) -> Cow<'_, str> {
+        if text.width() <= max_width || self == Truncation::None {
+            return Cow::Borrowed(text);
+        }
+        if max_width == 0 {
+            return Cow::Borrowed("");
+        }
+
+        let keep = max_width - 1;
+        let mut result = String::with_capacity(max_width * 4);
+        match self {
+            Truncation::None => unreachable!(),
+            Truncation::End => {
+                result.push_str(&text[..prefix_end(text, keep)]);
+                result.push(ELLIPSIS);
+            },
+            Truncation::Middle => {
+                let tail = keep / 2;
+                let head = keep - tail;
+                result.push_str(&text[..prefix_end(text, head)]);
+                result.push(ELLIPSIS);
+                result.push_str(&text[suffix_start(text, tail)..]);
+            },
+            Truncation::WordBoundary => {
+                // The word fits if it's followed by whitespace, so we look at one more column.
+                let prefix = &text[..prefix_end(text, keep + 1)];
+                match prefix.rfind(char::is_whitespace) {
+                    Some(end) if !prefix[..end].trim_end().is_empty() => {
+                        result.push_str(prefix[..end].trim_end_matches(|c: char| c.is_whitespace() || c == ','));
+                    },
+                    _ => result.push_str(&text[..prefix_end(text, keep)]),
+                }
+                result.push(ELLIPSIS);
+            },
+        }
+        Cow::Owned(result)
+    }
+}
+
+/// Shortens the text using the strategy if the maximum width is set.
+pub(crate) fn shorten(text: &str, max_width: Option<usize>, truncation: Truncation) -> Cow<'_, str> {
+    match max_width {
+        Some(max_width) => truncation.apply(text, max_width),
+        None => Cow::Borrowed(text),
+    }
+}
+
+/// Returns the end of the longest prefix at most `max_width` columns wide.
+fn prefix_end(text: &str, max_width: usize) -> usize {
+    let mut width = 0;
+    for (i, c) in text.char_indices() {
+        width += c.width().unwrap_or(0);
+        if width > max_width {
+            return i;
+        }
+    }
+    text.len()
+}
+
+/// Returns the start of the longest suffix at most `max_width` columns wide.
+fn suffix_start(text: &str, max_width: usize) -> usize {
+    let mut width = 0;
+    let mut start = text.len();
+    for (i, c) in text.char_indices().rev() {
+        width += c.width().unwrap_or(0);
+        if width > max_width {
+            break;
+        }
+        start = i;
+    }
+    start
+}
+
+/// Formats menus as a table with aligned prices.
+///
+/// ```
+/// use zomato::format::{TableFormatter, Truncation};
+///
+/// let menus = zomato::parse_daily_menu(zomato::testing::FIXTURE)?;
+/// let formatter = TableFormatter { max_width: Some(20), truncation: Truncation::End, ..Default::default() };
+/// let mut table = String::new();
+/// formatter.format(&menus, &mut table).unwrap();
+/// assert_eq!(table.lines().nth(1).unwrap(), "Hovädzí vývar s rez… | €1.20");
+/// # Ok::<_, zomato::Error>(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct TableFormatter {
+    /// Maximum width of the description column.
+    pub max_width: Option<usize>,
+    /// How to shorten descriptions exceeding `max_width`.
+    pub truncation: Truncation,
+    /// Preferred language of descriptions, see [`MenuItem::description_in`](crate::MenuItem::description_in).
+    pub language: Option<Lang>,
+}
+
+impl TableFormatter {
+    /// Writes the menus into the writer.
+    ///
+    /// Each menu starts with its date on a separate line, followed by a line for each dish.
+    pub fn format(&self, menus: &[Menu], out: &mut dyn fmt::Write) -> fmt::Result {
+        zomato_core::write_table(menus, |food| shorten(self.description(food), self.max_width, self.truncation), out)
+    }
+
+    fn description<'a>(&self, food: &'a MenuItem) -> &'a str {
+        match self.language {
+            Some(language) => food.description_in(language),
+            None => &food.description,
+        }
+    }
+}
+
+/// Formats a single menu as one line suitable for status bars.
+///
+/// ```
+/// use zomato::format::{StatusBarFormatter, Truncation};
+///
+/// let menus = zomato::parse_daily_menu(zomato::testing::FIXTURE)?;
+/// let formatter = StatusBarFormatter { max_width: Some(40), truncation: Truncation::End, ..Default::default() };
+/// assert_eq!(formatter.format(&menus[0]), "Hovädzí vývar s rezancami (1,3,9) €1.20…");
+/// # Ok::<_, zomato::Error>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct StatusBarFormatter {
+    /// Maximum width of the whole line.
+    pub max_width: Option<usize>,
+    /// How to shorten the line exceeding `max_width`.
+    pub truncation: Truncation,
+    /// Separator put between the dishes.
+    pub separator: String,
+    /// Preferred language of descriptions, see [`MenuItem::description_in`](crate::MenuItem::description_in).
+    pub language: Option<Lang>,
+}
+
+impl Default for StatusBarFormatter {
+    fn default() -> Self {
+        StatusBarFormatter {
+            max_width: None,
+            truncation: Truncation::End,
+            separator: " · ".to_owned(),
+            language: None,
+        }
+    }
+}
+
+impl StatusBarFormatter {
+    /// Formats the menu.
+    pub fn format(&self, menu: &Menu) -> String {
+        let mut line = String::new();
+        for (i, food) in menu.items.iter().enumerate() {
+            if i > 0 {
+                line.push_str(&self.separator);
+            }
+            match self.language {
+                Some(language) => line.push_str(food.description_in(language)),
+                None => line.push_str(&food.description),
+            }
+            if !food.price.is_empty() {
+                line.push(' ');
+                line.push_str(&food.price);
+            }
+        }
+        match self.max_width {
+            Some(max_width) => self.truncation.apply(&line, max_width).into_owned(),
+            None => line,
+        }
+    }
+}
+
+/// Formats a single menu as a Slack message using the `mrkdwn` syntax.
+///
+/// The dishes are listed one per line below the name of the restaurant, the maximum width applies
+/// to each description.
+///
+/// ```
+/// use zomato::format::{SlackFormatter, Truncation};
+///
+/// let menus = zomato::parse_daily_menu(zomato::testing::FIXTURE)?;
+/// let formatter = SlackFormatter { max_width: Some(20), truncation: Truncation::WordBoundary, ..Default::default() };
+/// let message = formatter.format("U Zlatého Bažanta", menus.first());
+/// assert_eq!(message.lines().next().unwrap(), "*Lunch at U Zlatého Bažanta* (Today)");
+/// assert_eq!(message.lines().nth(1).unwrap(), "• Hovädzí vývar s… – _€1.20_");
+/// assert_eq!(SlackFormatter::default().format("Fish & Chips", None), "*Lunch at Fish &amp; Chips*\nNo daily menu available");
+/// # Ok::<_, zomato::Error>(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SlackFormatter {
+    /// Maximum width of each description.
+    pub max_width: Option<usize>,
+    /// How to shorten descriptions exceeding `max_width`.
+    pub truncation: Truncation,
+    /// Preferred language of descriptions, see [`MenuItem::description_in`](crate::MenuItem::description_in).
+    pub language: Option<Lang>,
+}
+
+impl SlackFormatter {
+    /// Formats the menu of the restaurant, `None` is formatted as missing menu.
+    pub fn format(&self, restaurant_name: &str, menu: Option<&Menu>) -> String {
+        let mut message = format!("*Lunch at {}*", escape_slack(restaurant_name));
+        let menu = match menu {
+            Some(menu) if !menu.items.is_empty() => menu,
+            _ => {
+                message.push_str("\nNo daily menu available");
+                return message;
+            },
+        };
+
+        message.push_str(" (");
+        message.push_str(&escape_slack(&menu.date));
+        message.push(')');
+        for food in &menu.items {
+            let description = match self.language {
+                Some(language) => food.description_in(language),
+                None => &food.description,
+            };
+            message.push_str("\n• ");
+            message.push_str(&escape_slack(&shorten(description, self.max_width, self.truncation)));
+            if !food.price.is_empty() {
+                message.push_str(" – _");
+                message.push_str(&escape_slack(&food.price));
+                message.push('_');
+            }
+        }
+        message
+    }
+}
+
+/// Escapes the characters Slack requires to be escaped.
+///
+/// Formatting characters can't be escaped in `mrkdwn`, Slack ignores them if they aren't paired.
+pub(crate) fn escape_slack(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,69 @@
+//! Detection of responses refusing the request because of the region of the client.
+
+use crate::transport::Response;
+
+/// Phrases used by the pages explaining that the service is not available in the region.
+///
+/// Matched against the body converted to ASCII lowercase.
+const REGION_MESSAGES: &[&str] = &[
+    "not available in your region",
+    "not available in your country",
+    "not available in your location",
+    "unavailable in your region",
+    "unavailable in your country",
+    "blocked in your region",
+    "blocked in your country",
+    "в вашем регионе",
+    "в вашей стране",
+];
+
+/// Phrases followed by the name of the region in which the service is not available.
+const REGION_PREFIXES: &[&str] = &["not available in ", "unavailable in ", "blocked in "];
+
+/// Longest region hint, longer matches are most likely not names of regions.
+const MAX_HINT_LEN: usize = 40;
+
+/// Error returned when the server refuses to serve the client because of its region.
+#[derive(Debug, thiserror::Error)]
+#[error("the server refused the request because of the region of the client (HTTP {status}{}), route the requests through a proxy in a different region", .region_hint.as_ref().map(|hint| format!(", region: {}", hint)).unwrap_or_default())]
+pub(crate) struct GeoBlockedError {
+    status: u16,
+    pub(crate) region_hint: Option<String>,
+}
+
+/// Returns the error if the response refuses the request because of the region.
+///
+/// That's the case for `451 Unavailable For Legal Reasons` and for `403 Forbidden` explaining
+/// that the service is not available in the region or naming the region.
+pub(crate) fn detect(response: &Response) -> Option<GeoBlockedError> {
+    // ASCII lowercase keeps the offsets the same as in the original body
+    let body = response.body.to_ascii_lowercase();
+    let region_hint = region_hint(&response.body, &body);
+    let explained = region_hint.is_some() || REGION_MESSAGES.iter().any(|message| body.contains(message));
+    if !(response.status == 451 || (response.status == 403 && explained)) {
+        return None;
+    }
+    Some(GeoBlockedError {
+        status: response.status,
+        region_hint,
+    })
+}
+
+/// Finds the name of the region in phrases like "not available in Russia".
+fn region_hint(body: &str, lowercase_body: &str) -> Option<String> {
+    REGION_PREFIXES
+        .iter()
+        .flat_map(|prefix| lowercase_body.match_indices(prefix).map(move |(start, _)| start + prefix.len()))
+        .filter_map(|start| {
+            let rest = &body[start..];
+            let end = rest.find(['.', ',', '!', '<', '"', '(', '\n']).unwrap_or(rest.len());
+            let hint = rest[..end].trim();
+            let is_name = hint.starts_with(char::is_uppercase) && hint.len() <= MAX_HINT_LEN;
+            if is_name {
+                Some(hint.to_owned())
+            } else {
+                None
+            }
+        })
+        .next()
+}
@@ -0,0 +1,290 @@
+//! Weekly summaries of the stored menus.
+//!
+//! [`WeeklyDigest`] collects the menus recorded during a week and summarizes them per restaurant:
+//! which dishes were offered, how many days each of them, and the price range. The summaries can
+//! be turned into ordinary [`Menu`]s, so they can be rendered by any formatter and delivered
+//! through sinks, e.g. from a cron job on Friday afternoon (`0 14 * * 5`).
+//!
+//! ```
+//! use std::time::{Duration, SystemTime};
+//! use zomato::history::History;
+//! use zomato::history::digest::WeeklyDigest;
+//!
+//! let mut history = History::open_in_memory()?;
+//! let menus = zomato::parse_daily_menu(zomato::testing::FIXTURE)?;
+//! let yesterday = SystemTime::now() - Duration::from_secs(24 * 60 * 60);
+//! history.record_at("bratislava", "u-zlateho-bazanta", &menus, yesterday)?;
+//! history.record("bratislava", "u-zlateho-bazanta", &menus[..1])?;
+//!
+//! let week = &WeeklyDigest::new().build(&history)?[0];
+//! assert_eq!(week.days, 2);
+//! assert_eq!(week.dishes.len(), 3);
+//! assert_eq!(week.most_repeated().unwrap().days, 2);
+//! assert_eq!(week.min_price.as_ref().unwrap().amount, 1.2);
+//! assert_eq!(week.max_price.as_ref().unwrap().amount, 7.2);
+//!
+//! let mut table = String::new();
+//! zomato::format::TableFormatter::default().format(&[week.to_menu()], &mut table).unwrap();
+//! let first_dish = table.lines().nth(1).unwrap();
+//! assert!(first_dish.starts_with("Hovädzí vývar s rezancami (1,3,9) (2×) "));
+//! assert!(first_dish.ends_with(" | €1.20"));
+//! # Ok::<_, zomato::Error>(())
+//! ```
+
+use std::collections::BTreeMap;
+use chrono::{Duration, NaiveDate};
+use crate::similarity::Similarity;
+use crate::sink::MenuEvent;
+use crate::{Error, Menu, MenuItem, Price, PriceBounds};
+use super::History;
+
+/// Builder of summaries of the menus offered during a week.
+///
+/// By default the week consists of the seven days ending today and all restaurants found in the
+/// history are summarized.
+#[derive(Clone, Debug)]
+pub struct WeeklyDigest {
+    last_day: NaiveDate,
+    days: u32,
+    restaurants: Vec<(String, String)>,
+    merge_similar: Option<f64>,
+}
+
+impl Default for WeeklyDigest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WeeklyDigest {
+    /// Creates the builder summarizing the week ending today.
+    pub fn new() -> Self {
+        WeeklyDigest {
+            last_day: chrono::Local::now().date_naive(),
+            days: 7,
+            restaurants: Vec::new(),
+            merge_similar: None,
+        }
+    }
+
+    /// Sets the last day of the summarized period, inclusive.
+    pub fn ending(mut self, last_day: NaiveDate) -> Self {
+        self.last_day = last_day;
+        self
+    }
+
+    /// Sets the number of summarized days, 7 by default.
+    ///
+    /// Setting it to 5 on Friday summarizes the working week.
+    pub fn days(mut self, days: u32) -> Self {
+        self.days = days;
+        self
+    }
+
+    /// Summarizes only given restaurants, can be called multiple times.
+    ///
+    /// The summaries are returned in the order in which the restaurants were added.
+    pub fn restaurant(mut self, city: impl Into<String>, restaurant: impl Into<String>) -> Self {
+        self.restaurants.push((city.into(), restaurant.into()));
+        self
+    }
+
+    /// Counts dishes with similar descriptions as the same dish.
+    ///
+    /// By default only dishes with equal descriptions are merged. With this, descriptions with
+    /// at least `min_similarity` according to the
+    /// [global similarity backend](crate::similarity::global) are merged too, so that typos and
+    /// small changes don't hide repeated dishes. The latest description is kept.
+    ///
+    /// ```
+    /// use std::time::{Duration, SystemTime};
+    /// use zomato::history::digest::WeeklyDigest;
+    /// use zomato::history::History;
+    ///
+    /// let mut history = History::open_in_memory()?;
+    /// let menus = zomato::parse_daily_menu(zomato::testing::FIXTURE)?;
+    /// let typo = zomato::parse_daily_menu(&zomato::testing::FIXTURE.replace("rezancami", "rezancamy"))?;
+    /// let yesterday = SystemTime::now() - Duration::from_secs(24 * 60 * 60);
+    /// history.record_at("bratislava", "u-zlateho-bazanta", &typo, yesterday)?;
+    /// history.record("bratislava", "u-zlateho-bazanta", &menus)?;
+    ///
+    /// let week = WeeklyDigest::new().merge_similar(0.95).build(&history)?.remove(0);
+    /// assert_eq!(week.dishes.len(), 3);
+    /// assert!(week.dishes.iter().all(|dish| dish.days == 2));
+    /// # Ok::<_, zomato::Error>(())
+    /// ```
+    pub fn merge_similar(mut self, min_similarity: f64) -> Self {
+        self.merge_similar = Some(min_similarity);
+        self
+    }
+
+    /// Computes the summaries from the history.
+    ///
+    /// Restaurants without any menu in the period are omitted.
+    pub fn build(&self, history: &History) -> Result<Vec<RestaurantWeek>, Error> {
+        let first_day = self.last_day - Duration::days(i64::from(self.days.saturating_sub(1)));
+        let similarity = crate::similarity::global();
+        let merge_similar = self.merge_similar.map(|min_similarity| (&*similarity, min_similarity));
+        let mut weeks = BTreeMap::<(String, String), RestaurantWeek>::new();
+        for date in first_day.iter_days().take(self.days as usize) {
+            for stored in history.menus_for_date(date)? {
+                let (city, restaurant, items) = (stored.city, stored.restaurant, stored.menu.items);
+                let is_selected = self.restaurants.is_empty() || self.restaurants.iter().any(|selected| selected.0 == city && selected.1 == restaurant);
+                if !is_selected || items.is_empty() {
+                    continue;
+                }
+                weeks
+                    .entry((city.clone(), restaurant.clone()))
+                    .or_insert_with(|| RestaurantWeek::new(city, restaurant, first_day, self.last_day))
+                    .add(items, merge_similar, &history.price_bounds);
+            }
+        }
+
+        if self.restaurants.is_empty() {
+            return Ok(weeks.into_values().collect());
+        }
+        Ok(self
+            .restaurants
+            .iter()
+            .filter_map(|(city, restaurant)| weeks.remove(&(city.clone(), restaurant.clone())))
+            .collect())
+    }
+}
+
+/// Dish offered during the summarized period.
+pub struct DishSummary {
+    /// The most recent offering of the dish.
+    pub item: MenuItem,
+    /// Number of days at which the dish was offered.
+    pub days: usize,
+}
+
+/// Summary of the menus of a single restaurant.
+pub struct RestaurantWeek {
+    /// City in which the restaurant is located.
+    pub city: String,
+    /// Name of the restaurant as used in the URL.
+    pub restaurant: String,
+    /// First day of the summarized period.
+    pub first_day: NaiveDate,
+    /// Last day of the summarized period.
+    pub last_day: NaiveDate,
+    /// Number of days at which the restaurant offered a menu.
+    pub days: usize,
+    /// Offered dishes in the order of their first appearance.
+    ///
+    /// Dishes are considered the same if their descriptions are equal.
+    pub dishes: Vec<DishSummary>,
+    /// Price of the cheapest dish, `None` if no price could be parsed.
+    pub min_price: Option<Price>,
+    /// Price of the most expensive dish, `None` if no price could be parsed.
+    pub max_price: Option<Price>,
+}
+
+impl RestaurantWeek {
+    fn new(city: String, restaurant: String, first_day: NaiveDate, last_day: NaiveDate) -> Self {
+        RestaurantWeek {
+            city,
+            restaurant,
+            first_day,
+            last_day,
+            days: 0,
+            dishes: Vec::new(),
+            min_price: None,
+            max_price: None,
+        }
+    }
+
+    /// Adds the items offered at the next day.
+    ///
+    /// Dishes are merged if their descriptions are equal or similar enough if the similarity is
+    /// passed.
+    fn add(&mut self, items: Vec<MenuItem>, merge_similar: Option<(&dyn Similarity, f64)>, price_bounds: &PriceBounds) {
+        self.days += 1;
+        let mut seen_today = Vec::<usize>::new();
+        for item in items {
+            if let Some(price) = item.plausible_price(price_bounds) {
+                if self.min_price.as_ref().is_none_or(|min| price.amount < min.amount) {
+                    self.min_price = Some(price.clone());
+                }
+                if self.max_price.as_ref().is_none_or(|max| price.amount > max.amount) {
+                    self.max_price = Some(price);
+                }
+            }
+            let is_same = |dish: &DishSummary| {
+                dish.item.description == item.description
+                    || merge_similar.is_some_and(|(similarity, min)| similarity.similarity(&dish.item.description, &item.description) >= min)
+            };
+            match self.dishes.iter().position(is_same) {
+                Some(index) => {
+                    // Some restaurants list the same dish twice, e.g. in different sizes
+                    if !seen_today.contains(&index) {
+                        self.dishes[index].days += 1;
+                        seen_today.push(index);
+                    }
+                    self.dishes[index].item = item;
+                },
+                None => {
+                    seen_today.push(self.dishes.len());
+                    self.dishes.push(DishSummary { item, days: 1 });
+                },
+            }
+        }
+    }
+
+    /// Returns the dish offered at the most days, the earliest one if there are more.
+    pub fn most_repeated(&self) -> Option<&DishSummary> {
+        self.dishes.iter().rev().max_by_key(|dish| dish.days)
+    }
+
+    /// Converts the summary into a menu so that it can be rendered by formatters.
+    ///
+    /// The heading contains the period and the price range, the dishes are sorted by the number
+    /// of days they were offered with the count appended to the description of repeated ones.
+    pub fn to_menu(&self) -> Menu {
+        let mut date = format!("{} – {}", self.first_day, self.last_day);
+        if let (Some(min), Some(max)) = (&self.min_price, &self.max_price) {
+            date.push_str(&format!(", {} – {}", min, max));
+        }
+
+        let mut dishes = self.dishes.iter().collect::<Vec<_>>();
+        // stable, so the order of appearance is kept for ties
+        dishes.sort_by_key(|dish| std::cmp::Reverse(dish.days));
+        let items = dishes
+            .into_iter()
+            .map(|dish| {
+                let mut description = dish.item.description.clone();
+                if dish.days > 1 {
+                    description.push_str(&format!(" ({}×)", dish.days));
+                }
+                MenuItem {
+                    description,
+                    raw_description: dish.item.raw_description.clone(),
+                    price: dish.item.price.clone(),
+                    allergens: dish.item.allergens.clone(),
+                    raw_allergens: dish.item.raw_allergens.clone(),
+                    portions: dish.item.portions.clone(),
+                    raw_portions: dish.item.raw_portions.clone(),
+                    description_translations: dish.item.description_translations.clone(),
+                    details: dish.item.details.clone(),
+                    tags: dish.item.tags.clone(),
+                    id: dish.item.id.clone(),
+                }
+            })
+            .collect();
+
+        Menu {
+            date,
+            items,
+            ocr: false,
+            valid_from: None,
+            valid_to: None,
+            is_current: false,
+        }
+    }
+
+    /// Creates event carrying the summary as a single menu, see [`to_menu`](Self::to_menu).
+    pub fn to_event(&self) -> MenuEvent {
+        MenuEvent::new(&*self.city, &*self.restaurant, vec![self.to_menu()])
+    }
+}
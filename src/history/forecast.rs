@@ -0,0 +1,142 @@
+//! Forecast of dishes based on the rotation of menus.
+//!
+//! Many canteens repeat their menus every two to four weeks. The forecast looks at how well the
+//! past menus of a restaurant repeat after each of the supported [`CYCLES_WEEKS`] and predicts
+//! dishes offered on a future day from the menus one or more cycles earlier. Each prediction
+//! carries a confidence score so planners can show only likely dishes.
+//!
+//! ```
+//! use std::time::{Duration, SystemTime};
+//! use zomato::history::History;
+//!
+//! let mut history = History::open_in_memory()?;
+//! let week_a = zomato::parse_daily_menu(zomato::testing::FIXTURE)?;
+//! let week_b = zomato::parse_daily_menu(&zomato::testing::FIXTURE.replace("syr", "karfiol"))?;
+//! let weeks_ago = |weeks: u64| SystemTime::now() - Duration::from_secs(weeks * 7 * 24 * 60 * 60);
+//! history.record_at("bratislava", "jedalen", &week_a, weeks_ago(4))?;
+//! history.record_at("bratislava", "jedalen", &week_b, weeks_ago(3))?;
+//! history.record_at("bratislava", "jedalen", &week_a, weeks_ago(2))?;
+//! history.record_at("bratislava", "jedalen", &week_b, weeks_ago(1))?;
+//!
+//! let today = chrono::Local::now().date_naive();
+//! let predicted = history.predict_menu("bratislava", "jedalen", today)?;
+//! assert_eq!(predicted.len(), 4);
+//! assert_eq!(predicted[2].description, "Vyprážaný syr, hranolky, tatárska omáčka (1,3,7)");
+//! assert_eq!(predicted[2].confidence, 1.0);
+//! assert_eq!(predicted[2].cycle_weeks, 2);
+//! // half of the dishes differ between the weeks, so the three-week cycle is half as reliable
+//! assert_eq!(predicted[3].description, "Vyprážaný karfiol, hranolky, tatárska omáčka (1,3,7)");
+//! assert_eq!(predicted[3].confidence, 0.5);
+//! assert_eq!(predicted[3].cycle_weeks, 3);
+//! # Ok::<_, zomato::Error>(())
+//! ```
+
+use std::collections::{BTreeMap, HashSet};
+use chrono::{Duration, NaiveDate};
+use crate::Error;
+use super::History;
+
+/// Lengths of the rotation cycles considered by the forecast, in weeks.
+pub const CYCLES_WEEKS: [u32; 4] = [1, 2, 3, 4];
+
+/// How many past cycles are looked at when predicting a day.
+const LOOKBACK_CYCLES: i32 = 3;
+
+/// Dish likely to be offered at the predicted day.
+#[derive(Clone, Debug)]
+pub struct PredictedDish {
+    /// Description of the dish.
+    pub description: String,
+    /// Price of the dish when it was last offered.
+    pub price: String,
+    /// Estimated probability of the dish being offered, between 0 and 1.
+    pub confidence: f64,
+    /// Length of the rotation cycle the prediction is based on.
+    pub cycle_weeks: u32,
+    /// The last day the dish was offered.
+    pub last_offered: NaiveDate,
+}
+
+impl History {
+    /// Predicts the dishes the restaurant will offer at given day.
+    ///
+    /// For each cycle length the score of a dish is the share of the menus one, two and three
+    /// cycles before the day which contained it, weighted by how similar the menus one cycle
+    /// apart were in the whole history. The best score of each dish is its confidence. Dishes are
+    /// ordered by confidence, dishes never repeated after any cycle are omitted, so the result is
+    /// empty if the restaurant doesn't rotate its menus.
+    ///
+    /// Use [`BusinessCalendar::days_from`](crate::BusinessCalendar::days_from) to get the days to
+    /// plan, so that weekends and holidays are skipped.
+    pub fn predict_menu(&self, city: &str, restaurant: &str, date: NaiveDate) -> Result<Vec<PredictedDish>, Error> {
+        let menus = self
+            .dated_menus(city, restaurant)?
+            .into_iter()
+            .filter(|dated| dated.date < date && !dated.menu.items.is_empty())
+            .map(|dated| (dated.date, dated.menu.items))
+            .collect::<BTreeMap<_, _>>();
+        let dishes = menus
+            .iter()
+            .map(|(date, items)| (*date, items.iter().map(|item| &*item.description).collect::<HashSet<_>>()))
+            .collect::<BTreeMap<_, _>>();
+
+        let mut predictions = Vec::<PredictedDish>::new();
+        for cycle_weeks in CYCLES_WEEKS {
+            let cycle = Duration::weeks(cycle_weeks.into());
+            let similarity = cycle_similarity(&dishes, cycle);
+            if similarity == 0.0 {
+                continue;
+            }
+
+            let past_days = (1..=LOOKBACK_CYCLES)
+                .filter_map(|cycles| menus.get_key_value(&(date - cycle * cycles)))
+                .collect::<Vec<_>>();
+            for (day, items) in &past_days {
+                for item in items.iter() {
+                    let offered = past_days
+                        .iter()
+                        .filter(|(day, _)| dishes[day].contains(&*item.description))
+                        .count();
+                    let confidence = similarity * offered as f64 / past_days.len() as f64;
+                    match predictions.iter_mut().find(|prediction| prediction.description == item.description) {
+                        Some(prediction) => {
+                            if **day > prediction.last_offered {
+                                prediction.last_offered = **day;
+                                prediction.price = item.price.clone();
+                            }
+                            if confidence > prediction.confidence {
+                                prediction.confidence = confidence;
+                                prediction.cycle_weeks = cycle_weeks;
+                            }
+                        },
+                        None => predictions.push(PredictedDish {
+                            description: item.description.clone(),
+                            price: item.price.clone(),
+                            confidence,
+                            cycle_weeks,
+                            last_offered: **day,
+                        }),
+                    }
+                }
+            }
+        }
+
+        // stable, so dishes with equal confidence stay in the order of the menus
+        predictions.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+        Ok(predictions)
+    }
+}
+
+/// Computes average Jaccard similarity of the menus which are `cycle` apart.
+fn cycle_similarity(dishes: &BTreeMap<NaiveDate, HashSet<&str>>, cycle: Duration) -> f64 {
+    let (sum, pairs) = dishes
+        .iter()
+        .filter_map(|(date, current)| Some((current, dishes.get(&(*date - cycle))?)))
+        .map(|(current, previous)| current.intersection(previous).count() as f64 / current.union(previous).count() as f64)
+        .fold((0.0, 0), |(sum, pairs), similarity| (sum + similarity, pairs + 1));
+    if pairs == 0 {
+        0.0
+    } else {
+        sum / pairs as f64
+    }
+}
@@ -0,0 +1,479 @@
+//! Persistent archive of fetched menus.
+//!
+//! The archive is stored in a SQLite database so that you can compute long-term statistics about
+//! your lunches. Each recorded menu is stored along with the time it was fetched, so the same
+//! menu may be recorded multiple times, the queries return the most recent record.
+//!
+//! Zomato labels menus with headings like "Today" or "Wednesday, 14th October", so the calendar
+//! date of each menu is computed from the heading and the time it was fetched. Menus with
+//! headings that can't be understood are still stored but can't be queried by date.
+//!
+//! The schema of the database is upgraded automatically when it's opened by a newer version of
+//! this crate. Opening a database upgraded by a newer version fails with [`SchemaError`] instead
+//! of risking corruption of the archive.
+//!
+//! ```
+//! use zomato::history::History;
+//!
+//! let mut history = History::open_in_memory()?;
+//! let menus = zomato::parse_daily_menu(zomato::testing::FIXTURE)?;
+//! history.record("bratislava", "u-zlateho-bazanta", &menus)?;
+//!
+//! let today = chrono::Local::now().date_naive();
+//! assert_eq!(history.menus_for_date(today)?[0].menu.items.len(), 3);
+//! assert_eq!(history.price_history("rezeň")?[0].price, "€7.20");
+//! # Ok::<_, zomato::Error>(())
+//! ```
+
+pub mod digest;
+pub mod forecast;
+pub mod stats;
+
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use chrono::{NaiveDate, TimeZone};
+use rusqlite::{params, Connection, TransactionBehavior};
+use crate::{date, Error, Menu, Parser, PriceBounds};
+use crate::export::DatedMenu;
+
+/// Version of the database schema written by this version of the crate.
+///
+/// Stored in `PRAGMA user_version`. Version 0 is either an empty database or one created before
+/// the schema was versioned, which already contains the tables.
+pub const SCHEMA_VERSION: u32 = MIGRATIONS.len() as u32;
+
+/// Error returned when the database can't be used by this version of the crate.
+///
+/// See [`Error::schema_error`].
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum SchemaError {
+    /// The database was upgraded by a newer version of this crate.
+    #[error("the history database has schema version {found} but only versions up to {supported} are supported, upgrade the application")]
+    TooNew {
+        /// Version found in the database.
+        found: u32,
+        /// The newest version supported by this crate, same as [`SCHEMA_VERSION`].
+        supported: u32,
+    },
+}
+
+/// Archive of fetched menus.
+pub struct History {
+    connection: Connection,
+    price_bounds: PriceBounds,
+}
+
+/// Menu retrieved from [`History`].
+pub struct StoredMenu {
+    /// City in which the restaurant is located.
+    pub city: String,
+    /// Name of the restaurant as used in the URL.
+    pub restaurant: String,
+    /// When the menu was fetched.
+    pub fetched_at: SystemTime,
+    /// Calendar date of the menu if it could be determined.
+    pub date: Option<NaiveDate>,
+    /// The menu itself.
+    pub menu: Menu,
+}
+
+/// Menus of a restaurant fetched at specific time.
+///
+/// Used to record multiple menus at once, see [`History::record_batch`] and [`BatchWriter`].
+pub struct Record {
+    /// City in which the restaurant is located.
+    pub city: String,
+    /// Name of the restaurant as used in the URL.
+    pub restaurant: String,
+    /// Fetched menus.
+    pub menus: Vec<Menu>,
+    /// When the menus were fetched.
+    pub fetched_at: SystemTime,
+}
+
+/// Buffers records and writes them into the history in batches.
+///
+/// Each batch is written in a single transaction once it's full, so at most one batch is lost if
+/// the application crashes. Call [`flush`](Self::flush) or [`into_inner`](Self::into_inner) to
+/// write the remaining records; dropping the writer attempts to flush too but ignores errors.
+pub struct BatchWriter {
+    history: Option<History>,
+    batch: Vec<Record>,
+    batch_size: usize,
+}
+
+impl BatchWriter {
+    /// Creates writer committing a transaction every `batch_size` records.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `batch_size` is zero.
+    pub fn new(history: History, batch_size: usize) -> Self {
+        assert_ne!(batch_size, 0, "batch size must not be zero");
+
+        BatchWriter {
+            history: Some(history),
+            batch: Vec::with_capacity(batch_size),
+            batch_size,
+        }
+    }
+
+    /// Adds record into the buffer, writing the batch if it's full.
+    pub fn push(&mut self, record: Record) -> Result<(), Error> {
+        self.batch.push(record);
+        if self.batch.len() >= self.batch_size {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Writes all buffered records.
+    pub fn flush(&mut self) -> Result<(), Error> {
+        if !self.batch.is_empty() {
+            self.history.as_mut().expect("history taken").record_batch(&self.batch)?;
+            self.batch.clear();
+        }
+        Ok(())
+    }
+
+    /// Writes all buffered records and returns the underlying history.
+    pub fn into_inner(mut self) -> Result<History, Error> {
+        self.flush()?;
+        Ok(self.history.take().expect("history taken"))
+    }
+}
+
+impl Drop for BatchWriter {
+    fn drop(&mut self) {
+        if self.history.is_some() {
+            let _ = self.flush();
+        }
+    }
+}
+
+/// Price of a dish at a specific date.
+///
+/// Returned by [`History::price_history`].
+pub struct PriceRecord {
+    /// Calendar date at which the dish was offered.
+    pub date: NaiveDate,
+    /// City in which the restaurant is located.
+    pub city: String,
+    /// Name of the restaurant as used in the URL.
+    pub restaurant: String,
+    /// Description of the dish.
+    pub description: String,
+    /// Price of the dish as displayed by Zomato.
+    pub price: String,
+}
+
+impl History {
+    /// Opens the archive at given path, creating it if it doesn't exist.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        Self::init(Connection::open(path)).map_err(Error)
+    }
+
+    /// Opens temporary archive in memory.
+    ///
+    /// This is mainly useful for testing.
+    pub fn open_in_memory() -> Result<Self, Error> {
+        Self::init(Connection::open_in_memory()).map_err(Error)
+    }
+
+    fn init(connection: rusqlite::Result<Connection>) -> Result<Self, anyhow::Error> {
+        let mut connection = connection?;
+        migrate(&mut connection)?;
+        Ok(History { connection, price_bounds: PriceBounds::default(), })
+    }
+
+    /// Sets the bounds of plausible prices used by the [statistics](stats) and the
+    /// [weekly digests](digest).
+    ///
+    /// [`PriceBounds::default`] is used unless changed.
+    pub fn with_price_bounds(mut self, bounds: PriceBounds) -> Self {
+        self.price_bounds = bounds;
+        self
+    }
+
+    /// Returns the version of the schema of the database.
+    ///
+    /// This is always [`SCHEMA_VERSION`] since the database is upgraded when opened.
+    pub fn schema_version(&self) -> Result<u32, Error> {
+        schema_version(&self.connection).map_err(|error| Error(error.into()))
+    }
+
+    /// Appends menus fetched just now.
+    pub fn record(&mut self, city: &str, restaurant: &str, menus: &[Menu]) -> Result<(), Error> {
+        self.record_at(city, restaurant, menus, SystemTime::now())
+    }
+
+    /// Appends menus fetched at given time.
+    pub fn record_at(&mut self, city: &str, restaurant: &str, menus: &[Menu], fetched_at: SystemTime) -> Result<(), Error> {
+        self.record_internal(std::iter::once((city, restaurant, menus, fetched_at))).map_err(Error)
+    }
+
+    /// Appends multiple records in a single transaction.
+    ///
+    /// This is a lot faster than recording them one by one. Either all records are stored or none
+    /// of them is.
+    pub fn record_batch(&mut self, records: &[Record]) -> Result<(), Error> {
+        let records = records
+            .iter()
+            .map(|record| (&*record.city, &*record.restaurant, &*record.menus, record.fetched_at));
+        self.record_internal(records).map_err(Error)
+    }
+
+    fn record_internal<'a, I>(&mut self, records: I) -> Result<(), anyhow::Error> where I: IntoIterator<Item = (&'a str, &'a str, &'a [Menu], SystemTime)> {
+        let transaction = self.connection.transaction()?;
+        for (city, restaurant, menus, fetched_at) in records {
+            let timestamp = fetched_at.duration_since(UNIX_EPOCH)?.as_secs() as i64;
+            let fetch_date = chrono::Local.timestamp_opt(timestamp, 0).single().map(|time| time.date_naive());
+            let mut insert_menu = transaction.prepare_cached("INSERT INTO menus (fetched_at, city, restaurant, heading, date) VALUES (?1, ?2, ?3, ?4, ?5)")?;
+            let mut insert_item = transaction.prepare_cached("INSERT INTO items (menu_id, position, description, price) VALUES (?1, ?2, ?3, ?4)")?;
+            for menu in menus {
+                let date = fetch_date.and_then(|fetch_date| date::resolve_heading(&menu.date, fetch_date, None));
+                let menu_id = insert_menu.insert(params![timestamp, city, restaurant, menu.date, date.map(|date| date.to_string())])?;
+                for (position, item) in menu.items.iter().enumerate() {
+                    insert_item.execute(params![menu_id, position as i64, item.description, item.price])?;
+                }
+            }
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+
+    /// Returns menus of all restaurants offered at given date.
+    ///
+    /// If the menu of a restaurant was recorded multiple times, only the most recent record is
+    /// returned.
+    pub fn menus_for_date(&self, date: NaiveDate) -> Result<Vec<StoredMenu>, Error> {
+        self.menus_for_date_internal(date).map_err(Error)
+    }
+
+    fn menus_for_date_internal(&self, date: NaiveDate) -> Result<Vec<StoredMenu>, anyhow::Error> {
+        let mut statement = self.connection.prepare(
+            "SELECT id, fetched_at, city, restaurant, heading FROM menus AS m
+             WHERE date = ?1 AND fetched_at = (
+                 SELECT MAX(fetched_at) FROM menus WHERE date = m.date AND city = m.city AND restaurant = m.restaurant
+             )
+             ORDER BY city, restaurant"
+        )?;
+        let rows = statement.query_map(params![date.to_string()], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?, row.get::<_, String>(2)?, row.get::<_, String>(3)?, row.get::<_, String>(4)?))
+        })?;
+
+        let parser = Parser::new();
+        let mut result = Vec::new();
+        for row in rows {
+            let (id, fetched_at, city, restaurant, heading) = row?;
+            let mut items = self.connection.prepare_cached("SELECT description, price FROM items WHERE menu_id = ?1 ORDER BY position")?;
+            let items = items
+                .query_map(params![id], |row| {
+                    // Allergens and portions aren't stored since they can be extracted from the description.
+                    Ok(parser.menu_item(row.get(0)?, row.get(1)?))
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+
+            result.push(StoredMenu {
+                city,
+                restaurant,
+                fetched_at: UNIX_EPOCH + Duration::from_secs(fetched_at as u64),
+                date: Some(date),
+                menu: Menu {
+                    date: heading,
+                    items,
+                    ocr: false,
+                    valid_from: None,
+                    valid_to: None,
+                    is_current: false,
+                },
+            });
+        }
+        Ok(result)
+    }
+
+    /// Returns the menus of the restaurant with known dates along with their revisions.
+    ///
+    /// The menus are ordered by date, each date contains the most recent record. A record counts
+    /// as a new revision if its dishes or prices differ from the previous record of the same date,
+    /// so recording backfilled or corrected menus bumps the sequence while fetching unchanged
+    /// menus repeatedly doesn't. This makes it possible to regenerate calendars and feeds from the
+    /// history idempotently, see [`ical::dated_to_ics`](crate::export::ical::dated_to_ics) and
+    /// [`feed::dated_to_atom`](crate::export::feed::dated_to_atom).
+    ///
+    /// ```
+    /// use std::time::{Duration, SystemTime};
+    /// use zomato::history::History;
+    ///
+    /// let mut history = History::open_in_memory()?;
+    /// let mut menus = zomato::parse_daily_menu(zomato::testing::FIXTURE)?;
+    /// let fetched_at = SystemTime::now() - Duration::from_secs(60);
+    /// history.record_at("bratislava", "u-zlateho-bazanta", &menus, fetched_at)?;
+    /// history.record("bratislava", "u-zlateho-bazanta", &menus)?;
+    /// assert_eq!(history.dated_menus("bratislava", "u-zlateho-bazanta")?[0].sequence, 0);
+    ///
+    /// menus[0].items[0].price = "€1.50".to_owned();
+    /// history.record("bratislava", "u-zlateho-bazanta", &menus)?;
+    /// let dated = history.dated_menus("bratislava", "u-zlateho-bazanta")?;
+    /// assert_eq!(dated.len(), 1);
+    /// assert_eq!(dated[0].sequence, 1);
+    /// assert_eq!(dated[0].menu.items[0].price, "€1.50");
+    /// # Ok::<_, zomato::Error>(())
+    /// ```
+    pub fn dated_menus(&self, city: &str, restaurant: &str) -> Result<Vec<DatedMenu>, Error> {
+        self.dated_menus_internal(city, restaurant).map_err(Error)
+    }
+
+    fn dated_menus_internal(&self, city: &str, restaurant: &str) -> Result<Vec<DatedMenu>, anyhow::Error> {
+        let mut statement = self.connection.prepare(
+            "SELECT id, fetched_at, heading, date FROM menus
+             WHERE city = ?1 AND restaurant = ?2 AND date IS NOT NULL
+             ORDER BY date, fetched_at, id"
+        )?;
+        let rows = statement.query_map(params![city, restaurant], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?, row.get::<_, String>(2)?, row.get::<_, String>(3)?))
+        })?;
+
+        let parser = Parser::new();
+        let mut result = Vec::<DatedMenu>::new();
+        for row in rows {
+            let (id, fetched_at, heading, date) = row?;
+            let date = date.parse::<NaiveDate>()?;
+            let fetched_at = UNIX_EPOCH + Duration::from_secs(fetched_at as u64);
+            let mut items = self.connection.prepare_cached("SELECT description, price FROM items WHERE menu_id = ?1 ORDER BY position")?;
+            let items = items
+                .query_map(params![id], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?
+                .collect::<Result<Vec<_>, _>>()?;
+
+            match result.last_mut().filter(|previous| previous.date == date) {
+                Some(previous) => {
+                    let unchanged = previous.menu.items.len() == items.len() && previous
+                        .menu
+                        .items
+                        .iter()
+                        .zip(&items)
+                        .all(|(item, (description, price))| item.raw_description == *description && item.price == *price);
+                    if !unchanged {
+                        previous.sequence += 1;
+                        previous.modified = fetched_at;
+                    }
+                    previous.menu = Menu {
+                        date: heading,
+                        items: items.into_iter().map(|(description, price)| parser.menu_item(description, price)).collect(),
+                        ocr: false,
+                        valid_from: None,
+                        valid_to: None,
+                        is_current: false,
+                    };
+                },
+                None => result.push(DatedMenu {
+                    date,
+                    menu: Menu {
+                        date: heading,
+                        items: items.into_iter().map(|(description, price)| parser.menu_item(description, price)).collect(),
+                        ocr: false,
+                        valid_from: None,
+                        valid_to: None,
+                        is_current: false,
+                    },
+                    sequence: 0,
+                    modified: fetched_at,
+                }),
+            }
+        }
+        Ok(result)
+    }
+
+    /// Returns prices of dishes containing `dish_pattern` in their descriptions, ordered by date.
+    ///
+    /// The matching is case-insensitive for ASCII characters only.
+    pub fn price_history(&self, dish_pattern: &str) -> Result<Vec<PriceRecord>, Error> {
+        self.price_history_internal(dish_pattern).map_err(Error)
+    }
+
+    fn price_history_internal(&self, dish_pattern: &str) -> Result<Vec<PriceRecord>, anyhow::Error> {
+        let mut statement = self.connection.prepare(
+            "SELECT m.date, m.city, m.restaurant, i.description, i.price FROM items AS i
+             JOIN menus AS m ON m.id = i.menu_id
+             WHERE m.date IS NOT NULL AND i.description LIKE ?1 ESCAPE '\\' AND m.fetched_at = (
+                 SELECT MAX(fetched_at) FROM menus WHERE date = m.date AND city = m.city AND restaurant = m.restaurant
+             )
+             ORDER BY m.date, m.city, m.restaurant, i.position"
+        )?;
+        let pattern = format!("%{}%", dish_pattern.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_"));
+        let rows = statement.query_map(params![pattern], |row| {
+            Ok((row.get::<_, String>(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?))
+        })?;
+
+        let mut result = Vec::new();
+        for row in rows {
+            let (date, city, restaurant, description, price) = row?;
+            result.push(PriceRecord {
+                date: date.parse()?,
+                city,
+                restaurant,
+                description,
+                price,
+            });
+        }
+        Ok(result)
+    }
+}
+
+fn schema_version(connection: &Connection) -> rusqlite::Result<u32> {
+    connection.query_row("PRAGMA user_version", [], |row| row.get(0))
+}
+
+/// Applies the migrations the database is missing, each in its own transaction.
+///
+/// The transactions take the write lock before reading the version, so that processes opening
+/// the database concurrently don't apply the same migration twice.
+fn migrate(connection: &mut Connection) -> Result<(), anyhow::Error> {
+    use anyhow::Context;
+
+    loop {
+        let transaction = connection.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        let version = schema_version(&transaction)?;
+        if version > SCHEMA_VERSION {
+            return Err(SchemaError::TooNew { found: version, supported: SCHEMA_VERSION }.into());
+        }
+        let migration = match MIGRATIONS.get(version as usize) {
+            Some(migration) => migration,
+            None => return Ok(()),
+        };
+        transaction
+            .execute_batch(migration)
+            .with_context(|| format!("failed to migrate the history database to version {}", version + 1))?;
+        // PRAGMA doesn't support parameters
+        transaction.execute_batch(&format!("PRAGMA user_version = {}", version + 1))?;
+        transaction.commit()?;
+    }
+}
+
+/// Changes of the schema, the index of each migration plus one is the resulting version.
+///
+/// Never modify existing migrations, always append new ones. Databases created before
+/// migrations were introduced have version 0 but already contain the tables, so the first
+/// migration must not fail if they exist.
+const MIGRATIONS: &[&str] = &[
+    // 1: initial schema
+    "
+CREATE TABLE IF NOT EXISTS menus (
+    id INTEGER PRIMARY KEY,
+    fetched_at INTEGER NOT NULL,
+    city TEXT NOT NULL,
+    restaurant TEXT NOT NULL,
+    heading TEXT NOT NULL,
+    date TEXT
+);
+CREATE INDEX IF NOT EXISTS menus_date ON menus (date, city, restaurant);
+CREATE TABLE IF NOT EXISTS items (
+    menu_id INTEGER NOT NULL REFERENCES menus (id),
+    position INTEGER NOT NULL,
+    description TEXT NOT NULL,
+    price TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS items_menu ON items (menu_id);
+",
+];
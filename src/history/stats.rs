@@ -0,0 +1,189 @@
+//! Price statistics computed from the stored menus.
+//!
+//! Only items with prices that can be parsed (see [`Price`](crate::Price)) and are
+//! [plausible](crate::PriceBounds) and menus with known dates are taken into account, the bounds
+//! are set using [`History::with_price_bounds`]. The amounts are not converted between
+//! currencies, so mixing restaurants from different countries in a single comparison makes
+//! little sense.
+//!
+//! ```
+//! use zomato::history::History;
+//!
+//! let mut history = History::open_in_memory()?;
+//! let menus = zomato::parse_daily_menu(zomato::testing::FIXTURE)?;
+//! history.record("bratislava", "u-zlateho-bazanta", &menus)?;
+//!
+//! let stats = &history.average_prices()?[0];
+//! assert_eq!(stats.samples, 3);
+//! assert_eq!(stats.min, 1.2);
+//! assert_eq!(stats.max, 7.2);
+//! let today = chrono::Local::now().date_naive();
+//! let weekdays = history.weekday_prices("bratislava", "u-zlateho-bazanta")?;
+//! assert_eq!(weekdays.cheapest, Some(chrono::Datelike::weekday(&today)));
+//!
+//! let history = history.with_price_bounds(zomato::PriceBounds::default().max("€", 5.0));
+//! assert_eq!(history.average_prices()?[0].samples, 1);
+//! # Ok::<_, zomato::Error>(())
+//! ```
+
+use std::collections::BTreeMap;
+use chrono::{Datelike, NaiveDate, Weekday};
+use crate::{Error, Price};
+use super::{History, PriceRecord};
+
+/// Summary of prices of a single restaurant.
+#[derive(Clone, Debug)]
+pub struct RestaurantPriceStats {
+    /// City in which the restaurant is located.
+    pub city: String,
+    /// Name of the restaurant as used in the URL.
+    pub restaurant: String,
+    /// Average price of all dishes.
+    pub average: f64,
+    /// Price of the cheapest dish.
+    pub min: f64,
+    /// Price of the most expensive dish.
+    pub max: f64,
+    /// Currency of the prices, taken from the first dish.
+    pub currency: String,
+    /// Number of dishes the statistics were computed from.
+    pub samples: usize,
+}
+
+/// Average price of dishes offered at a specific day of the week.
+#[derive(Clone, Debug)]
+pub struct WeekdayPrice {
+    /// Day of the week.
+    pub weekday: Weekday,
+    /// Average price of dishes offered at this day.
+    pub average: f64,
+    /// Number of dishes the average was computed from.
+    pub samples: usize,
+}
+
+/// Prices of a restaurant by the day of the week.
+#[derive(Clone, Debug)]
+pub struct WeekdayStats {
+    /// Averages for days at which the restaurant offered any dishes, starting with Monday.
+    pub by_weekday: Vec<WeekdayPrice>,
+    /// Day with the lowest average price.
+    pub cheapest: Option<Weekday>,
+    /// Day with the highest average price.
+    pub most_expensive: Option<Weekday>,
+}
+
+/// Price of a dish at a specific date.
+#[derive(Clone, Debug)]
+pub struct PricePoint {
+    /// Calendar date at which the dish was offered.
+    pub date: NaiveDate,
+    /// City in which the restaurant is located.
+    pub city: String,
+    /// Name of the restaurant as used in the URL.
+    pub restaurant: String,
+    /// Parsed price.
+    pub price: f64,
+}
+
+/// Development of the price of a dish over time.
+#[derive(Clone, Debug)]
+pub struct DishTrend {
+    /// Description of the dish.
+    pub description: String,
+    /// Prices ordered by date.
+    pub points: Vec<PricePoint>,
+}
+
+impl History {
+    /// Computes price statistics for each restaurant, ordered by city and restaurant.
+    pub fn average_prices(&self) -> Result<Vec<RestaurantPriceStats>, Error> {
+        let mut stats = BTreeMap::<(String, String), RestaurantPriceStats>::new();
+        for (record, Price { amount, currency }) in self.parsed_prices("")? {
+            let entry = stats
+                .entry((record.city.clone(), record.restaurant.clone()))
+                .or_insert_with(|| RestaurantPriceStats {
+                    city: record.city,
+                    restaurant: record.restaurant,
+                    // temporarily holds the sum
+                    average: 0.0,
+                    min: f64::INFINITY,
+                    max: f64::NEG_INFINITY,
+                    currency,
+                    samples: 0,
+                });
+            entry.average += amount;
+            entry.min = entry.min.min(amount);
+            entry.max = entry.max.max(amount);
+            entry.samples += 1;
+        }
+
+        Ok(stats
+            .into_values()
+            .map(|mut stats| {
+                stats.average /= stats.samples as f64;
+                stats
+            })
+            .collect())
+    }
+
+    /// Computes average prices of the restaurant for each day of the week.
+    pub fn weekday_prices(&self, city: &str, restaurant: &str) -> Result<WeekdayStats, Error> {
+        let mut sums = [(0.0, 0); 7];
+        for (record, price) in self.parsed_prices("")? {
+            if record.city == city && record.restaurant == restaurant {
+                let sum = &mut sums[record.date.weekday().num_days_from_monday() as usize];
+                sum.0 += price.amount;
+                sum.1 += 1;
+            }
+        }
+
+        let by_weekday = sums
+            .iter()
+            .zip(std::iter::successors(Some(Weekday::Mon), |day| Some(day.succ())))
+            .filter(|((_, samples), _)| *samples > 0)
+            .map(|(&(sum, samples), weekday)| WeekdayPrice {
+                weekday,
+                average: sum / samples as f64,
+                samples,
+            })
+            .collect::<Vec<_>>();
+        let cheapest = by_weekday.iter().min_by(|a, b| a.average.total_cmp(&b.average)).map(|day| day.weekday);
+        let most_expensive = by_weekday.iter().max_by(|a, b| a.average.total_cmp(&b.average)).map(|day| day.weekday);
+
+        Ok(WeekdayStats {
+            by_weekday,
+            cheapest,
+            most_expensive,
+        })
+    }
+
+    /// Returns price development of each dish containing `dish_pattern` in its description.
+    ///
+    /// The dishes are ordered by description.
+    pub fn dish_price_trends(&self, dish_pattern: &str) -> Result<Vec<DishTrend>, Error> {
+        let mut trends = BTreeMap::<String, Vec<PricePoint>>::new();
+        for (record, price) in self.parsed_prices(dish_pattern)? {
+            trends.entry(record.description).or_default().push(PricePoint {
+                date: record.date,
+                city: record.city,
+                restaurant: record.restaurant,
+                price: price.amount,
+            });
+        }
+
+        Ok(trends
+            .into_iter()
+            .map(|(description, points)| DishTrend { description, points, })
+            .collect())
+    }
+
+    fn parsed_prices(&self, dish_pattern: &str) -> Result<impl Iterator<Item = (PriceRecord, Price)> + '_, Error> {
+        let records = self.price_history_internal(dish_pattern).map_err(Error)?;
+        Ok(records
+            .into_iter()
+            .filter_map(move |record| {
+                let price = record.price.parse().ok().filter(|price| self.price_bounds.is_plausible(price))?;
+                Some((record, price))
+            }))
+    }
+}
@@ -0,0 +1,168 @@
+//! Log of changes of the structure of Zomato pages.
+//!
+//! The [layout](Layout) of each downloaded daily menu page is detected and if its
+//! [generation](Layout::generation) wasn't seen before, a record is appended to the log as a
+//! single JSON line:
+//!
+//! ```json
+//! {"timestamp":"2020-06-01T10:15:00Z","generation":"5c2f...","markers":["dish:displayPrice",...],"sample_hash":"9d1e...","url":"https://www.zomato.com/..."}
+//! ```
+//!
+//! A new record warns that Zomato changed its pages, usually before the parser breaks. Comparing
+//! the markers with the previous record shows what changed and the SHA-256 hash of the page
+//! identifies the sample which should be saved for fixing the parser. `zomato doctor` prints the
+//! records and the [server](crate::server) returns them at `GET /status`.
+//!
+//! ```
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use std::sync::Arc;
+//! use zomato::layout::LayoutLog;
+//! use zomato::testing::{MockTransport, FIXTURE};
+//!
+//! # let dir = mktemp::Temp::new_dir()?;
+//! # let path = dir.as_path().join("layouts.jsonl");
+//! let log = Arc::new(LayoutLog::open(&path)?);
+//! let client = zomato::Client::builder()
+//!     .transport(MockTransport::serving(FIXTURE))
+//!     .layout_log(Arc::clone(&log))
+//!     .build()?;
+//! client.get_daily_menu("bratislava", "u-zlateho-bazanta").await?;
+//! client.get_daily_menu("bratislava", "u-kohuta").await?;
+//!
+//! // both pages have the same layout
+//! let records = zomato::layout::read_records(&path)?;
+//! assert_eq!(records.len(), 1);
+//! assert!(records[0].url.ends_with("/u-zlateho-bazanta/daily-menu"));
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::SystemTime;
+use serde_derive::{Deserialize, Serialize};
+use crate::Error;
+
+pub use zomato_core::layout::Layout;
+
+/// Page of a previously unseen layout.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct LayoutRecord {
+    /// When the page was downloaded.
+    #[serde(with = "crate::timestamp::rfc3339")]
+    pub timestamp: SystemTime,
+    /// The [generation](Layout::generation) of the layout.
+    pub generation: String,
+    /// The [markers](Layout::markers) of the layout in alphabetical order.
+    pub markers: Vec<String>,
+    /// Hex-encoded SHA-256 hash of the page.
+    pub sample_hash: String,
+    /// URL of the page.
+    pub url: String,
+}
+
+impl LayoutRecord {
+    /// Describes the page downloaded now.
+    pub fn new(url: &str, html: &str) -> Self {
+        use sha2::{Digest, Sha256};
+
+        let layout = Layout::detect(html);
+        let sample_hash = Sha256::digest(html.as_bytes())
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        LayoutRecord {
+            timestamp: SystemTime::now(),
+            generation: layout.generation(),
+            markers: layout.markers().map(ToOwned::to_owned).collect(),
+            sample_hash,
+            url: url.to_owned(),
+        }
+    }
+}
+
+/// Append-only log of layouts.
+///
+/// Attach it to the client using [`ClientBuilder::layout_log`](crate::ClientBuilder::layout_log).
+pub struct LayoutLog {
+    state: Mutex<State>,
+}
+
+struct State {
+    file: File,
+    records: Vec<LayoutRecord>,
+}
+
+impl LayoutLog {
+    /// Opens the log at given path, creating it if it doesn't exist.
+    ///
+    /// The layouts already recorded in the log are considered known.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let records = match read_records(path) {
+            Ok(records) => records,
+            Err(_) if !path.exists() => Vec::new(),
+            Err(error) => return Err(error),
+        };
+        let file = OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(path)
+            .map_err(|error| Error(error.into()))?;
+
+        Ok(LayoutLog {
+            state: Mutex::new(State {
+                file,
+                records,
+            }),
+        })
+    }
+
+    /// Records the layout of the page if it wasn't seen before.
+    ///
+    /// Returns the appended record, `None` if the layout is already known.
+    pub fn observe(&self, url: &str, html: &str) -> io::Result<Option<LayoutRecord>> {
+        let record = LayoutRecord::new(url, html);
+        let mut state = self.state.lock().unwrap_or_else(|error| error.into_inner());
+        if state.records.iter().any(|known| known.generation == record.generation) {
+            return Ok(None);
+        }
+        let mut line = serde_json::to_vec(&record).map_err(io::Error::other)?;
+        line.push(b'\n');
+        // Single write call so that lines aren't interleaved.
+        state.file.write_all(&line)?;
+        state.file.flush()?;
+        state.records.push(record.clone());
+        Ok(Some(record))
+    }
+
+    /// Returns all records in the order they were appended.
+    pub fn records(&self) -> Vec<LayoutRecord> {
+        self.state.lock().unwrap_or_else(|error| error.into_inner()).records.clone()
+    }
+}
+
+/// Reads records stored in the log at given path, in the order they were appended.
+pub fn read_records<P: AsRef<Path>>(path: P) -> Result<Vec<LayoutRecord>, Error> {
+    read_records_internal(path.as_ref()).map_err(Error)
+}
+
+fn read_records_internal(path: &Path) -> Result<Vec<LayoutRecord>, anyhow::Error> {
+    use anyhow::Context;
+
+    let file = File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+    let mut records = Vec::new();
+    for (line_number, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record = serde_json::from_str(&line).with_context(|| format!("invalid record on line {}", line_number + 1))?;
+        records.push(record);
+    }
+    Ok(records)
+}
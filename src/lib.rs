@@ -1,4 +1,63 @@
-use serde_derive::Deserialize;
+pub mod api;
+pub mod audit;
+mod batch;
+mod calendar;
+mod client;
+mod date;
+pub mod directory;
+pub mod export;
+mod geoblock;
+pub mod format;
+pub mod layout;
+mod lunch_board;
+pub mod pipeline;
+pub mod photos;
+mod policy;
+mod query;
+mod restaurant;
+mod runtime;
+pub mod reservation;
+pub mod reviews;
+mod throttle;
+mod timestamp;
+mod watch;
+pub mod search;
+pub mod sink;
+pub mod testing;
+pub mod translate;
+pub mod transport;
+#[cfg(feature = "tts")]
+pub mod tts;
+
+pub use batch::{BatchFailure, BatchOutcome};
+pub use calendar::{business_days_from, BusinessCalendar, BusinessDays};
+pub use date::{menu_date, MaxMenuAge};
+pub use client::{Backend, Client, ClientBuilder, ParseBackendError, DEFAULT_BASE_URL, DEFAULT_DAILY_MENU_LISTING_PATH, DEFAULT_DAILY_MENU_PATH, DEFAULT_DIRECTORY_PATH, DEFAULT_INFO_PATH, DEFAULT_NEARBY_PATH, DEFAULT_PHOTOS_PATH, DEFAULT_REVIEWS_PATH, DEFAULT_SEARCH_PATH, DEFAULT_WEB_API_PATH};
+pub use lunch_board::{BoardEntry, LunchBoard, Ranking};
+pub use policy::PolicyError;
+pub use query::{Query, QueryResult};
+pub use restaurant::{ParseRestaurantRefError, RestaurantRef};
+pub use watch::MenuUpdate;
+pub use zomato_core::{borrowed, parse_daily_menu_borrowed, similarity, Course, DietaryClassifier, DietaryHint, DishBlacklist, Extraction, Lang, Menu, MenuItem, MenuQuery, ParseLangError, ParsePriceError, Parser, Price, PriceBounds, PriceWarning, Quantity, RestaurantInfo, SectionHandler, Unit};
+
+#[cfg(feature = "bench-fixtures")]
+pub mod bench_fixtures;
+#[cfg(feature = "ureq")]
+pub mod blocking;
+#[cfg(feature = "binary-cache")]
+pub mod cache;
+#[cfg(feature = "cookies")]
+pub mod cookies;
+#[cfg(feature = "history")]
+pub mod crawl;
+#[cfg(feature = "history")]
+pub mod history;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+#[cfg(feature = "ocr")]
+pub mod ocr;
+#[cfg(feature = "server")]
+pub mod server;
 
 /// Error returned when fetching fails.
 ///
@@ -8,24 +67,137 @@ use serde_derive::Deserialize;
 #[error(transparent)]
 pub struct Error (anyhow::Error);
 
-/// Food within daily menu.
-pub struct MenuItem {
-    /// Description of the food
-    pub description: String,
-    /// Food price.
-    ///
-    /// Note that sometimes the price may be empty!
-    pub price: String,
+impl From<zomato_core::Error> for Error {
+    fn from(error: zomato_core::Error) -> Self {
+        Error(error.into())
+    }
 }
 
-/// Daily menu of a restaurant.
+/// Category of [`Error`] allowing to handle some errors specially.
 ///
-/// This is a menu for specific day.
-pub struct Menu {
-    /// Date of the menu.
-    pub date: String,
-    /// Food items offered at this day.
-    pub items: Vec<MenuItem>,
+/// More kinds may be added in the future.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// Connecting, receiving data or the whole request took longer than configured.
+    ///
+    /// See [`ClientBuilder::timeout`].
+    Timeout,
+    /// The server refused to serve the client because of its region.
+    ///
+    /// Detected from `451 Unavailable For Legal Reasons` responses and `403 Forbidden` responses
+    /// explaining that the service is not available in the region. Retrying won't help, the
+    /// requests have to be routed through a [proxy](ClientBuilder::proxy) in a different region.
+    GeoBlocked {
+        /// The region mentioned by the server, if any.
+        region_hint: Option<String>,
+    },
+    /// Any other error.
+    Other,
+}
+
+impl Error {
+    /// Returns the category of the error.
+    ///
+    /// See also [`ClientBuilder::timeout`].
+    ///
+    /// ```
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), zomato::Error> {
+    /// use zomato::testing::{MockTransport, FIXTURE};
+    /// use zomato::transport::Response;
+    /// use zomato::ErrorKind;
+    ///
+    /// let url = "https://www.zomato.com/bratislava/u-zlateho-bazanta/daily-menu";
+    /// let blocked = Response::new(403, "<p>Zomato is not available in Russia.</p>");
+    /// let transport = MockTransport::serving(FIXTURE).queue_response(url, blocked);
+    /// let client = zomato::Client::builder().transport(transport).build()?;
+    /// let error = client.get_daily_menu("bratislava", "u-zlateho-bazanta").await.err().unwrap();
+    /// assert_eq!(error.kind(), ErrorKind::GeoBlocked { region_hint: Some("Russia".to_owned()) });
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn kind(&self) -> ErrorKind {
+        if let Some(error) = self.0.downcast_ref::<geoblock::GeoBlockedError>() {
+            return ErrorKind::GeoBlocked { region_hint: error.region_hint.clone() };
+        }
+
+        let is_timeout = |error: &(dyn std::error::Error + 'static)| {
+            #[cfg(feature = "reqwest")]
+            if error.downcast_ref::<reqwest::Error>().is_some_and(reqwest::Error::is_timeout) {
+                return true;
+            }
+            #[cfg(feature = "isahc")]
+            if error.downcast_ref::<isahc::Error>().is_some_and(isahc::Error::is_timeout) {
+                return true;
+            }
+            error.is::<transport::TimeoutError>()
+                || error.downcast_ref::<std::io::Error>().is_some_and(|error| error.kind() == std::io::ErrorKind::TimedOut)
+        };
+        // errors returned by transports are boxed, so they are not visible in the chain directly
+        let transport_error = self.0
+            .downcast_ref::<transport::TransportError>()
+            .map(|error| &**error as &(dyn std::error::Error + 'static));
+        let transport_chain = std::iter::successors(transport_error, |error| error.source());
+        if self.0.chain().chain(transport_chain).any(is_timeout) {
+            ErrorKind::Timeout
+        } else {
+            ErrorKind::Other
+        }
+    }
+
+    /// Returns the violated policy if the request was refused because of it.
+    ///
+    /// ```
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// # let client = zomato::testing::fixture_client();
+    /// let error = client.get_daily_menu("bratislava", "@evil.example").await.err().unwrap();
+    /// assert!(matches!(error.policy_violation(), Some(zomato::PolicyError::InvalidSegment { .. })));
+    /// # }
+    /// ```
+    pub fn policy_violation(&self) -> Option<&PolicyError> {
+        self.0.downcast_ref()
+    }
+
+    /// Returns the error described by the [official API](crate::api) if it refused the request.
+    ///
+    /// ```
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), zomato::Error> {
+    /// use zomato::api::ApiClient;
+    /// use zomato::testing::MockTransport;
+    /// use zomato::transport::Response;
+    ///
+    /// let url = "https://developers.zomato.com/api/v2.1/dailymenu?res_id=1";
+    /// let invalid_key = Response::new(403, r#"{"code": 403, "status": "Forbidden", "message": "Invalid API Key"}"#);
+    /// let transport = MockTransport::new().queue_response(url, invalid_key);
+    /// let client = ApiClient::from_client(ApiClient::builder("invalid").transport(transport).build()?);
+    /// let error = client.get_daily_menu(1).await.err().unwrap();
+    /// assert_eq!(error.api_error().unwrap().message, "Invalid API Key");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn api_error(&self) -> Option<&api::ApiError> {
+        self.0.downcast_ref()
+    }
+
+    /// Returns the schema error if the history database couldn't be opened because of it.
+    ///
+    /// ```
+    /// # let dir = mktemp::Temp::new_dir().unwrap();
+    /// # let path = dir.as_path().join("history.sqlite");
+    /// let connection = rusqlite::Connection::open(&path).unwrap();
+    /// connection.execute_batch("PRAGMA user_version = 1000").unwrap();
+    /// drop(connection);
+    ///
+    /// let error = zomato::history::History::open(&path).err().unwrap();
+    /// assert!(matches!(error.schema_error(), Some(zomato::history::SchemaError::TooNew { found: 1000, .. })));
+    /// ```
+    #[cfg(feature = "history")]
+    pub fn schema_error(&self) -> Option<&history::SchemaError> {
+        self.0.downcast_ref()
+    }
 }
 
 /// Fetches daily manu of given restaurant.
@@ -34,123 +206,33 @@ pub struct Menu {
 ///
 /// This returns heap-allocated menu, because iterator would require self-referential return value.
 /// If you happen to have some clever idea to work around this, I'll happily accept a PR.
+///
+/// The menus are returned the earliest first, see [`Page::menus`](zomato_core::Page::menus).
+///
+/// This creates a new [`Client`] for each call, consider reusing the client if you make multiple
+/// requests.
 pub async fn get_daily_menu(city: &str, restaurant: &str) -> Result<Vec<Menu>, Error> {
-    get_daily_menu_internal(city, restaurant).await.map_err(Error)
-}
-
-#[derive(Deserialize, Debug)]
-struct InternalMenuItem {
-    name: String,
-    #[serde(rename = "displayPrice")]
-    price: String,
-}
-
-#[derive(Deserialize)]
-struct DailyMenu {
-    dishes: Vec<InternalMenuItem>,
-    #[serde(rename = "timeHeading")]
-    date: String,
-}
-
-#[derive(Deserialize)]
-struct Sections {
-    #[serde(rename = "SECTION_DAILY_MENU")]
-    daily_menu: Vec<DailyMenu>,
-}
-
-#[derive(Deserialize)]
-struct UnknownObject {
-    sections: Sections,
+    get_daily_menu_with_parser(city, restaurant, &mut Parser::new()).await
 }
 
-#[derive(Deserialize)]
-struct Pages {
-    restaurant: std::collections::HashMap<String, UnknownObject>,
-}
-
-#[derive(Deserialize)]
-struct Data {
-    pages: Pages,
+/// Fetches daily menu of given restaurant using custom parser.
+///
+/// This allows handling sections of the page not supported by this crate, see [`Parser`].
+pub async fn get_daily_menu_with_parser(city: &str, restaurant: &str, parser: &mut Parser<'_>) -> Result<Vec<Menu>, Error> {
+    Client::new()?.get_daily_menu_with_parser(city, restaurant, parser).await
 }
 
-// We use internal function with `anyhow::Error` for convenience and it gets translated into our
-// `Error` in the public function. This allows us to maintain ability to extend error type with
-// information, while making it easy to write the initial version of library.
-async fn get_daily_menu_internal(city: &str, restaurant: &str) -> Result<Vec<Menu>, anyhow::Error> {
-    use scraper::Selector;
-    use anyhow::Context;
-
-    let url = format!("https://www.zomato.com/{}/{}/daily-menu", city, restaurant);
-    #[cfg(feature = "debug-log")]
-    let verbose = true;
-    #[cfg(not(feature = "debug-log"))]
-    let verbose = false;
-    let req_builder = reqwest::Client::builder()
-        .connection_verbose(verbose)
-        .build()?
-        .request(reqwest::Method::GET, &url)
-        // I found that zomato server has some problems when some headers are passed,
-        // so I copied everything from Mozilla Firefox.
-        .header("User-Agent", "Mozilla/5.0 (X11; Fedora; Linux x86_64; rv:60.0) Gecko/20100101 Firefox/60.0")
-        .header("Accept", "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8")
-        // This seems to be important
-        .header("Accept-Encoding", "identity")
-        // keep-alive must be lower case - not Keep-Alive!
-        .header("Connection", "keep-alive")
-        .header("DNT", "1")
-        .header("Upgrade-Insecure-Requests", "1")
-        .header("Cache-Control", "max-age=0")
-        .header("Accept-Language", "en-US,en;q=0.5");
-
-    let response = req_builder.send()
-        .await?
-        .bytes()
-        .await?;
-    let response_decoded = std::str::from_utf8(&response)?;
-    let html = scraper::Html::parse_document(response_decoded);
-    let script = html
-        .select(&Selector::parse("script").unwrap())
-        .into_iter()
-        .filter_map(|script| script.text().next())
-        .find(|script| script.contains("window.__PRELOADED_STATE__ = JSON.parse(\""))
-        .ok_or_else(|| anyhow::anyhow!("data not found"))?;
-
-    let mut iter = script.split("window.__PRELOADED_STATE__ = JSON.parse(\"");
-    iter.next().expect("empty split");
-    let json_with_tail = iter.next().expect("missing pattern");
-    let json_escaped = json_with_tail.split("\")\n").next().expect("empty split");
-    let mut json_unescaped = String::with_capacity(json_escaped.len());
-    for piece in json_escaped.split("\\\"") {
-        if !json_unescaped.is_empty() {
-            json_unescaped.push_str("\"");
-        }
-        json_unescaped.push_str(piece);
-    }
-    let data = serde_json::from_str::<Data>(&json_unescaped).context("failed to parse json")?;
-    let result = data
-        .pages
-        .restaurant
-        .into_iter()
-        .next()
-        .ok_or_else(|| anyhow::anyhow!("missing restaurant"))?
-        .1
-        .sections.daily_menu
-        .into_iter()
-        .map(|menu| {
-            let items = menu
-                .dishes
-                .into_iter()
-                .map(|item| MenuItem {
-                    description: item.name,
-                    price: item.price,
-                })
-                .collect::<Vec<_>>();
-            Menu {
-                items,
-                date: menu.date,
-            }
-        })
-        .collect::<Vec<_>>();
-
-    Ok(result)
+/// Parses daily menu from the HTML page previously downloaded from Zomato.
+///
+/// This is useful if you need to download the page yourself or if you want to process archived
+/// pages. See also [`zomato_core`], which provides the parser without the networking.
+///
+/// ```
+/// let menus = zomato::parse_daily_menu(zomato::testing::FIXTURE)?;
+/// assert_eq!(menus[0].date, "Today");
+/// assert_eq!(menus[0].items[0].price, "€1.20");
+/// # Ok::<_, zomato::Error>(())
+/// ```
+pub fn parse_daily_menu(html: &str) -> Result<Vec<Menu>, Error> {
+    client::parse_page(&mut Parser::new(), html).map(|page| page.menus).map_err(Error)
 }
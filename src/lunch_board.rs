@@ -0,0 +1,243 @@
+use std::time::Duration;
+use crate::{BatchFailure, BatchOutcome, Client, Error, Menu, PriceBounds, RestaurantRef};
+
+/// Compares today's menus of multiple restaurants by price.
+///
+/// Useful for answering the question "where do we eat today".
+///
+/// ```
+/// use zomato::{LunchBoard, Ranking};
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), zomato::Error> {
+/// # let client = zomato::testing::fixture_client();
+/// let board = LunchBoard::new()
+///     .restaurant("bratislava", "u-zlateho-bazanta")
+///     .restaurant("bratislava", "bistro-pod-hradom")
+///     .rank_by(Ranking::AveragePrice)
+///     .with_deadline(std::time::Duration::from_secs(30))
+///     .fetch(&client)
+///     .await?;
+/// assert!(board.is_complete());
+/// for entry in &board.ok {
+///     println!("{}: {:.2}", entry.restaurant, entry.average_price.unwrap_or(f64::NAN));
+/// }
+/// for failure in &board.failed {
+///     println!("{}: {}", failure.restaurant, failure.error);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct LunchBoard {
+    restaurants: Vec<(String, String)>,
+    ranking: Ranking,
+    deadline: Option<Duration>,
+    fail_fast: bool,
+    price_bounds: PriceBounds,
+}
+
+/// Criterion used to order restaurants on [`LunchBoard`].
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum Ranking {
+    /// Restaurants with the cheapest dish come first.
+    #[default]
+    MinimumPrice,
+    /// Restaurants with the lowest average price of dishes come first.
+    AveragePrice,
+}
+
+/// Restaurant on the [`LunchBoard`].
+pub struct BoardEntry {
+    /// City in which the restaurant is located.
+    pub city: String,
+    /// Name of the restaurant as used in the URL.
+    pub restaurant: String,
+    /// Today's menu, `None` if the restaurant doesn't publish any.
+    pub menu: Option<Menu>,
+    /// Price of the cheapest dish with known [plausible](LunchBoard::price_bounds) price.
+    pub min_price: Option<f64>,
+    /// Average price of dishes with known plausible prices.
+    pub average_price: Option<f64>,
+}
+
+impl LunchBoard {
+    /// Creates an empty board.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Adds restaurant to the board.
+    pub fn restaurant(mut self, city: impl Into<String>, restaurant: impl Into<String>) -> Self {
+        self.restaurants.push((city.into(), restaurant.into()));
+        self
+    }
+
+    /// Sets criterion used to order the restaurants.
+    pub fn rank_by(mut self, ranking: Ranking) -> Self {
+        self.ranking = ranking;
+        self
+    }
+
+    /// Limits the time fetching of the whole board may take.
+    ///
+    /// Requests that don't finish before the deadline are cancelled and their restaurants are
+    /// returned in [`timed_out`](BatchOutcome::timed_out), so the board isn't
+    /// [complete](BatchOutcome::is_complete).
+    ///
+    /// ```
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), zomato::Error> {
+    /// use std::time::Duration;
+    /// use zomato::testing::{MockTransport, FIXTURE};
+    /// use zomato::transport::{Transport, TransportFuture};
+    /// use zomato::{ErrorKind, LunchBoard};
+    ///
+    /// /// Never answers requests for the menu of Bistro Pod Hradom.
+    /// struct Stalled(MockTransport);
+    ///
+    /// impl Transport for Stalled {
+    ///     fn get<'a>(&'a self, url: &'a str) -> TransportFuture<'a> {
+    ///         if url.contains("bistro-pod-hradom") {
+    ///             Box::pin(std::future::pending())
+    ///         } else {
+    ///             self.0.get(url)
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let client = zomato::Client::with_transport(Stalled(MockTransport::serving(FIXTURE)));
+    /// let board = LunchBoard::new()
+    ///     .restaurant("bratislava", "u-zlateho-bazanta")
+    ///     .restaurant("bratislava", "bistro-pod-hradom")
+    ///     .with_deadline(Duration::from_millis(50))
+    ///     .fetch(&client)
+    ///     .await?;
+    /// assert!(!board.is_complete());
+    /// assert_eq!(board.ok.len(), 1);
+    /// assert_eq!(board.timed_out[0].to_string(), "bratislava/bistro-pod-hradom");
+    /// assert_eq!(board.into_result().err().unwrap().kind(), ErrorKind::Timeout);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Sets the bounds of plausible prices, other prices are left out of the comparison.
+    ///
+    /// [`PriceBounds::default`] is used unless changed.
+    ///
+    /// ```
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), zomato::Error> {
+    /// # let client = zomato::testing::fixture_client();
+    /// use zomato::{LunchBoard, PriceBounds};
+    ///
+    /// let board = LunchBoard::new()
+    ///     .restaurant("bratislava", "u-zlateho-bazanta")
+    ///     .price_bounds(PriceBounds::default().max("€", 5.0))
+    ///     .fetch(&client)
+    ///     .await?;
+    /// assert_eq!(board.ok[0].min_price, Some(1.2));
+    /// assert_eq!(board.ok[0].average_price, Some(1.2));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn price_bounds(mut self, bounds: PriceBounds) -> Self {
+        self.price_bounds = bounds;
+        self
+    }
+
+    /// Makes [`fetch`](Self::fetch) fail as soon as any restaurant fails.
+    ///
+    /// The outstanding requests are cancelled in such case.
+    pub fn fail_fast(mut self) -> Self {
+        self.fail_fast = true;
+        self
+    }
+
+    /// Fetches today's menus of all restaurants concurrently and returns them ordered by price.
+    ///
+    /// Restaurants without any known price are put at the end, keeping the order in which they
+    /// were added. Restaurants whose menus failed to be fetched are returned separately, unless
+    /// [`fail_fast`](Self::fail_fast) was requested, in which case the first error is returned.
+    pub async fn fetch(&self, client: &Client) -> Result<BatchOutcome<BoardEntry>, Error> {
+        let deadline = self.deadline;
+        let fetches = self.restaurants.iter().map(|(city, restaurant)| async move {
+            let fetch = client.get_daily_menu(city, restaurant);
+            // all fetches start at the same time, so they share the deadline
+            let menus = match deadline {
+                Some(deadline) => match crate::runtime::timeout(deadline, fetch).await {
+                    Ok(menus) => Some(menus?),
+                    Err(_) => None,
+                },
+                None => Some(fetch.await?),
+            };
+            Ok::<_, Error>(menus)
+        });
+        let results = if self.fail_fast {
+            futures_util::future::try_join_all(fetches).await?.into_iter().map(Ok).collect()
+        } else {
+            futures_util::future::join_all(fetches).await
+        };
+
+        let mut outcome = BatchOutcome {
+            ok: Vec::with_capacity(results.len()),
+            failed: Vec::new(),
+            timed_out: Vec::new(),
+        };
+        for ((city, restaurant), result) in self.restaurants.iter().zip(results) {
+            match result {
+                Ok(Some(menus)) => outcome.ok.push(entry(city.clone(), restaurant.clone(), menus.into_iter().next(), &self.price_bounds)),
+                Ok(None) => outcome.timed_out.push(RestaurantRef {
+                    city: city.clone(),
+                    slug: restaurant.clone(),
+                }),
+                Err(error) => outcome.failed.push(BatchFailure {
+                    city: city.clone(),
+                    restaurant: restaurant.clone(),
+                    error,
+                }),
+            }
+        }
+        self.sort(&mut outcome.ok);
+        Ok(outcome)
+    }
+
+    fn sort(&self, entries: &mut [BoardEntry]) {
+        let key = |entry: &BoardEntry| match self.ranking {
+            Ranking::MinimumPrice => entry.min_price,
+            Ranking::AveragePrice => entry.average_price,
+        };
+        // sorting is stable
+        entries.sort_by(|a, b| match (key(a), key(b)) {
+            (Some(a), Some(b)) => a.total_cmp(&b),
+            (a, b) => b.is_some().cmp(&a.is_some()),
+        });
+    }
+}
+
+fn entry(city: String, restaurant: String, menu: Option<Menu>, price_bounds: &PriceBounds) -> BoardEntry {
+    let prices = menu
+        .iter()
+        .flat_map(|menu| &menu.items)
+        .filter_map(|item| item.plausible_price(price_bounds))
+        .map(|price| price.amount)
+        .collect::<Vec<_>>();
+    let min_price = prices.iter().copied().min_by(f64::total_cmp);
+    let average_price = if prices.is_empty() {
+        None
+    } else {
+        Some(prices.iter().sum::<f64>() / prices.len() as f64)
+    };
+
+    BoardEntry {
+        city,
+        restaurant,
+        menu,
+        min_price,
+        average_price,
+    }
+}
@@ -0,0 +1,183 @@
+//! Counters and histograms describing the health of the scraper.
+//!
+//! The metrics are collected process-wide by all clients and [`parse_daily_menu`](crate::parse_daily_menu)
+//! and can be rendered in the Prometheus text format using [`Metrics::render`]. Pages parsed
+//! using [`Parser`](crate::Parser) directly are not measured. The server behind the `server` feature exposes them at
+//! `/metrics`.
+//!
+//! ```
+//! use zomato::metrics::Metrics;
+//!
+//! zomato::parse_daily_menu(zomato::testing::FIXTURE)?;
+//! let metrics = Metrics::global();
+//! assert_eq!(metrics.parse_duration().count(), 1);
+//! assert!(metrics.render().contains("zomato_parse_duration_seconds_count 1\n"));
+//! # Ok::<_, zomato::Error>(())
+//! ```
+
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Upper bounds of the buckets of [`Metrics::parse_duration`] in seconds.
+pub const PARSE_DURATION_BUCKETS: [f64; 10] = [0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0];
+
+/// Reason of a failure counted by [`Metrics::failures`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+pub enum FailureKind {
+    /// The page couldn't be downloaded.
+    Transport,
+    /// The server responded with unsuccessful HTTP status.
+    HttpStatus,
+    /// The page couldn't be parsed.
+    Parse,
+}
+
+impl FailureKind {
+    const ALL: [FailureKind; 3] = [FailureKind::Transport, FailureKind::HttpStatus, FailureKind::Parse];
+
+    /// Returns the value of the `kind` label.
+    pub fn label(self) -> &'static str {
+        match self {
+            FailureKind::Transport => "transport",
+            FailureKind::HttpStatus => "http_status",
+            FailureKind::Parse => "parse",
+        }
+    }
+}
+
+/// Monotonically increasing counter.
+#[derive(Default)]
+pub struct Counter(AtomicU64);
+
+impl Counter {
+    /// Returns the current value.
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn increment(&self) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Histogram of durations with fixed buckets.
+pub struct Histogram {
+    bounds: &'static [f64],
+    // Not cumulative, the last one counts the values above all bounds.
+    buckets: Vec<AtomicU64>,
+    sum_micros: AtomicU64,
+}
+
+impl Histogram {
+    fn new(bounds: &'static [f64]) -> Self {
+        Histogram {
+            bounds,
+            buckets: (0..=bounds.len()).map(|_| AtomicU64::new(0)).collect(),
+            sum_micros: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the number of observed values.
+    pub fn count(&self) -> u64 {
+        self.buckets.iter().map(|bucket| bucket.load(Ordering::Relaxed)).sum()
+    }
+
+    /// Returns the sum of observed values.
+    pub fn sum(&self) -> Duration {
+        Duration::from_micros(self.sum_micros.load(Ordering::Relaxed))
+    }
+
+    pub(crate) fn observe(&self, duration: Duration) {
+        let seconds = duration.as_secs_f64();
+        let bucket = self.bounds.iter().position(|bound| seconds <= *bound).unwrap_or(self.bounds.len());
+        self.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        self.sum_micros.fetch_add(duration.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    fn render(&self, out: &mut String, name: &str) {
+        let mut cumulative = 0;
+        for (bound, bucket) in self.bounds.iter().zip(&self.buckets) {
+            cumulative += bucket.load(Ordering::Relaxed);
+            let _ = writeln!(out, "{}_bucket{{le=\"{}\"}} {}", name, bound, cumulative);
+        }
+        cumulative += self.buckets[self.bounds.len()].load(Ordering::Relaxed);
+        let _ = writeln!(out, "{}_bucket{{le=\"+Inf\"}} {}", name, cumulative);
+        let _ = writeln!(out, "{}_sum {}", name, self.sum().as_secs_f64());
+        let _ = writeln!(out, "{}_count {}", name, cumulative);
+    }
+}
+
+/// Process-wide metrics.
+pub struct Metrics {
+    fetch_attempts: Counter,
+    failures: [Counter; FailureKind::ALL.len()],
+    parse_duration: Histogram,
+    cache_hits: Counter,
+    cache_misses: Counter,
+}
+
+impl Metrics {
+    /// Returns the metrics collected by this process.
+    pub fn global() -> &'static Metrics {
+        static METRICS: std::sync::OnceLock<Metrics> = std::sync::OnceLock::new();
+
+        METRICS.get_or_init(|| Metrics {
+            fetch_attempts: Counter::default(),
+            failures: Default::default(),
+            parse_duration: Histogram::new(&PARSE_DURATION_BUCKETS),
+            cache_hits: Counter::default(),
+            cache_misses: Counter::default(),
+        })
+    }
+
+    /// Number of pages requested from Zomato.
+    pub fn fetch_attempts(&self) -> &Counter {
+        &self.fetch_attempts
+    }
+
+    /// Number of failures of given kind.
+    pub fn failures(&self, kind: FailureKind) -> &Counter {
+        &self.failures[kind as usize]
+    }
+
+    /// Time spent parsing pages.
+    pub fn parse_duration(&self) -> &Histogram {
+        &self.parse_duration
+    }
+
+    /// Number of requests served from the cache of the server.
+    pub fn cache_hits(&self) -> &Counter {
+        &self.cache_hits
+    }
+
+    /// Number of requests the server couldn't serve from its cache.
+    pub fn cache_misses(&self) -> &Counter {
+        &self.cache_misses
+    }
+
+    /// Renders the metrics in the Prometheus text format.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        // Writing into String never fails
+        let _ = writeln!(out, "# HELP zomato_fetch_attempts_total Number of pages requested from Zomato.");
+        let _ = writeln!(out, "# TYPE zomato_fetch_attempts_total counter");
+        let _ = writeln!(out, "zomato_fetch_attempts_total {}", self.fetch_attempts.get());
+        let _ = writeln!(out, "# HELP zomato_failures_total Number of failed fetches by reason.");
+        let _ = writeln!(out, "# TYPE zomato_failures_total counter");
+        for kind in FailureKind::ALL {
+            let _ = writeln!(out, "zomato_failures_total{{kind=\"{}\"}} {}", kind.label(), self.failures(kind).get());
+        }
+        let _ = writeln!(out, "# HELP zomato_parse_duration_seconds Time spent parsing pages.");
+        let _ = writeln!(out, "# TYPE zomato_parse_duration_seconds histogram");
+        self.parse_duration.render(&mut out, "zomato_parse_duration_seconds");
+        let _ = writeln!(out, "# HELP zomato_cache_hits_total Number of requests served from the cache.");
+        let _ = writeln!(out, "# TYPE zomato_cache_hits_total counter");
+        let _ = writeln!(out, "zomato_cache_hits_total {}", self.cache_hits.get());
+        let _ = writeln!(out, "# HELP zomato_cache_misses_total Number of requests not served from the cache.");
+        let _ = writeln!(out, "# TYPE zomato_cache_misses_total counter");
+        let _ = writeln!(out, "zomato_cache_misses_total {}", self.cache_misses.get());
+        out
+    }
+}
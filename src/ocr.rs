@@ -0,0 +1,179 @@
+//! Recognizing daily menus published only as photos.
+//!
+//! Some restaurants never fill in the structured daily menu and upload a photo of the blackboard
+//! or a leaflet instead. [`Client::get_daily_menu_ocr`] downloads the newest such photo and turns
+//! it into [`Menu`] using the `tesseract` program, which has to be installed separately together
+//! with the data of the languages of the menus.
+//!
+//! The recognition is best-effort: every line of the text becomes an item, prices found at the
+//! end of the lines are split off. The menus are flagged using [`Menu::ocr`] so that applications
+//! can warn about possible mistakes.
+//!
+//! ```no_run
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), zomato::Error> {
+//! use zomato::ocr::Tesseract;
+//!
+//! let client = zomato::Client::new()?;
+//! let tesseract = Tesseract { language: Some("slk".to_owned()) };
+//! if let Some(menu) = client.get_daily_menu_ocr("bratislava", "u-zlateho-bazanta", &tesseract).await? {
+//!     assert!(menu.ocr);
+//!     for item in &menu.items {
+//!         println!("{}\t{}", item.description, item.price);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::io;
+use std::process::Stdio;
+use zomato_core::Parser;
+use crate::photos::Photo;
+use crate::{Client, Error, Menu};
+
+/// Hosts the photos are downloaded from.
+const IMAGE_HOSTS: &[&str] = &["b.zmtcdn.com"];
+
+/// Largest photo downloaded, in bytes.
+const MAX_IMAGE_SIZE: usize = 10 * 1024 * 1024;
+
+/// Texts following prices which are recognized as currencies.
+const CURRENCIES: &[&str] = &["€", "EUR", "Eur", "eur", "Kč", "kč", "CZK", ",-", ".-", "-"];
+
+/// The Tesseract OCR engine.
+#[derive(Clone, Debug, Default)]
+pub struct Tesseract {
+    /// Languages passed to `-l`, e.g. `slk` or `ces+eng`.
+    ///
+    /// Tesseract uses English if this is `None`.
+    pub language: Option<String>,
+}
+
+impl Tesseract {
+    /// Recognizes the text in the image, which may be in any format supported by Tesseract.
+    pub async fn recognize(&self, image: &[u8]) -> io::Result<String> {
+        use tokio::io::AsyncWriteExt;
+
+        let mut cmd = tokio::process::Command::new("tesseract");
+        cmd.arg("stdin").arg("stdout");
+        if let Some(language) = &self.language {
+            cmd.arg("-l");
+            cmd.arg(language);
+        }
+        cmd.stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true);
+        let mut child = cmd.spawn()?;
+        let mut stdin = child.stdin.take().expect("bug in tokio");
+        // Writing while the output is read avoids deadlock on large images
+        let write = async move {
+            stdin.write_all(image).await?;
+            // close
+            std::mem::drop(stdin);
+            Ok::<_, io::Error>(())
+        };
+        let (written, output) = futures_util::future::join(write, child.wait_with_output()).await;
+        let output = output?;
+        written?;
+        if !output.status.success() {
+            return Err(io::Error::other(format!("tesseract failed: {}", output.status)));
+        }
+        String::from_utf8(output.stdout).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+}
+
+/// Turns the recognized text into menu with given date.
+///
+/// Lines without letters are skipped as noise, other lines become items. Prices are recognized
+/// at the end of the lines if they have a currency or two decimal digits, so that allergens and
+/// portions aren't mistaken for them.
+///
+/// ```
+/// let text = "Denné menu\n\nCesnaková polievka 0,33l (1,7)\n1. Vyprážaný syr, hranolky 150g  6,90 €\n2. Kurací rezeň €7.20\n~~\n";
+/// let menu = zomato::ocr::parse_text(text, "2026-10-12".to_owned());
+/// assert!(menu.ocr);
+/// assert_eq!(menu.items.len(), 4);
+/// assert_eq!(menu.items[1].price, "");
+/// assert_eq!(menu.items[1].allergens, [1, 7]);
+/// assert_eq!(menu.items[2].description, "1. Vyprážaný syr, hranolky 150g");
+/// assert_eq!(menu.items[2].price, "6,90 €");
+/// assert_eq!(menu.items[3].price, "€7.20");
+/// ```
+pub fn parse_text(text: &str, date: String) -> Menu {
+    let parser = Parser::new();
+    let items = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.chars().any(char::is_alphabetic))
+        .map(|line| {
+            let (description, price) = split_price(line);
+            parser.menu_item(description.to_owned(), price.to_owned())
+        })
+        .collect();
+
+    Menu {
+        date,
+        items,
+        ocr: true,
+        valid_from: None,
+        valid_to: None,
+        is_current: false,
+    }
+}
+
+/// Splits the line into the description and the price at its end, which may be empty.
+fn split_price(line: &str) -> (&str, &str) {
+    let not_found = (line, "");
+    let last_digit = match line.rfind(|c: char| c.is_ascii_digit()) {
+        Some(position) => position,
+        None => return not_found,
+    };
+    let suffix = line[(last_digit + 1)..].trim();
+    if !suffix.is_empty() && !CURRENCIES.contains(&suffix) {
+        return not_found;
+    }
+    let number_start = line[..last_digit].trim_end_matches(|c: char| c.is_ascii_digit() || c == '.' || c == ',').len();
+    let number = &line[number_start..=last_digit];
+    let start = line[..number_start].trim_end().strip_suffix('€').map_or(number_start, str::len);
+    let has_currency = !suffix.is_empty() || start != number_start;
+    // Without currency only prices with decimals are recognized, "(1,3)" would be a price otherwise
+    let has_decimals = number.rsplit_once([',', '.']).is_some_and(|(_, decimals)| decimals.len() == 2);
+    let description = line[..start].trim_end();
+    // The price has to be a separate word
+    if !(has_currency || has_decimals) || description.is_empty() || description.len() == start {
+        return not_found;
+    }
+    (description, &line[start..])
+}
+
+impl Client {
+    /// Recognizes the daily menu of the restaurant from the newest photo of the daily menu.
+    ///
+    /// Returns `None` if the restaurant didn't upload any photo of the daily menu. The date of
+    /// the menu is the day the photo was uploaded, `Today` if it's unknown.
+    ///
+    /// The photo is downloaded using the transport of the client, so the configured proxy,
+    /// headers, rate limit and timeouts apply to it too.
+    pub async fn get_daily_menu_ocr(&self, city: &str, restaurant: &str, tesseract: &Tesseract) -> Result<Option<Menu>, Error> {
+        let photo = match self.get_daily_menu_photos(city, restaurant).await?.into_iter().next() {
+            Some(photo) => photo,
+            None => return Ok(None),
+        };
+        self.recognize_photo(&photo, tesseract).await.map(Some).map_err(Error)
+    }
+
+    async fn recognize_photo(&self, photo: &Photo, tesseract: &Tesseract) -> Result<Menu, anyhow::Error> {
+        use anyhow::Context;
+
+        let hosts = IMAGE_HOSTS.iter().map(|host| (*host).to_owned()).collect::<Vec<_>>();
+        let image = self.fetch_bytes(&photo.url, &hosts, MAX_IMAGE_SIZE).await?;
+        let text = tesseract.recognize(&image).await.context("failed to run tesseract")?;
+        let date = match photo.uploaded {
+            Some(uploaded) => uploaded.date_naive().format("%Y-%m-%d").to_string(),
+            None => "Today".to_owned(),
+        };
+        Ok(parse_text(&text, date))
+    }
+}
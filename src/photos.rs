@@ -0,0 +1,142 @@
+//! Photos of restaurants, including the daily menus published as images.
+//!
+//! Many restaurants don't fill in the structured daily menu and upload a photo of the blackboard
+//! or a scanned leaflet instead. [`Client::get_daily_menu_photos`] returns such photos, the newest
+//! first, so that applications can display them or pass them to OCR.
+//!
+//! Only the URLs are returned, the images are hosted on the CDN of Zomato, not on the host the
+//! client is allowed to fetch.
+//!
+//! ```
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), zomato::Error> {
+//! use zomato::photos::PhotoKind;
+//! use zomato::testing::{MockTransport, PHOTOS_FIXTURE};
+//!
+//! let url = "https://www.zomato.com/bratislava/u-zlateho-bazanta/photos";
+//! let client = zomato::Client::with_transport(MockTransport::new().with_page(url, PHOTOS_FIXTURE));
+//! let photos = client.get_photos("bratislava", "u-zlateho-bazanta").await?;
+//! assert_eq!(photos.len(), 5);
+//! assert_eq!(photos[0].kind, PhotoKind::Food);
+//!
+//! let menus = client.get_daily_menu_photos("bratislava", "u-zlateho-bazanta").await?;
+//! assert_eq!(menus.len(), 2);
+//! assert_eq!(menus[0].caption.as_deref(), Some("Denné menu 12.10."));
+//! assert_eq!(menus[0].url, "https://b.zmtcdn.com/data/pictures/2/16508052/d4e5f6.jpg");
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use crate::{Client, Error};
+
+/// What the photo shows.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum PhotoKind {
+    /// Dishes.
+    Food,
+    /// Interior or exterior of the restaurant.
+    Ambience,
+    /// The regular menu.
+    Menu,
+    /// Daily menu published as an image.
+    DailyMenu,
+    /// Photo of unknown category.
+    Other,
+}
+
+impl PhotoKind {
+    fn from_category(category: &str) -> Self {
+        match category {
+            "food" => PhotoKind::Food,
+            "ambience" => PhotoKind::Ambience,
+            "menu" => PhotoKind::Menu,
+            "daily_menu" => PhotoKind::DailyMenu,
+            _ => PhotoKind::Other,
+        }
+    }
+}
+
+/// Displayed as the category used by Zomato, e.g. `daily_menu`.
+impl fmt::Display for PhotoKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let category = match self {
+            PhotoKind::Food => "food",
+            PhotoKind::Ambience => "ambience",
+            PhotoKind::Menu => "menu",
+            PhotoKind::DailyMenu => "daily_menu",
+            PhotoKind::Other => "other",
+        };
+        f.write_str(category)
+    }
+}
+
+/// Photo uploaded to the page of the restaurant.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub struct Photo {
+    /// What the photo shows.
+    pub kind: PhotoKind,
+    /// URL of the photo in full resolution.
+    pub url: String,
+    /// URL of a small preview, if available.
+    pub thumbnail_url: Option<String>,
+    /// Caption of the photo, if any.
+    pub caption: Option<String>,
+    /// Time when the photo was uploaded.
+    pub uploaded: Option<DateTime<Utc>>,
+}
+
+impl Client {
+    /// Fetches all photos of the restaurant in the order in which Zomato lists them.
+    ///
+    /// Photos without valid URLs are skipped.
+    pub async fn get_photos(&self, city: &str, restaurant: &str) -> Result<Vec<Photo>, Error> {
+        self.get_photos_internal(city, restaurant).await.map_err(Error)
+    }
+
+    /// Fetches the photos of the daily menu of the restaurant, the newest first.
+    ///
+    /// Photos without upload time are at the end.
+    pub async fn get_daily_menu_photos(&self, city: &str, restaurant: &str) -> Result<Vec<Photo>, Error> {
+        let mut photos = self.get_photos_internal(city, restaurant).await.map_err(Error)?;
+        photos.retain(|photo| photo.kind == PhotoKind::DailyMenu);
+        photos.sort_by_key(|photo| std::cmp::Reverse(photo.uploaded));
+        Ok(photos)
+    }
+
+    async fn get_photos_internal(&self, city: &str, restaurant: &str) -> Result<Vec<Photo>, anyhow::Error> {
+        let html = self.fetch(&self.photos_url(city, restaurant)?).await?;
+        parse_photos_page(&html)
+    }
+}
+
+fn parse_photos_page(html: &str) -> Result<Vec<Photo>, anyhow::Error> {
+    let state = zomato_core::extract_preloaded_state(html)?;
+    let state = serde_json::from_str::<serde_json::Value>(&state).context("failed to parse json")?;
+    let photos = state["pages"]["restaurant"]
+        .as_object()
+        .and_then(|restaurant| restaurant.values().next())
+        .and_then(|restaurant| restaurant["sections"]["SECTION_PHOTOS"]["photos"].as_array())
+        .ok_or_else(|| anyhow::anyhow!("missing photos"))?;
+
+    Ok(photos.iter().filter_map(parse_photo).collect())
+}
+
+fn parse_photo(photo: &serde_json::Value) -> Option<Photo> {
+    let image_url = |value: &serde_json::Value| {
+        let url = url::Url::parse(value.as_str()?).ok()?;
+        matches!(url.scheme(), "http" | "https").then(|| url.into())
+    };
+
+    Some(Photo {
+        kind: PhotoKind::from_category(photo["category"].as_str().unwrap_or_default()),
+        url: image_url(&photo["url"])?,
+        thumbnail_url: image_url(&photo["thumbUrl"]),
+        caption: photo["caption"].as_str().map(zomato_core::normalize).filter(|caption| !caption.is_empty()),
+        uploaded: photo["timestamp"].as_i64().and_then(|timestamp| DateTime::from_timestamp(timestamp, 0)),
+    })
+}
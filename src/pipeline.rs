@@ -0,0 +1,189 @@
+//! Composable processing of menus.
+//!
+//! A [`Pipeline`] fetches the menus of its restaurants, passes them through [`Transform`]s
+//! (filters being the simplest ones) and delivers the result to [`Sink`]s. This allows building
+//! different outputs from the same building blocks, e.g. vegetarian lunch options into a chat and
+//! everything into an archive.
+//!
+//! ```
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use zomato::MenuQuery;
+//! use zomato::pipeline::Pipeline;
+//! use zomato::sink::event_log::{read_events, EventLog};
+//!
+//! # let client = zomato::testing::fixture_client();
+//! # let dir = mktemp::Temp::new_dir()?;
+//! # let path = dir.as_path().join("events.jsonl");
+//! let pipeline = Pipeline::new("cheap-lunch")
+//!     .restaurant("bratislava", "u-zlateho-bazanta")
+//!     .transform(MenuQuery::new().max_price(7.0))
+//!     .sink(EventLog::open(&path)?);
+//!
+//! let report = pipeline.run(&client).await;
+//! assert_eq!(report.delivered, 1);
+//! let event = read_events(&path)?.next().expect("delivered event")?;
+//! assert_eq!(event.menus[0].items.len(), 2);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use crate::sink::{deliver_to_all, DeliveryError, MenuEvent, Sink};
+use crate::{BatchFailure, Client, Error, Menu, MenuQuery};
+
+/// Future returned by [`Transform::apply`].
+pub type TransformFuture<'a> = Pin<Box<dyn Future<Output = Result<Vec<Menu>, Error>> + Send + 'a>>;
+
+/// Stage of a pipeline modifying the menus before they are delivered.
+///
+/// The method returns a boxed future so that transforms can be used as trait objects and may
+/// perform I/O, e.g. to translate the menus.
+pub trait Transform: Send + Sync {
+    /// Transforms the menus.
+    fn apply(&self, menus: Vec<Menu>) -> TransformFuture<'_>;
+}
+
+impl<T: Transform + ?Sized> Transform for Arc<T> {
+    fn apply(&self, menus: Vec<Menu>) -> TransformFuture<'_> {
+        (**self).apply(menus)
+    }
+}
+
+impl<T: Transform + ?Sized> Transform for Box<T> {
+    fn apply(&self, menus: Vec<Menu>) -> TransformFuture<'_> {
+        (**self).apply(menus)
+    }
+}
+
+/// Keeps only the items matching the query.
+///
+/// Menus without matching items are kept, so that the consumers can tell that nothing matched.
+impl Transform for MenuQuery {
+    fn apply(&self, mut menus: Vec<Menu>) -> TransformFuture<'_> {
+        for menu in &mut menus {
+            menu.filter(self);
+        }
+        Box::pin(async move { Ok(menus) })
+    }
+}
+
+/// Named chain of stages: restaurants to fetch, transforms and sinks.
+pub struct Pipeline {
+    name: String,
+    restaurants: Vec<(String, String)>,
+    transforms: Vec<Box<dyn Transform>>,
+    sinks: Vec<Box<dyn Sink>>,
+}
+
+/// Summary of [`Pipeline::run`].
+pub struct PipelineReport {
+    /// Number of restaurants which were delivered to all sinks.
+    pub delivered: usize,
+    /// Restaurants which couldn't be fetched or transformed.
+    pub failed: Vec<BatchFailure>,
+    /// Deliveries that failed.
+    pub undelivered: Vec<Undelivered>,
+}
+
+/// Menus of a restaurant which failed to be delivered to a sink.
+pub struct Undelivered {
+    /// City in which the restaurant is located.
+    pub city: String,
+    /// Name of the restaurant as used in the URL.
+    pub restaurant: String,
+    /// Index of the sink in the order the sinks were added.
+    pub sink: usize,
+    /// Error returned by the sink.
+    pub error: DeliveryError,
+}
+
+impl Pipeline {
+    /// Creates an empty pipeline with given name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Pipeline {
+            name: name.into(),
+            restaurants: Vec::new(),
+            transforms: Vec::new(),
+            sinks: Vec::new(),
+        }
+    }
+
+    /// Returns the name of the pipeline.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Adds restaurant whose menus are fetched.
+    pub fn restaurant(mut self, city: impl Into<String>, restaurant: impl Into<String>) -> Self {
+        self.restaurants.push((city.into(), restaurant.into()));
+        self
+    }
+
+    /// Appends a transform, the transforms are applied in the order they were added.
+    pub fn transform<T: Transform + 'static>(mut self, transform: T) -> Self {
+        self.transforms.push(Box::new(transform));
+        self
+    }
+
+    /// Adds a sink receiving the transformed menus.
+    pub fn sink<S: Sink + 'static>(mut self, sink: S) -> Self {
+        self.sinks.push(Box::new(sink));
+        self
+    }
+
+    /// Runs the pipeline once.
+    ///
+    /// The restaurants are processed one by one in the order they were added. Failures don't
+    /// stop the pipeline, they are collected in the report instead.
+    pub async fn run(&self, client: &Client) -> PipelineReport {
+        let mut report = PipelineReport {
+            delivered: 0,
+            failed: Vec::new(),
+            undelivered: Vec::new(),
+        };
+        let sinks = self.sinks.iter().map(|sink| &**sink as &dyn Sink).collect::<Vec<_>>();
+
+        for (city, restaurant) in &self.restaurants {
+            let menus = match self.process(client, city, restaurant).await {
+                Ok(menus) => menus,
+                Err(error) => {
+                    report.failed.push(BatchFailure {
+                        city: city.clone(),
+                        restaurant: restaurant.clone(),
+                        error,
+                    });
+                    continue;
+                },
+            };
+
+            let event = MenuEvent::new(city.clone(), restaurant.clone(), menus);
+            let mut all_delivered = true;
+            for (sink, result) in deliver_to_all(&event, &sinks).await.into_iter().enumerate() {
+                if let Err(error) = result {
+                    all_delivered = false;
+                    report.undelivered.push(Undelivered {
+                        city: city.clone(),
+                        restaurant: restaurant.clone(),
+                        sink,
+                        error,
+                    });
+                }
+            }
+            if all_delivered {
+                report.delivered += 1;
+            }
+        }
+        report
+    }
+
+    async fn process(&self, client: &Client, city: &str, restaurant: &str) -> Result<Vec<Menu>, Error> {
+        let mut menus = client.get_daily_menu(city, restaurant).await?;
+        for transform in &self.transforms {
+            menus = transform.apply(menus).await?;
+        }
+        Ok(menus)
+    }
+}
@@ -0,0 +1,60 @@
+//! Restrictions of the URLs the client fetches.
+//!
+//! City and restaurant names may come from untrusted sources, e.g. requests to the server, so
+//! they must not be able to redirect the client to other hosts.
+
+/// Error returned when a request would violate the URL policy of the client.
+///
+/// Obtain it from [`Error::policy_violation`](crate::Error::policy_violation).
+#[derive(Debug, Clone, thiserror::Error)]
+#[non_exhaustive]
+pub enum PolicyError {
+    /// City or restaurant name contains characters which could change the meaning of the URL.
+    #[error("invalid {kind} '{value}', only letters, digits, '-', '_' and '.' are allowed")]
+    InvalidSegment {
+        /// What the value represents, e.g. "city".
+        kind: &'static str,
+        /// The rejected value.
+        value: String,
+    },
+    /// The URL targets a host which isn't allowed.
+    #[error("refusing to fetch {url}, the host is not allowed")]
+    HostNotAllowed {
+        /// The rejected URL.
+        url: String,
+    },
+}
+
+/// Checks that the value can be safely used as a single path segment.
+pub(crate) fn check_segment(kind: &'static str, value: &str) -> Result<(), PolicyError> {
+    let valid_char = |c: char| c.is_alphanumeric() || matches!(c, '-' | '_' | '.');
+    if value.is_empty() || value == "." || value == ".." || !value.chars().all(valid_char) {
+        return Err(PolicyError::InvalidSegment {
+            kind,
+            value: value.to_owned(),
+        });
+    }
+    Ok(())
+}
+
+/// Checks that the URL uses HTTP(S) and targets one of the allowed hosts.
+pub(crate) fn check_url(url: &str, allowed_hosts: &[String]) -> Result<(), PolicyError> {
+    let parsed = url::Url::parse(url).ok();
+    let allowed = parsed.is_some_and(|parsed| {
+        matches!(parsed.scheme(), "http" | "https")
+            && parsed.username().is_empty()
+            && parsed.password().is_none()
+            && parsed.host_str().is_some_and(|host| allowed_hosts.iter().any(|allowed| allowed.eq_ignore_ascii_case(host)))
+    });
+    if !allowed {
+        return Err(PolicyError::HostNotAllowed {
+            url: url.to_owned(),
+        });
+    }
+    Ok(())
+}
+
+/// Returns the host of the URL if it has one.
+pub(crate) fn host(url: &str) -> Option<String> {
+    url::Url::parse(url).ok()?.host_str().map(str::to_owned)
+}
@@ -0,0 +1,131 @@
+use std::time::Duration;
+use crate::{Client, Error, Menu, Parser, RestaurantInfo};
+
+/// Composable query fetching multiple kinds of information at once.
+///
+/// The query figures out which pages need to be downloaded to satisfy it and fetches each of them
+/// only once. E.g. both daily menu and basic information about restaurant are available on the
+/// same page, so requesting both results in a single request.
+///
+/// ```
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), zomato::Error> {
+/// # let client = zomato::testing::fixture_client();
+/// // let client = zomato::Client::new()?;
+/// let result = zomato::Query::city("praha")
+///     .restaurant("xyz")
+///     .daily_menu()
+///     .with_info()
+///     .with_deadline(std::time::Duration::from_secs(30))
+///     .fetch(&client)
+///     .await?;
+/// assert_eq!(result.info.unwrap().name, "U Zlatého Bažanta");
+/// assert_eq!(result.daily_menu.unwrap()[0].items.len(), 3);
+/// assert!(!result.incomplete);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Query {
+    city: String,
+    restaurant: Option<String>,
+    daily_menu: bool,
+    info: bool,
+    deadline: Option<Duration>,
+}
+
+impl Query {
+    /// Starts building query for given city.
+    pub fn city(city: impl Into<String>) -> Self {
+        Query {
+            city: city.into(),
+            restaurant: None,
+            daily_menu: false,
+            info: false,
+            deadline: None,
+        }
+    }
+
+    /// Sets the restaurant the query is about.
+    pub fn restaurant(mut self, restaurant: impl Into<String>) -> Self {
+        self.restaurant = Some(restaurant.into());
+        self
+    }
+
+    /// Requests daily menu of the restaurant.
+    pub fn daily_menu(mut self) -> Self {
+        self.daily_menu = true;
+        self
+    }
+
+    /// Requests basic information about the restaurant (name, rating, opening hours...).
+    pub fn with_info(mut self) -> Self {
+        self.info = true;
+        self
+    }
+
+    /// Limits the time the whole query may take.
+    ///
+    /// If the deadline passes outstanding requests are cancelled and the information that wasn't
+    /// retrieved in time is missing from the result, which is marked as
+    /// [`incomplete`](QueryResult::incomplete).
+    pub fn with_deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Executes the query using given client.
+    pub async fn fetch(&self, client: &Client) -> Result<QueryResult, Error> {
+        let result = match self.deadline {
+            Some(deadline) => match crate::runtime::timeout(deadline, self.fetch_internal(client)).await {
+                Ok(result) => result,
+                Err(_) => Ok(QueryResult {
+                    daily_menu: None,
+                    info: None,
+                    incomplete: true,
+                }),
+            },
+            None => self.fetch_internal(client).await,
+        };
+        result.map_err(Error)
+    }
+
+    async fn fetch_internal(&self, client: &Client) -> Result<QueryResult, anyhow::Error> {
+        let mut result = QueryResult {
+            daily_menu: None,
+            info: None,
+            incomplete: false,
+        };
+
+        if !(self.daily_menu || self.info) {
+            return Ok(result);
+        }
+
+        let restaurant = self.restaurant.as_ref().ok_or_else(|| anyhow::anyhow!("the query is missing restaurant"))?;
+        let mut page = client.fetch_and_parse_daily_menu(&self.city, restaurant, &mut Parser::new()).await?;
+
+        if self.daily_menu {
+            client.filter_menus(restaurant, &mut page.menus);
+            result.daily_menu = Some(page.menus);
+        }
+        if self.info {
+            let info = page.info.ok_or_else(|| anyhow::anyhow!("missing restaurant information"))?;
+            result.info = Some(info);
+        }
+
+        Ok(result)
+    }
+}
+
+/// Combined result of a [`Query`].
+///
+/// Each field is `Some` if and only if it was requested, unless the result is incomplete.
+pub struct QueryResult {
+    /// Daily menus, requested using [`Query::daily_menu`].
+    pub daily_menu: Option<Vec<Menu>>,
+    /// Information about the restaurant, requested using [`Query::with_info`].
+    pub info: Option<RestaurantInfo>,
+    /// `true` if the [deadline](Query::with_deadline) passed before all requested information
+    /// was retrieved.
+    pub incomplete: bool,
+}
@@ -0,0 +1,173 @@
+//! Hints about how busy restaurants are, provided by reservation systems.
+//!
+//! Zomato doesn't know how full the restaurant will be, but the booking system of the office
+//! or of the restaurant might. [`ReservationProvider`] is the extension point for querying such
+//! systems, [`seating_hint`] turns the answer into a short hint like "likely full at 12:00"
+//! suitable for briefings.
+//!
+//! Two providers are available: [`NoReservations`] which never knows anything and
+//! [`WebhookReservations`] which asks an HTTP endpoint, so that integrating a booking system
+//! only requires a small adapter service. The latter is not available in `wasm32` builds.
+//!
+//! ```no_run
+//! use zomato::reservation::{seating_hint, WebhookReservations};
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), zomato::Error> {
+//! let provider = WebhookReservations::new("https://booking.example.com/occupancy");
+//! let today = chrono::Local::now().date_naive();
+//! if let Some(hint) = seating_hint(&provider, "bratislava", "u-zlateho-bazanta", today).await? {
+//!     println!("U Zlatého Bažanta is {}", hint);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use chrono::{NaiveDate, NaiveTime};
+use crate::Error;
+
+/// Occupancy from which the restaurant is considered full.
+pub const FULL_OCCUPANCY: f64 = 0.9;
+
+/// Future returned by [`ReservationProvider::occupancy`].
+pub type OccupancyFuture<'a> = Pin<Box<dyn Future<Output = Result<Vec<Occupancy>, Error>> + Send + 'a>>;
+
+/// Expected occupancy of a restaurant at specific time.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Occupancy {
+    /// Start of the time slot.
+    pub time: NaiveTime,
+    /// Expected share of occupied seats, between 0 and 1.
+    pub occupancy: f64,
+}
+
+/// Source of the expected occupancy of restaurants.
+pub trait ReservationProvider: Send + Sync {
+    /// Returns the expected occupancy of the restaurant at given day.
+    ///
+    /// The slots don't have to be sorted, an empty list means the occupancy is unknown.
+    fn occupancy<'a>(&'a self, city: &'a str, restaurant: &'a str, date: NaiveDate) -> OccupancyFuture<'a>;
+}
+
+/// Provider which doesn't know the occupancy of any restaurant.
+///
+/// Useful as a default when no booking system is configured.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct NoReservations;
+
+impl ReservationProvider for NoReservations {
+    fn occupancy<'a>(&'a self, _city: &'a str, _restaurant: &'a str, _date: NaiveDate) -> OccupancyFuture<'a> {
+        Box::pin(async { Ok(Vec::new()) })
+    }
+}
+
+/// Provider asking an HTTP endpoint.
+///
+/// The endpoint is requested using `GET` with `city`, `restaurant` and `date` (`YYYY-MM-DD`)
+/// query parameters and has to respond with JSON array like
+/// `[{"time": "12:00", "occupancy": 0.95}]`. `404 Not Found` means the occupancy of the
+/// restaurant is unknown.
+#[cfg(all(feature = "reqwest", not(target_arch = "wasm32")))]
+pub struct WebhookReservations {
+    http: reqwest::Client,
+    url: String,
+}
+
+#[cfg(all(feature = "reqwest", not(target_arch = "wasm32")))]
+#[derive(serde_derive::Deserialize)]
+struct Slot {
+    time: String,
+    occupancy: f64,
+}
+
+#[cfg(all(feature = "reqwest", not(target_arch = "wasm32")))]
+impl WebhookReservations {
+    /// Creates provider requesting given URL.
+    pub fn new(url: impl Into<String>) -> Self {
+        WebhookReservations {
+            http: reqwest::Client::new(),
+            url: url.into(),
+        }
+    }
+
+    async fn request(&self, city: &str, restaurant: &str, date: NaiveDate) -> Result<Vec<Occupancy>, anyhow::Error> {
+        use anyhow::Context;
+
+        let response = self.http
+            .get(&self.url)
+            .query(&[("city", city), ("restaurant", restaurant), ("date", &date.to_string())])
+            .send()
+            .await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(Vec::new());
+        }
+        let body = response.error_for_status()?.bytes().await?;
+        serde_json::from_slice::<Vec<Slot>>(&body)
+            .context("invalid response of the reservation endpoint")?
+            .into_iter()
+            .map(|slot| {
+                let time = NaiveTime::parse_from_str(&slot.time, "%H:%M").with_context(|| format!("invalid time '{}', expected HH:MM", slot.time))?;
+                Ok(Occupancy {
+                    time,
+                    occupancy: slot.occupancy,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(all(feature = "reqwest", not(target_arch = "wasm32")))]
+impl ReservationProvider for WebhookReservations {
+    fn occupancy<'a>(&'a self, city: &'a str, restaurant: &'a str, date: NaiveDate) -> OccupancyFuture<'a> {
+        Box::pin(async move { self.request(city, restaurant, date).await.map_err(Error) })
+    }
+}
+
+/// Hint that the restaurant is expected to be full.
+///
+/// Displayed as e.g. "likely full at 12:00".
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SeatingHint {
+    /// The earliest time slot at which the restaurant is expected to be full.
+    pub full_at: NaiveTime,
+    /// Expected occupancy at that time.
+    pub occupancy: f64,
+}
+
+impl SeatingHint {
+    /// Returns hint about the earliest slot reaching [`FULL_OCCUPANCY`], if any.
+    ///
+    /// ```
+    /// use chrono::NaiveTime;
+    /// use zomato::reservation::{Occupancy, SeatingHint};
+    ///
+    /// let slot = |hour, occupancy| Occupancy { time: NaiveTime::from_hms_opt(hour, 0, 0).unwrap(), occupancy };
+    /// let hint = SeatingHint::from_slots(&[slot(13, 1.0), slot(11, 0.4), slot(12, 0.95)]).unwrap();
+    /// assert_eq!(hint.to_string(), "likely full at 12:00");
+    /// assert_eq!(SeatingHint::from_slots(&[slot(12, 0.5)]), None);
+    /// ```
+    pub fn from_slots(slots: &[Occupancy]) -> Option<Self> {
+        slots
+            .iter()
+            .filter(|slot| slot.occupancy >= FULL_OCCUPANCY)
+            .min_by_key(|slot| slot.time)
+            .map(|slot| SeatingHint {
+                full_at: slot.time,
+                occupancy: slot.occupancy,
+            })
+    }
+}
+
+impl fmt::Display for SeatingHint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "likely full at {}", self.full_at.format("%H:%M"))
+    }
+}
+
+/// Asks the provider about the restaurant and returns the hint if it's expected to be full.
+pub async fn seating_hint(provider: &dyn ReservationProvider, city: &str, restaurant: &str, date: NaiveDate) -> Result<Option<SeatingHint>, Error> {
+    Ok(SeatingHint::from_slots(&provider.occupancy(city, restaurant, date).await?))
+}
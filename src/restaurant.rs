@@ -0,0 +1,138 @@
+//! References to restaurants parsed from URLs.
+
+use std::fmt;
+use std::str::FromStr;
+use percent_encoding::{percent_decode_str, utf8_percent_encode, NON_ALPHANUMERIC};
+use crate::policy::{self, PolicyError};
+
+/// Host of the Zomato pages, subdomains are accepted too.
+const ZOMATO_HOST: &str = "zomato.com";
+
+/// City and restaurant identifying the restaurant in the URLs of Zomato pages.
+///
+/// It can be parsed from `city/restaurant` or from the URL of any page of the restaurant copied
+/// from the browser. Both names are validated so that they can be safely passed to [`Client`].
+///
+/// [`Client`]: crate::Client
+///
+/// ```
+/// use zomato::RestaurantRef;
+///
+/// let restaurant = "https://www.zomato.com/bratislava/u-zlateho-bazanta/daily-menu".parse::<RestaurantRef>()?;
+/// assert_eq!(restaurant.city, "bratislava");
+/// assert_eq!(restaurant.slug, "u-zlateho-bazanta");
+/// assert_eq!(restaurant, "bratislava/u-zlateho-bazanta".parse()?);
+/// assert_eq!(restaurant.to_string(), "bratislava/u-zlateho-bazanta");
+///
+/// assert!("bratislava/../admin".parse::<RestaurantRef>().is_err());
+/// assert!("https://evil.example/bratislava/u-zlateho-bazanta".parse::<RestaurantRef>().is_err());
+/// # Ok::<_, zomato::ParseRestaurantRefError>(())
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct RestaurantRef {
+    /// City in which the restaurant is located, as used in the URL.
+    pub city: String,
+    /// Name of the restaurant as used in the URL.
+    pub slug: String,
+}
+
+/// Error returned when the restaurant reference is invalid.
+#[derive(Debug, Clone, thiserror::Error)]
+#[non_exhaustive]
+pub enum ParseRestaurantRefError {
+    /// The input is neither `city/restaurant` nor a valid URL.
+    #[error("invalid restaurant '{input}', use city/restaurant or the URL of the restaurant")]
+    Malformed {
+        /// The rejected input.
+        input: String,
+    },
+    /// The URL doesn't point to Zomato.
+    #[error("{url} is not a Zomato URL")]
+    NotZomato {
+        /// The rejected URL.
+        url: String,
+    },
+    /// City or restaurant contains characters not allowed in a single path segment.
+    #[error(transparent)]
+    InvalidSegment(#[from] PolicyError),
+}
+
+impl RestaurantRef {
+    /// Creates the reference, checking that both names are valid path segments.
+    pub fn new(city: impl Into<String>, slug: impl Into<String>) -> Result<Self, ParseRestaurantRefError> {
+        let city = city.into();
+        let slug = slug.into();
+        policy::check_segment("city", &city)?;
+        policy::check_segment("restaurant", &slug)?;
+        Ok(RestaurantRef {
+            city,
+            slug,
+        })
+    }
+
+    /// Parses the URL of any page of the restaurant, e.g. the daily menu or the overview.
+    ///
+    /// Only HTTP(S) URLs of `zomato.com` and its subdomains are accepted. The query and fragment,
+    /// e.g. tracking parameters added when sharing, are ignored.
+    pub fn try_from_url(url: &str) -> Result<Self, ParseRestaurantRefError> {
+        let parsed = url::Url::parse(url).map_err(|_| ParseRestaurantRefError::Malformed { input: url.to_owned() })?;
+        let is_zomato = |host: &str| {
+            let host = host.to_ascii_lowercase();
+            host == ZOMATO_HOST || host.ends_with(&format!(".{}", ZOMATO_HOST))
+        };
+        if !matches!(parsed.scheme(), "http" | "https") || !parsed.host_str().is_some_and(is_zomato) {
+            return Err(ParseRestaurantRefError::NotZomato { url: url.to_owned() });
+        }
+        let mut segments = parsed.path_segments().into_iter().flatten().filter(|segment| !segment.is_empty());
+        match (segments.next(), segments.next()) {
+            (Some(city), Some(slug)) => Self::new(decode(city, url)?, decode(slug, url)?),
+            _ => Err(ParseRestaurantRefError::Malformed { input: url.to_owned() }),
+        }
+    }
+
+    /// Returns the path `city/restaurant` with both names percent-encoded.
+    ///
+    /// ```
+    /// let restaurant = zomato::RestaurantRef::new("praha", "kavárna")?;
+    /// assert_eq!(restaurant.url_path(), "praha/kav%C3%A1rna");
+    /// # Ok::<_, zomato::ParseRestaurantRefError>(())
+    /// ```
+    pub fn url_path(&self) -> String {
+        format!("{}/{}", encode(&self.city), encode(&self.slug))
+    }
+}
+
+/// Parses `city/restaurant` or the URL of the restaurant.
+impl FromStr for RestaurantRef {
+    type Err = ParseRestaurantRefError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.contains("://") {
+            return Self::try_from_url(s);
+        }
+        match s.split_once('/') {
+            Some((city, slug)) => Self::new(city, slug),
+            None => Err(ParseRestaurantRefError::Malformed { input: s.to_owned() }),
+        }
+    }
+}
+
+/// Displayed as `city/restaurant`, without encoding.
+impl fmt::Display for RestaurantRef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.city, self.slug)
+    }
+}
+
+fn decode(segment: &str, url: &str) -> Result<String, ParseRestaurantRefError> {
+    percent_decode_str(segment)
+        .decode_utf8()
+        .map(Into::into)
+        .map_err(|_| ParseRestaurantRefError::Malformed { input: url.to_owned() })
+}
+
+fn encode(segment: &str) -> String {
+    // '-', '_' and '.' are allowed in segments and don't need encoding
+    const SEGMENT: &percent_encoding::AsciiSet = &NON_ALPHANUMERIC.remove(b'-').remove(b'_').remove(b'.');
+    utf8_percent_encode(segment, SEGMENT).to_string()
+}
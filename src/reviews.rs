@@ -0,0 +1,102 @@
+//! Reviews of restaurants written by Zomato users.
+//!
+//! Zomato splits the reviews into pages, the newest first. [`Client::get_reviews`] fetches the
+//! requested number of pages at once, which is enough for dashboards aggregating the recent
+//! opinions about lunches.
+//!
+//! ```
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), zomato::Error> {
+//! use zomato::testing::{MockTransport, REVIEWS_FIXTURES};
+//!
+//! let transport = MockTransport::new()
+//!     .with_page("https://www.zomato.com/bratislava/u-zlateho-bazanta/reviews?page=1", REVIEWS_FIXTURES[0])
+//!     .with_page("https://www.zomato.com/bratislava/u-zlateho-bazanta/reviews?page=2", REVIEWS_FIXTURES[1]);
+//! let client = zomato::Client::with_transport(transport);
+//! let reviews = client.get_reviews("bratislava", "u-zlateho-bazanta", 5).await?;
+//! assert_eq!(reviews.len(), 3);
+//! assert_eq!(reviews[0].author, "Jana");
+//! assert_eq!(reviews[0].rating, Some(4.5));
+//! assert_eq!(reviews[0].date.unwrap().to_rfc3339(), "2026-10-12T11:30:00+00:00");
+//!
+//! let average = reviews.iter().filter_map(|review| review.rating).sum::<f32>() / reviews.len() as f32;
+//! assert_eq!(average, 4.1666665);
+//! # Ok(())
+//! # }
+//! ```
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use crate::{Client, Error};
+
+/// Review of a restaurant.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct Review {
+    /// Rating given by the author, usually between 1 and 5.
+    pub rating: Option<f32>,
+    /// Text of the review, may be empty if the author only rated the restaurant.
+    pub text: String,
+    /// Name of the author as displayed by Zomato.
+    pub author: String,
+    /// Time when the review was posted.
+    pub date: Option<DateTime<Utc>>,
+}
+
+impl Client {
+    /// Fetches at most given number of pages of reviews of the restaurant, the newest first.
+    ///
+    /// Fetching stops at the last page, reviews the crate doesn't understand are skipped.
+    pub async fn get_reviews(&self, city: &str, restaurant: &str, pages: u32) -> Result<Vec<Review>, Error> {
+        self.get_reviews_internal(city, restaurant, pages).await.map_err(Error)
+    }
+
+    async fn get_reviews_internal(&self, city: &str, restaurant: &str, pages: u32) -> Result<Vec<Review>, anyhow::Error> {
+        let mut reviews = Vec::new();
+        for page in 1..=pages {
+            let html = self.fetch(&self.reviews_url(city, restaurant, page)?).await?;
+            let (found, has_more) = parse_reviews_page(&html)?;
+            let is_last = !has_more || found.is_empty();
+            reviews.extend(found);
+            if is_last {
+                break;
+            }
+        }
+        Ok(reviews)
+    }
+}
+
+/// Parses reviews and returns them along with the information whether there are more.
+fn parse_reviews_page(html: &str) -> Result<(Vec<Review>, bool), anyhow::Error> {
+    let state = zomato_core::extract_preloaded_state(html)?;
+    let state = serde_json::from_str::<serde_json::Value>(&state).context("failed to parse json")?;
+    let section = state["pages"]["restaurant"]
+        .as_object()
+        .and_then(|restaurant| restaurant.values().next())
+        .map(|restaurant| &restaurant["sections"]["SECTION_REVIEWS"])
+        .filter(|section| section.is_object())
+        .ok_or_else(|| anyhow::anyhow!("missing reviews"))?;
+
+    let reviews = section["reviews"]
+        .as_array()
+        .map(|reviews| reviews.iter().filter_map(parse_review).collect())
+        .unwrap_or_default();
+    // Same as in search results
+    let has_more = section["hasMore"].as_bool().unwrap_or(true);
+
+    Ok((reviews, has_more))
+}
+
+fn parse_review(review: &serde_json::Value) -> Option<Review> {
+    // Numbers are sometimes sent as strings
+    fn number(value: &serde_json::Value) -> Option<f64> {
+        value.as_f64().or_else(|| value.as_str()?.trim().parse().ok())
+    }
+
+    Some(Review {
+        rating: number(&review["rating"]).map(|rating| rating as f32),
+        text: zomato_core::normalize(review["reviewText"].as_str().unwrap_or_default()),
+        author: review["userName"].as_str()?.to_owned(),
+        date: number(&review["timestamp"]).and_then(|timestamp| DateTime::from_timestamp(timestamp as i64, 0)),
+    })
+}
@@ -0,0 +1,43 @@
+//! Timers independent of the async runtime.
+//!
+//! The timers of tokio are used if the `tokio` feature is enabled and the future runs inside a
+//! tokio runtime, which is the case with the default `reqwest` based
+//! [`HttpTransport`](crate::transport::HttpTransport). Otherwise the timers are driven by a helper
+//! thread of `futures-timer`, so they work with async-std, smol or any other executor even if
+//! some other crate enabled the `tokio` feature.
+
+use std::future::Future;
+use std::time::Duration;
+use futures_util::future::{select, Either};
+
+/// Error returned when the future passed to [`timeout`] didn't finish in time.
+#[derive(Debug)]
+pub(crate) struct Elapsed;
+
+/// Returns `true` if the timers of tokio can be used.
+#[cfg(feature = "tokio")]
+fn in_tokio() -> bool {
+    tokio::runtime::Handle::try_current().is_ok()
+}
+
+/// Waits for given time.
+pub(crate) async fn sleep(duration: Duration) {
+    #[cfg(feature = "tokio")]
+    if in_tokio() {
+        return tokio::time::sleep(duration).await;
+    }
+    futures_timer::Delay::new(duration).await
+}
+
+/// Waits for the future, failing if it doesn't finish within given time.
+pub(crate) async fn timeout<F: Future>(duration: Duration, future: F) -> Result<F::Output, Elapsed> {
+    #[cfg(feature = "tokio")]
+    if in_tokio() {
+        return tokio::time::timeout(duration, future).await.map_err(|_| Elapsed);
+    }
+    let future = std::pin::pin!(future);
+    match select(future, futures_timer::Delay::new(duration)).await {
+        Either::Left((output, _)) => Ok(output),
+        Either::Right(((), _)) => Err(Elapsed),
+    }
+}
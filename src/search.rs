@@ -0,0 +1,284 @@
+//! Searching for restaurants.
+//!
+//! Zomato splits search results into pages. Instead of exposing page numbers this crate returns a
+//! [`PageCursor`] which can be used to fetch the next page. The cursor can be serialized, so long
+//! listings can be resumed later.
+
+use serde_derive::{Deserialize, Serialize};
+use crate::{Client, Error, RestaurantRef};
+
+/// Most pages of the listing of restaurants publishing daily menus which are fetched.
+const MAX_LISTING_PAGES: u32 = 20;
+
+/// Mean radius of the Earth in meters.
+const EARTH_RADIUS: f64 = 6_371_000.0;
+
+/// Lowest similarity of the name of a found restaurant to the resolved name.
+const MIN_NAME_SIMILARITY: f64 = 0.8;
+
+/// Short information about a restaurant returned by listings.
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct RestaurantSummary {
+    /// City in which the restaurant is located, as used in the URL.
+    pub city: String,
+    /// Name of the restaurant as used in the URL.
+    ///
+    /// Pass this along with `city` to [`Client::get_daily_menu`].
+    pub restaurant: String,
+    /// Human-readable name of the restaurant.
+    pub name: String,
+    /// Distance in meters from the location passed to [`Client::nearby_restaurants`].
+    ///
+    /// `None` in other listings.
+    pub distance: Option<u32>,
+}
+
+/// Opaque position within paginated listing.
+///
+/// Pass it to [`Client::next_page`] to fetch the following page.
+#[derive(Clone, Debug, PartialEq, Eq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub struct PageCursor {
+    city: String,
+    query: String,
+    page: u32,
+}
+
+/// Single page of search results.
+pub struct SearchPage {
+    /// Restaurants found on this page.
+    pub restaurants: Vec<RestaurantSummary>,
+    /// Cursor pointing to the next page, `None` if this is the last page.
+    pub next: Option<PageCursor>,
+}
+
+impl Client {
+    /// Searches for restaurants in given city, returning the first page of results.
+    ///
+    /// ```
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), zomato::Error> {
+    /// # let client = zomato::testing::search_client();
+    /// let mut page = client.search("bratislava", "lunch").await?;
+    /// let mut restaurants = page.restaurants;
+    /// while let Some(cursor) = page.next {
+    ///     page = client.next_page(&cursor).await?;
+    ///     restaurants.extend(page.restaurants);
+    /// }
+    /// assert_eq!(restaurants.len(), 3);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn search(&self, city: &str, query: &str) -> Result<SearchPage, Error> {
+        let cursor = PageCursor {
+            city: city.to_owned(),
+            query: query.to_owned(),
+            page: 1,
+        };
+        self.next_page(&cursor).await
+    }
+
+    /// Finds the restaurants with names similar to given human-readable name.
+    ///
+    /// The name is searched for in the city and the names of the results on the first page are
+    /// compared with it using the [global similarity](crate::similarity::global), so typos and
+    /// missing diacritics are tolerated. The restaurants are sorted from the most similar one, an
+    /// empty list means nothing similar was found.
+    ///
+    /// ```
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), zomato::Error> {
+    /// use zomato::testing::{MockTransport, SEARCH_FIXTURES};
+    ///
+    /// let url = "https://www.zomato.com/bratislava/restaurants?q=U+Zlateho+Bazanta&page=1";
+    /// let client = zomato::Client::with_transport(MockTransport::new().with_page(url, SEARCH_FIXTURES[0]));
+    /// let restaurants = client.resolve_restaurant("bratislava", "U Zlateho Bazanta").await?;
+    /// assert_eq!(restaurants.len(), 1);
+    /// assert_eq!(restaurants[0].slug, "u-zlateho-bazanta");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn resolve_restaurant(&self, city: &str, name: &str) -> Result<Vec<RestaurantRef>, Error> {
+        let similarity = crate::similarity::global();
+        let mut found = self
+            .search(city, name)
+            .await?
+            .restaurants
+            .into_iter()
+            .map(|restaurant| (similarity.similarity(name, &restaurant.name), restaurant))
+            .filter(|(similarity, _)| *similarity >= MIN_NAME_SIMILARITY)
+            .collect::<Vec<_>>();
+        found.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+        found
+            .into_iter()
+            .map(|(_, restaurant)| RestaurantRef::new(restaurant.city, restaurant.restaurant).map_err(|error| Error(error.into())))
+            .collect()
+    }
+
+    /// Lists the restaurants in the city which publish daily menus.
+    ///
+    /// All pages of the listing are fetched, at most 20 of them. The listing has the same
+    /// structure as search results, restaurants the crate doesn't understand are skipped.
+    ///
+    /// ```
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), zomato::Error> {
+    /// use zomato::testing::{MockTransport, SEARCH_FIXTURES};
+    ///
+    /// let transport = MockTransport::new()
+    ///     .with_page("https://www.zomato.com/bratislava/daily-menus?page=1", SEARCH_FIXTURES[0])
+    ///     .with_page("https://www.zomato.com/bratislava/daily-menus?page=2", SEARCH_FIXTURES[1]);
+    /// let client = zomato::Client::with_transport(transport);
+    /// let restaurants = client.daily_menu_restaurants("bratislava").await?;
+    /// assert_eq!(restaurants.len(), 3);
+    /// assert_eq!(restaurants[0].name, "U Zlatého Bažanta");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn daily_menu_restaurants(&self, city: &str) -> Result<Vec<RestaurantSummary>, Error> {
+        self.daily_menu_restaurants_internal(city).await.map_err(Error)
+    }
+
+    async fn daily_menu_restaurants_internal(&self, city: &str) -> Result<Vec<RestaurantSummary>, anyhow::Error> {
+        self.fetch_listing(|page| self.daily_menu_listing_url(city, page), None).await
+    }
+
+    /// Lists the restaurants within `radius` meters from given coordinates, the closest first.
+    ///
+    /// The distances are computed from the coordinates of the restaurants, restaurants without
+    /// coordinates are skipped. Like in [`daily_menu_restaurants`](Self::daily_menu_restaurants)
+    /// at most 20 pages of results are fetched.
+    ///
+    /// ```
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), zomato::Error> {
+    /// use zomato::testing::{MockTransport, NEARBY_FIXTURE};
+    ///
+    /// let url = "https://www.zomato.com/restaurants/nearby?lat=48.1439&lon=17.1097&radius=1000&page=1";
+    /// let client = zomato::Client::with_transport(MockTransport::new().with_page(url, NEARBY_FIXTURE));
+    /// let restaurants = client.nearby_restaurants(48.1439, 17.1097, 1000).await?;
+    /// assert_eq!(restaurants.len(), 2);
+    /// assert_eq!(restaurants[0].restaurant, "u-zlateho-bazanta");
+    /// assert_eq!(restaurants[0].distance, Some(176));
+    /// assert!(client.nearby_restaurants(148.0, 17.1, 1000).await.is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn nearby_restaurants(&self, latitude: f64, longitude: f64, radius: u32) -> Result<Vec<RestaurantSummary>, Error> {
+        self.nearby_restaurants_internal(latitude, longitude, radius).await.map_err(Error)
+    }
+
+    async fn nearby_restaurants_internal(&self, latitude: f64, longitude: f64, radius: u32) -> Result<Vec<RestaurantSummary>, anyhow::Error> {
+        if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+            anyhow::bail!("invalid coordinates {}, {}", latitude, longitude);
+        }
+        let mut restaurants = self
+            .fetch_listing(|page| self.nearby_url(latitude, longitude, radius, page), Some((latitude, longitude)))
+            .await?;
+        restaurants.retain(|restaurant| restaurant.distance.is_some_and(|distance| distance <= radius));
+        restaurants.sort_by_key(|restaurant| restaurant.distance);
+        Ok(restaurants)
+    }
+
+    /// Fetches all pages of the listing, at most [`MAX_LISTING_PAGES`].
+    ///
+    /// The distances of the restaurants are computed if the origin is given.
+    async fn fetch_listing(&self, url: impl Fn(u32) -> Result<String, anyhow::Error>, origin: Option<(f64, f64)>) -> Result<Vec<RestaurantSummary>, anyhow::Error> {
+        let mut restaurants = Vec::new();
+        for page in 1..=MAX_LISTING_PAGES {
+            let html = self.fetch(&url(page)?).await?;
+            let (found, has_more) = parse_search_page(&html, |url| self.strip_base_url(url), origin)?;
+            let is_last = !has_more || found.is_empty();
+            restaurants.extend(found);
+            if is_last {
+                break;
+            }
+        }
+        Ok(restaurants)
+    }
+
+    /// Fetches the page of results the cursor points to.
+    pub async fn next_page(&self, cursor: &PageCursor) -> Result<SearchPage, Error> {
+        self.next_page_internal(cursor).await.map_err(Error)
+    }
+
+    async fn next_page_internal(&self, cursor: &PageCursor) -> Result<SearchPage, anyhow::Error> {
+        let url = self.search_page_url_internal(&cursor.city, &cursor.query, cursor.page)?;
+        let page = self.fetch(&url).await?;
+        let (restaurants, has_more) = parse_search_page(&page, |url| self.strip_base_url(url), None)?;
+        let next = if has_more && !restaurants.is_empty() {
+            Some(PageCursor {
+                page: cursor.page + 1,
+                ..cursor.clone()
+            })
+        } else {
+            None
+        };
+
+        Ok(SearchPage {
+            restaurants,
+            next,
+        })
+    }
+}
+
+/// Parses search results and returns them along with the information whether there are more.
+///
+/// `strip_base_url` turns absolute URLs of restaurants into paths. The distances of the
+/// restaurants from the origin are computed if it's given.
+fn parse_search_page(html: &str, strip_base_url: impl Fn(&str) -> &str, origin: Option<(f64, f64)>) -> Result<(Vec<RestaurantSummary>, bool), anyhow::Error> {
+    use anyhow::Context;
+
+    let state = zomato_core::extract_preloaded_state(html)?;
+    let state = serde_json::from_str::<serde_json::Value>(&state).context("failed to parse json")?;
+    let sections = state["pages"]["search"]
+        .as_object()
+        .and_then(|search| search.values().next())
+        .map(|search| &search["sections"])
+        .ok_or_else(|| anyhow::anyhow!("missing search results"))?;
+
+    let restaurants = sections["SECTION_SEARCH_RESULT"]
+        .as_array()
+        .map(|results| results.iter().filter_map(|result| parse_search_result(result, &strip_base_url, origin)).collect())
+        .unwrap_or_default();
+    // If the information is missing we try the next page and stop when it's empty.
+    let has_more = sections["SECTION_SEARCH_META_INFO"]["hasMore"].as_bool().unwrap_or(true);
+
+    Ok((restaurants, has_more))
+}
+
+fn parse_search_result<'a>(result: &'a serde_json::Value, strip_base_url: impl Fn(&'a str) -> &'a str, origin: Option<(f64, f64)>) -> Option<RestaurantSummary> {
+    // Coordinates are sent as strings
+    fn coordinate(value: &serde_json::Value) -> Option<f64> {
+        value.as_f64().or_else(|| value.as_str()?.trim().parse().ok())
+    }
+
+    let name = result["info"]["name"].as_str()?;
+    let (city, restaurant) = parse_restaurant_path(strip_base_url(result["cardAction"]["clickUrl"].as_str()?))?;
+    let location = &result["info"]["location"];
+    let distance = match (origin, coordinate(&location["latitude"]), coordinate(&location["longitude"])) {
+        (Some(origin), Some(latitude), Some(longitude)) => Some(distance(origin, (latitude, longitude)).round() as u32),
+        _ => None,
+    };
+
+    Some(RestaurantSummary {
+        city: city.to_owned(),
+        restaurant: restaurant.to_owned(),
+        name: name.to_owned(),
+        distance,
+    })
+}
+
+/// Computes the distance between the coordinates in meters using the haversine formula.
+fn distance((latitude_a, longitude_a): (f64, f64), (latitude_b, longitude_b): (f64, f64)) -> f64 {
+    let (latitude_a, latitude_b) = (latitude_a.to_radians(), latitude_b.to_radians());
+    let latitude_delta = latitude_b - latitude_a;
+    let longitude_delta = (longitude_b - longitude_a).to_radians();
+    let a = (latitude_delta / 2.0).sin().powi(2) + latitude_a.cos() * latitude_b.cos() * (longitude_delta / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS * a.sqrt().asin()
+}
+
+/// Extracts city and restaurant from path of restaurant page.
+pub(crate) fn parse_restaurant_path(path: &str) -> Option<(&str, &str)> {
+    let mut segments = path.split(['/', '?']).filter(|segment| !segment.is_empty());
+    Some((segments.next()?, segments.next()?))
+}
@@ -0,0 +1,534 @@
+//! HTTP server exposing the menus.
+//!
+//! The server answers `GET /menu/{city}/{restaurant}` with the daily menus of the restaurant. The
+//! menus are JSON (an array of [`Menu`]s) by default, HTML tables are returned if the client
+//! prefers `text/html` in its `Accept` header or if `?format=html` is appended. `?format=json`
+//! forces JSON.
+//!
+//! Fetched menus are cached so that the server doesn't hit Zomato on every request. If fetching
+//! fails, the last known menus are served even if they expired, so that the API stays available
+//! when Zomato has problems. With the `binary-cache` feature the menus can also be persisted to
+//! disk using [`Server::disk_cache`] so that they survive restarts.
+//!
+//! If the `metrics` feature is enabled, `GET /metrics` returns the [metrics](crate::metrics) in
+//! the Prometheus text format.
+//!
+//! The server also keeps a registry of restaurants the deployment cares about:
+//!
+//! * `GET /restaurants` lists them as JSON array of `{"city": ..., "restaurant": ...}` objects
+//! * `PUT /restaurants/{city}/{restaurant}` adds a restaurant
+//! * `DELETE /restaurants/{city}/{restaurant}` removes a restaurant
+//! * `POST /menu/{city}/{restaurant}/refresh` fetches the menus bypassing the cache
+//!
+//! `GET /status` returns `{"layout_changes": [...]}` listing the [records](crate::layout) of the
+//! [layout log](crate::ClientBuilder::layout_log) of the client, so that monitoring can warn when
+//! Zomato changes its pages.
+//!
+//! With [`Server::image_proxy`] the server also proxies images hosted by Zomato at
+//! `GET /img/{hash}`, see [`ImageProxy`].
+//!
+//! Requests are authorized using `Authorization: Bearer {token}` header, see [`Role`] and
+//! [`Server::token`]. Everyone can read menus and the registry unless viewer tokens are
+//! configured, modifications and forced refreshes always require an admin token.
+//!
+//! ```
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use zomato::server::Server;
+//!
+//! # let client = zomato::testing::fixture_client();
+//! // let client = zomato::Client::new()?;
+//! let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+//! let url = format!("http://{}/menu/bratislava/u-zlateho-bazanta", listener.local_addr()?);
+//! tokio::spawn(Server::new(client).serve(listener));
+//!
+//! let menus = serde_json::from_str::<Vec<zomato::Menu>>(&reqwest::get(&url).await?.text().await?)?;
+//! assert_eq!(menus[0].date, "Today");
+//! let html = reqwest::get(format!("{}?format=html", url)).await?.text().await?;
+//! assert!(html.contains("<h2>Today</h2>"));
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{BTreeSet, HashMap};
+use std::convert::Infallible;
+use std::net::{SocketAddr, TcpListener};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use hyper::{header, Body, Method, Request, Response, StatusCode};
+use crate::{Client, Error, Menu};
+
+mod image;
+
+pub use image::{ImageProxy, DEFAULT_IMAGE_HOSTS, DEFAULT_IMAGE_TTL, DEFAULT_MAX_CACHE_SIZE, DEFAULT_MAX_IMAGE_SIZE};
+
+/// How long fetched menus are served from cache by default.
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// HTTP server exposing the menus.
+///
+/// See the [module documentation](self) for the description of the API.
+pub struct Server {
+    client: Client,
+    cache_ttl: Duration,
+    cache: Mutex<HashMap<(String, String), CacheEntry>>,
+    tokens: HashMap<String, Role>,
+    registry: Mutex<BTreeSet<(String, String)>>,
+    images: Option<ImageProxy>,
+    #[cfg(feature = "binary-cache")]
+    disk_cache: Option<crate::cache::DiskCache>,
+}
+
+/// Permissions granted to a token.
+///
+/// Roles are ordered, admin can do everything viewer can.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Role {
+    /// Can read menus and list the registered restaurants.
+    Viewer,
+    /// Can also add and remove restaurants and force refreshes.
+    Admin,
+}
+
+struct CacheEntry {
+    // Not Instant because entries loaded from disk may be older than the uptime
+    fetched_at: SystemTime,
+    menus: Arc<Vec<Menu>>,
+}
+
+enum Format {
+    Json,
+    Html,
+}
+
+impl Server {
+    /// Creates server fetching the menus using given client.
+    pub fn new(client: Client) -> Self {
+        Server {
+            client,
+            cache_ttl: DEFAULT_CACHE_TTL,
+            cache: Default::default(),
+            tokens: HashMap::new(),
+            registry: Default::default(),
+            images: None,
+            #[cfg(feature = "binary-cache")]
+            disk_cache: None,
+        }
+    }
+
+    /// Grants the role to the requests authorized by given bearer token.
+    ///
+    /// Once a viewer token is configured, reading requires a token as well.
+    ///
+    /// ```
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use zomato::server::{Role, Server};
+    ///
+    /// # let client = zomato::testing::fixture_client();
+    /// let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    /// let url = format!("http://{}/restaurants/bratislava/u-zlateho-bazanta", listener.local_addr()?);
+    /// tokio::spawn(Server::new(client).token("s3cret", Role::Admin).serve(listener));
+    ///
+    /// let http = reqwest::Client::new();
+    /// assert_eq!(http.put(&url).send().await?.status(), 401);
+    /// assert_eq!(http.put(&url).bearer_auth("s3cret").send().await?.status(), 204);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn token(mut self, token: impl Into<String>, role: Role) -> Self {
+        self.tokens.insert(token.into(), role);
+        self
+    }
+
+    /// Sets how long the fetched menus are served from cache.
+    ///
+    /// The default is [`DEFAULT_CACHE_TTL`].
+    pub fn cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = ttl;
+        self
+    }
+
+    /// Persists the fetched menus in the cache and uses them if they are not in memory.
+    ///
+    /// The age of the menus is preserved, so expired menus are refetched but served if fetching
+    /// fails.
+    #[cfg(feature = "binary-cache")]
+    pub fn disk_cache(mut self, cache: crate::cache::DiskCache) -> Self {
+        self.disk_cache = Some(cache);
+        self
+    }
+
+    /// Enables proxying images at `/img/{hash}`.
+    ///
+    /// See [`ImageProxy`] for details.
+    pub fn image_proxy(mut self, proxy: ImageProxy) -> Self {
+        self.images = Some(proxy);
+        self
+    }
+
+    /// Registers the image at given URL in the image proxy and returns its path.
+    ///
+    /// This fails if the proxy is not enabled, the host of the URL is not allowed or too many
+    /// images were registered.
+    ///
+    /// ```
+    /// use zomato::server::{ImageProxy, Server};
+    ///
+    /// let server = Server::new(zomato::testing::fixture_client()).image_proxy(ImageProxy::new()?);
+    /// let path = server.image_path("https://b.zmtcdn.com/data/dish_photos/1.jpg")?;
+    /// assert!(path.starts_with("/img/"));
+    /// assert!(server.image_path("https://evil.example/1.jpg").is_err());
+    /// # Ok::<_, zomato::Error>(())
+    /// ```
+    pub fn image_path(&self, url: &str) -> Result<String, Error> {
+        match &self.images {
+            Some(images) => images.register(url),
+            None => Err(Error(anyhow::anyhow!("the image proxy is not enabled"))),
+        }
+    }
+
+    /// Returns the menus of the restaurant, fetching them if they are not cached.
+    ///
+    /// Expired menus are returned if fetching fails.
+    ///
+    /// ```
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), zomato::Error> {
+    /// use std::sync::Arc;
+    /// use zomato::testing::{MockTransport, FIXTURE};
+    ///
+    /// let transport = Arc::new(MockTransport::serving(FIXTURE));
+    /// let server = zomato::server::Server::new(zomato::Client::with_transport(transport.clone()));
+    /// server.menus("bratislava", "u-zlateho-bazanta").await?;
+    /// server.menus("bratislava", "u-zlateho-bazanta").await?;
+    /// assert_eq!(transport.requests().len(), 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn menus(&self, city: &str, restaurant: &str) -> Result<Arc<Vec<Menu>>, Error> {
+        let key = (city.to_owned(), restaurant.to_owned());
+        #[cfg(feature = "binary-cache")]
+        self.load_from_disk(&key);
+        if let Some(entry) = self.lock_cache().get(&key) {
+            // If the clock went backwards the entry is treated as fresh
+            if entry.fetched_at.elapsed().unwrap_or_default() < self.cache_ttl {
+                #[cfg(feature = "metrics")]
+                crate::metrics::Metrics::global().cache_hits().increment();
+                return Ok(Arc::clone(&entry.menus));
+            }
+        }
+        #[cfg(feature = "metrics")]
+        crate::metrics::Metrics::global().cache_misses().increment();
+
+        match self.fetch(key.clone()).await {
+            Ok(menus) => Ok(menus),
+            Err(error) => match self.lock_cache().get(&key) {
+                Some(entry) => Ok(Arc::clone(&entry.menus)),
+                None => Err(error),
+            },
+        }
+    }
+
+    /// Fetches the menus of the restaurant and replaces the cached ones.
+    ///
+    /// Unlike [`menus`](Self::menus) this never returns cached menus.
+    pub async fn refresh(&self, city: &str, restaurant: &str) -> Result<Arc<Vec<Menu>>, Error> {
+        self.fetch((city.to_owned(), restaurant.to_owned())).await
+    }
+
+    async fn fetch(&self, key: (String, String)) -> Result<Arc<Vec<Menu>>, Error> {
+        let menus = Arc::new(self.client.get_daily_menu(&key.0, &key.1).await?);
+        #[cfg(feature = "binary-cache")]
+        if let Some(cache) = &self.disk_cache {
+            // The menus are still in memory, so failing to persist them is not fatal
+            let _ = cache.store(&key.0, &key.1, &menus);
+        }
+        let entry = CacheEntry {
+            fetched_at: SystemTime::now(),
+            menus: Arc::clone(&menus),
+        };
+        self.lock_cache().insert(key, entry);
+        Ok(menus)
+    }
+
+    /// Loads the menus from disk into memory unless they are already there.
+    #[cfg(feature = "binary-cache")]
+    fn load_from_disk(&self, key: &(String, String)) {
+        let cache = match &self.disk_cache {
+            Some(cache) => cache,
+            None => return,
+        };
+        if self.lock_cache().contains_key(key) {
+            return;
+        }
+        if let Some((fetched_at, menus)) = cache.load(&key.0, &key.1) {
+            let entry = CacheEntry {
+                fetched_at,
+                menus: Arc::new(menus),
+            };
+            self.lock_cache().entry(key.clone()).or_insert(entry);
+        }
+    }
+
+    /// Adds the restaurant to the registry.
+    ///
+    /// Returns `false` if it was already registered and a policy error if the names are invalid.
+    pub fn add_restaurant(&self, city: &str, restaurant: &str) -> Result<bool, Error> {
+        crate::policy::check_segment("city", city).map_err(|error| Error(error.into()))?;
+        crate::policy::check_segment("restaurant", restaurant).map_err(|error| Error(error.into()))?;
+        Ok(self.lock_registry().insert((city.to_owned(), restaurant.to_owned())))
+    }
+
+    /// Removes the restaurant from the registry.
+    ///
+    /// Returns `false` if it wasn't registered.
+    pub fn remove_restaurant(&self, city: &str, restaurant: &str) -> bool {
+        self.lock_registry().remove(&(city.to_owned(), restaurant.to_owned()))
+    }
+
+    /// Returns the registered restaurants as `(city, restaurant)` pairs, sorted.
+    pub fn restaurants(&self) -> Vec<(String, String)> {
+        self.lock_registry().iter().cloned().collect()
+    }
+
+    /// Listens on given address and serves the requests until an error occurs.
+    pub async fn run(self, addr: SocketAddr) -> Result<(), Error> {
+        let listener = TcpListener::bind(addr).map_err(|error| Error(error.into()))?;
+        self.serve(listener).await
+    }
+
+    /// Serves the requests coming to the listener until an error occurs.
+    pub async fn serve(self, listener: TcpListener) -> Result<(), Error> {
+        use hyper::service::{make_service_fn, service_fn};
+
+        let server = Arc::new(self);
+        let make_service = make_service_fn(move |_| {
+            let server = Arc::clone(&server);
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request| {
+                    let server = Arc::clone(&server);
+                    async move { Ok::<_, Infallible>(server.handle(request).await) }
+                }))
+            }
+        });
+
+        listener.set_nonblocking(true).map_err(|error| Error(error.into()))?;
+        hyper::Server::from_tcp(listener)
+            .map_err(|error| Error(error.into()))?
+            .serve(make_service)
+            .await
+            .map_err(|error| Error(error.into()))
+    }
+
+    async fn handle(&self, request: Request<Body>) -> Response<Body> {
+        let path = request.uri().path().to_owned();
+        let segments = path.trim_start_matches('/').split('/').collect::<Vec<_>>();
+        if segments.iter().any(|segment| segment.is_empty()) {
+            return not_found();
+        }
+
+        let required_role = match (request.method(), segments.as_slice()) {
+            (&Method::GET, ["menu", _, _]) | (&Method::GET, ["restaurants"]) | (&Method::GET, ["status"]) => Role::Viewer,
+            (&Method::GET, ["img", _]) | (&Method::POST, ["img"]) if self.images.is_some() => Role::Viewer,
+            #[cfg(feature = "metrics")]
+            (&Method::GET, ["metrics"]) => Role::Viewer,
+            (&Method::POST, ["menu", _, _, "refresh"]) => Role::Admin,
+            (&Method::PUT, ["restaurants", _, _]) | (&Method::DELETE, ["restaurants", _, _]) => Role::Admin,
+            (_, ["img", _]) | (_, ["img"]) if self.images.is_some() => {
+                return error_response(StatusCode::METHOD_NOT_ALLOWED, "method not allowed");
+            },
+            (_, ["menu", _, _]) | (_, ["menu", _, _, "refresh"]) | (_, ["restaurants"]) | (_, ["restaurants", _, _]) | (_, ["status"]) => {
+                return error_response(StatusCode::METHOD_NOT_ALLOWED, "method not allowed");
+            },
+            #[cfg(feature = "metrics")]
+            (_, ["metrics"]) => return error_response(StatusCode::METHOD_NOT_ALLOWED, "method not allowed"),
+            _ => return not_found(),
+        };
+        if let Some(response) = self.reject_unauthorized(&request, required_role) {
+            return response;
+        }
+
+        match (request.method(), segments.as_slice()) {
+            (_, ["img"]) => {
+                let body = match hyper::body::to_bytes(request.into_body()).await {
+                    Ok(body) => body,
+                    Err(error) => return error_response(StatusCode::BAD_REQUEST, &error.to_string()),
+                };
+                let url = String::from_utf8_lossy(&body);
+                match self.image_path(url.trim()) {
+                    Ok(path) => Response::builder()
+                        .header(header::CONTENT_TYPE, "text/plain; charset=utf-8")
+                        .body(Body::from(path))
+                        .expect("valid response"),
+                    Err(error) => error_response(StatusCode::BAD_REQUEST, &error.to_string()),
+                }
+            },
+            (_, ["img", hash]) => image_response(self.images.as_ref().expect("checked when routing"), hash).await,
+            #[cfg(feature = "metrics")]
+            (_, ["metrics"]) => Response::builder()
+                .header(header::CONTENT_TYPE, "text/plain; version=0.0.4")
+                .body(Body::from(crate::metrics::Metrics::global().render()))
+                .expect("valid response"),
+            (_, ["restaurants"]) => {
+                let restaurants = self.restaurants()
+                    .into_iter()
+                    .map(|(city, restaurant)| serde_json::json!({ "city": city, "restaurant": restaurant }))
+                    .collect::<Vec<_>>();
+                json_response(serde_json::Value::from(restaurants).to_string())
+            },
+            (_, ["status"]) => {
+                let layout_changes = self.client.layout_log().map(|log| log.records()).unwrap_or_default();
+                json_response(serde_json::json!({ "layout_changes": layout_changes }).to_string())
+            },
+            (&Method::PUT, ["restaurants", city, restaurant]) => match self.add_restaurant(city, restaurant) {
+                Ok(_) => empty_response(),
+                Err(error) => error_response(StatusCode::BAD_REQUEST, &error.to_string()),
+            },
+            (_, ["restaurants", city, restaurant]) => match self.remove_restaurant(city, restaurant) {
+                true => empty_response(),
+                false => error_response(StatusCode::NOT_FOUND, "restaurant is not registered"),
+            },
+            (_, ["menu", city, restaurant, "refresh"]) => {
+                let result = self.refresh(city, restaurant).await;
+                menu_response(&request, restaurant, result)
+            },
+            (_, ["menu", city, restaurant]) => {
+                let result = self.menus(city, restaurant).await;
+                menu_response(&request, restaurant, result)
+            },
+            _ => unreachable!("all routes were checked above"),
+        }
+    }
+
+    /// Returns error response if the request is not authorized to act in given role.
+    fn reject_unauthorized(&self, request: &Request<Body>, required_role: Role) -> Option<Response<Body>> {
+        if required_role == Role::Viewer && !self.tokens.values().any(|role| *role == Role::Viewer) {
+            return None;
+        }
+
+        let token = request
+            .headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "));
+        match token.and_then(|token| self.tokens.get(token.trim())) {
+            Some(role) if *role >= required_role => None,
+            Some(_) => Some(error_response(StatusCode::FORBIDDEN, "the token is not allowed to do this")),
+            None => {
+                let mut response = error_response(StatusCode::UNAUTHORIZED, "missing or unknown bearer token");
+                response.headers_mut().insert(header::WWW_AUTHENTICATE, header::HeaderValue::from_static("Bearer"));
+                Some(response)
+            },
+        }
+    }
+
+    fn lock_cache(&self) -> std::sync::MutexGuard<'_, HashMap<(String, String), CacheEntry>> {
+        self.cache.lock().unwrap_or_else(|error| error.into_inner())
+    }
+
+    fn lock_registry(&self) -> std::sync::MutexGuard<'_, BTreeSet<(String, String)>> {
+        self.registry.lock().unwrap_or_else(|error| error.into_inner())
+    }
+}
+
+fn menu_response(request: &Request<Body>, restaurant: &str, result: Result<Arc<Vec<Menu>>, Error>) -> Response<Body> {
+    let format = match requested_format(request) {
+        Some(format) => format,
+        None => return error_response(StatusCode::BAD_REQUEST, "unknown format, use json or html"),
+    };
+
+    let menus = match result {
+        Ok(menus) => menus,
+        Err(error) if error.policy_violation().is_some() => return error_response(StatusCode::BAD_REQUEST, &error.to_string()),
+        Err(error) => return error_response(StatusCode::BAD_GATEWAY, &format!("failed to fetch the menu: {:#}", error.0)),
+    };
+
+    match format {
+        Format::Json => json_response(serde_json::to_string(&*menus).expect("menus are always serializable")),
+        Format::Html => Response::builder()
+            .header(header::CONTENT_TYPE, "text/html; charset=utf-8")
+            .body(Body::from(html_page(restaurant, &menus)))
+            .expect("valid response"),
+    }
+}
+
+async fn image_response(images: &ImageProxy, hash: &str) -> Response<Body> {
+    let image = match images.get(hash).await {
+        Some(Ok(image)) => image,
+        Some(Err(error)) => return error_response(StatusCode::BAD_GATEWAY, &format!("failed to fetch the image: {:#}", error.0)),
+        None => return error_response(StatusCode::NOT_FOUND, "unknown image"),
+    };
+    Response::builder()
+        .header(header::CONTENT_TYPE, image.content_type)
+        .header(header::CACHE_CONTROL, format!("public, max-age={}", images.ttl_secs()))
+        .header(header::X_CONTENT_TYPE_OPTIONS, "nosniff")
+        // Nothing in the image may run in the origin of the server
+        .header(header::CONTENT_SECURITY_POLICY, "sandbox")
+        .body(Body::from(image.data))
+        .expect("valid response")
+}
+
+/// Returns the format requested by the query string or `Accept` header.
+///
+/// `None` is returned if the query string requests an unknown format.
+fn requested_format(request: &Request<Body>) -> Option<Format> {
+    let query_format = request
+        .uri()
+        .query()
+        .into_iter()
+        .flat_map(|query| query.split('&'))
+        .find_map(|param| param.strip_prefix("format="));
+    match query_format {
+        Some("json") => return Some(Format::Json),
+        Some("html") => return Some(Format::Html),
+        Some(_) => return None,
+        None => (),
+    }
+
+    // Browsers send */* too, so only an explicit text/html ahead of JSON counts
+    let accept = request.headers().get(header::ACCEPT).and_then(|accept| accept.to_str().ok()).unwrap_or("");
+    let html = accept.find("text/html");
+    let json = accept.find("application/json");
+    match (html, json) {
+        (Some(html), Some(json)) if json < html => Some(Format::Json),
+        (Some(_), _) => Some(Format::Html),
+        _ => Some(Format::Json),
+    }
+}
+
+fn html_page(restaurant: &str, menus: &[Menu]) -> String {
+    let title = crate::export::html::escape(restaurant);
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"><title>{}</title></head>\n<body>\n<h1>{}</h1>\n{}</body>\n</html>\n",
+        title,
+        title,
+        crate::export::html::to_html(menus),
+    )
+}
+
+fn json_response(body: String) -> Response<Body> {
+    Response::builder()
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body))
+        .expect("valid response")
+}
+
+fn empty_response() -> Response<Body> {
+    Response::builder()
+        .status(StatusCode::NO_CONTENT)
+        .body(Body::empty())
+        .expect("valid response")
+}
+
+fn not_found() -> Response<Body> {
+    error_response(StatusCode::NOT_FOUND, "unknown path, see the documentation of the API")
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, "text/plain; charset=utf-8")
+        .body(Body::from(format!("{}\n", message)))
+        .expect("valid response")
+}
@@ -0,0 +1,291 @@
+//! Caching proxy of images hosted by Zomato.
+//!
+//! Dashboards embedding photos of dishes or restaurants directly from the Zomato CDN break when
+//! hotlinking is refused and leak the address of the dashboard through `Referer`. The proxy
+//! downloads the images itself and serves them at `GET /img/{hash}`.
+//!
+//! Only registered images are served, so that the proxy can't be used to download arbitrary
+//! content. Images are registered using [`Server::image_path`](super::Server::image_path) or
+//! `POST /img` with the URL of the image as the body, both returning the path of the proxied
+//! image. The URL must target one of the allowed hosts and at most 1000 images may be registered.
+//! Registering and downloading images requires the viewer role if viewer tokens are configured.
+//!
+//! Only raster images are proxied, SVG images may contain scripts which would run in the origin
+//! of the server. The downloaded images are cached up to a total size, the oldest ones are evicted
+//! first.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use hyper::body::Bytes;
+use sha2::{Digest, Sha256};
+use crate::throttle::Throttle;
+use crate::Error;
+
+/// Hosts images are downloaded from by default.
+pub const DEFAULT_IMAGE_HOSTS: &[&str] = &["b.zmtcdn.com"];
+
+/// Largest image downloaded by default, in bytes.
+pub const DEFAULT_MAX_IMAGE_SIZE: usize = 5 * 1024 * 1024;
+
+/// Largest total size of the cached images by default, in bytes.
+pub const DEFAULT_MAX_CACHE_SIZE: usize = 64 * 1024 * 1024;
+
+/// Content types of images which are proxied.
+///
+/// These can't contain scripts, unlike `image/svg+xml`.
+const RASTER_IMAGE_TYPES: &[&str] = &["image/avif", "image/gif", "image/jpeg", "image/png", "image/webp"];
+
+/// How long downloaded images are served from cache by default.
+pub const DEFAULT_IMAGE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// How many images are downloaded per minute by default.
+const DEFAULT_REQUESTS_PER_MINUTE: u32 = 60;
+
+/// How many images may be downloaded at once by default.
+const DEFAULT_BURST: u32 = 10;
+
+/// How many images may be registered, so that the cache can't grow indefinitely.
+const MAX_IMAGES: usize = 1000;
+
+/// Longest time downloading an image may take.
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Configuration and cache of the image proxy.
+///
+/// Enable it using [`Server::image_proxy`](super::Server::image_proxy).
+///
+/// ```
+/// # use std::io::{Read, Write};
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use zomato::server::{ImageProxy, Server};
+///
+/// # let origin = std::net::TcpListener::bind("127.0.0.1:0")?;
+/// # let origin_url = format!("http://{}", origin.local_addr()?);
+/// # let image_url = format!("{}/dish.png", origin_url);
+/// # let (sender, downloads) = std::sync::mpsc::channel();
+/// # std::thread::spawn(move || for stream in origin.incoming() {
+/// #     let mut stream = stream.unwrap();
+/// #     let mut request = [0; 1024];
+/// #     let len = stream.read(&mut request).unwrap_or(0);
+/// #     let path = String::from_utf8_lossy(&request[..len]).split(' ').nth(1).unwrap_or_default().to_owned();
+/// #     let content_type = if path.ends_with(".svg") { "image/svg+xml" } else { "image/png" };
+/// #     sender.send(path).unwrap();
+/// #     let _ = write!(stream, "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: 4\r\nConnection: close\r\n\r\nPNG!", content_type);
+/// # });
+/// # let client = zomato::testing::fixture_client();
+/// let proxy = ImageProxy::new()?.allowed_host("127.0.0.1").max_cache_size(4);
+/// let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+/// let base = format!("http://{}", listener.local_addr()?);
+/// tokio::spawn(Server::new(client).image_proxy(proxy).serve(listener));
+///
+/// let http = reqwest::Client::new();
+/// let path = http.post(format!("{}/img", base)).body(image_url).send().await?.text().await?;
+/// let image = http.get(format!("{}{}", base, path)).send().await?;
+/// assert_eq!(image.headers()["content-type"], "image/png");
+/// assert_eq!(image.headers()["content-security-policy"], "sandbox");
+/// assert_eq!(image.bytes().await?, "PNG!");
+///
+/// // The cache fits a single image, so the first one is evicted
+/// let other_path = http.post(format!("{}/img", base)).body(format!("{}/other.png", origin_url)).send().await?.text().await?;
+/// http.get(format!("{}{}", base, other_path)).send().await?;
+/// http.get(format!("{}{}", base, path)).send().await?;
+/// assert_eq!(downloads.try_iter().collect::<Vec<_>>(), ["/dish.png", "/other.png", "/dish.png"]);
+///
+/// // SVG images may contain scripts
+/// let svg_path = http.post(format!("{}/img", base)).body(format!("{}/drawing.svg", origin_url)).send().await?.text().await?;
+/// assert_eq!(http.get(format!("{}{}", base, svg_path)).send().await?.status(), 502);
+///
+/// let response = http.post(format!("{}/img", base)).body("http://evil.example/x.png").send().await?;
+/// assert_eq!(response.status(), 400);
+/// # Ok(())
+/// # }
+/// ```
+pub struct ImageProxy {
+    http: reqwest::Client,
+    allowed_hosts: Vec<String>,
+    max_size: usize,
+    max_cache_size: usize,
+    ttl: Duration,
+    throttle: Throttle,
+    /// URLs of the registered images by their hashes.
+    urls: Mutex<HashMap<String, String>>,
+    cache: Mutex<HashMap<String, CachedImage>>,
+}
+
+/// Downloaded image.
+#[derive(Clone)]
+pub(crate) struct CachedImage {
+    fetched_at: Instant,
+    pub(crate) content_type: String,
+    pub(crate) data: Bytes,
+}
+
+impl ImageProxy {
+    /// Creates proxy with default settings.
+    ///
+    /// This fails if the HTTP client can't be initialized.
+    pub fn new() -> Result<Self, Error> {
+        // Redirects could lead outside of the allowed hosts
+        let http = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .timeout(DOWNLOAD_TIMEOUT)
+            .build()
+            .map_err(|error| Error(error.into()))?;
+        Ok(ImageProxy {
+            http,
+            allowed_hosts: DEFAULT_IMAGE_HOSTS.iter().map(|host| (*host).to_owned()).collect(),
+            max_size: DEFAULT_MAX_IMAGE_SIZE,
+            max_cache_size: DEFAULT_MAX_CACHE_SIZE,
+            ttl: DEFAULT_IMAGE_TTL,
+            throttle: Throttle::new(Some((DEFAULT_REQUESTS_PER_MINUTE, DEFAULT_BURST)), Duration::ZERO),
+            urls: Default::default(),
+            cache: Default::default(),
+        })
+    }
+
+    /// Allows downloading images from given host in addition to [`DEFAULT_IMAGE_HOSTS`].
+    pub fn allowed_host(mut self, host: impl Into<String>) -> Self {
+        self.allowed_hosts.push(host.into());
+        self
+    }
+
+    /// Sets the largest image downloaded, [`DEFAULT_MAX_IMAGE_SIZE`] by default.
+    ///
+    /// Larger images are refused.
+    pub fn max_size(mut self, bytes: usize) -> Self {
+        self.max_size = bytes;
+        self
+    }
+
+    /// Sets the largest total size of the cached images, [`DEFAULT_MAX_CACHE_SIZE`] by default.
+    ///
+    /// The oldest images are evicted to make room for new ones, images larger than the limit are
+    /// served without caching them.
+    pub fn max_cache_size(mut self, bytes: usize) -> Self {
+        self.max_cache_size = bytes;
+        self
+    }
+
+    /// Sets how long downloaded images are served from cache, [`DEFAULT_IMAGE_TTL`] by default.
+    ///
+    /// Expired images are served if downloading them again fails.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Limits the rate of downloads, 60 per minute with bursts of 10 by default.
+    ///
+    /// Requests for images which are not cached wait until they may be downloaded.
+    ///
+    /// # Panics
+    ///
+    /// This panics if `requests_per_minute` is zero.
+    pub fn rate_limit(mut self, requests_per_minute: u32, burst: u32) -> Self {
+        assert_ne!(requests_per_minute, 0, "rate limit must allow some requests");
+        self.throttle = Throttle::new(Some((requests_per_minute, burst)), Duration::ZERO);
+        self
+    }
+
+    /// Registers the image and returns its path relative to the server.
+    pub(crate) fn register(&self, url: &str) -> Result<String, Error> {
+        crate::policy::check_url(url, &self.allowed_hosts).map_err(|error| Error(error.into()))?;
+        // Cryptographic hash so that nobody can register a URL colliding with another one
+        let hash = Sha256::digest(url.as_bytes()).iter().map(|byte| format!("{:02x}", byte)).collect::<String>();
+        let path = format!("/img/{}", hash);
+        let mut urls = self.lock_urls();
+        if urls.len() >= MAX_IMAGES && !urls.contains_key(&hash) {
+            return Err(Error(anyhow::anyhow!("too many images registered, at most {} are allowed", MAX_IMAGES)));
+        }
+        urls.insert(hash, url.to_owned());
+        Ok(path)
+    }
+
+    /// Returns the image with given hash, downloading it if it's not cached.
+    ///
+    /// Returns `None` if no image with the hash was registered.
+    pub(crate) async fn get(&self, hash: &str) -> Option<Result<CachedImage, Error>> {
+        let url = self.lock_urls().get(hash)?.clone();
+        if let Some(image) = self.lock_cache().get(hash) {
+            if image.fetched_at.elapsed() < self.ttl {
+                return Some(Ok(image.clone()));
+            }
+        }
+
+        match self.download(&url).await {
+            Ok(image) => {
+                self.cache(hash, image.clone());
+                Some(Ok(image))
+            },
+            Err(error) => match self.lock_cache().get(hash) {
+                Some(image) => Some(Ok(image.clone())),
+                None => Some(Err(Error(error))),
+            },
+        }
+    }
+
+    async fn download(&self, url: &str) -> Result<CachedImage, anyhow::Error> {
+        self.throttle.acquire().await;
+        let mut response = self.http.get(url).send().await?.error_for_status()?;
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or("")
+            .to_owned();
+        let mime_type = content_type.split(';').next().unwrap_or("").trim().to_ascii_lowercase();
+        if !RASTER_IMAGE_TYPES.contains(&&*mime_type) {
+            anyhow::bail!("{} is not a raster image, its content type is '{}'", url, content_type);
+        }
+        if response.content_length().is_some_and(|length| length > self.max_size as u64) {
+            anyhow::bail!("{} is larger than {} bytes", url, self.max_size);
+        }
+
+        let mut data = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            if data.len() + chunk.len() > self.max_size {
+                anyhow::bail!("{} is larger than {} bytes", url, self.max_size);
+            }
+            data.extend_from_slice(&chunk);
+        }
+        Ok(CachedImage {
+            fetched_at: Instant::now(),
+            content_type,
+            data: data.into(),
+        })
+    }
+
+    /// Stores the image, evicting the oldest ones if the cache would exceed its size.
+    fn cache(&self, hash: &str, image: CachedImage) {
+        let mut cache = self.lock_cache();
+        cache.remove(hash);
+        if image.data.len() > self.max_cache_size {
+            return;
+        }
+        let mut size = cache.values().map(|image| image.data.len()).sum::<usize>();
+        while size + image.data.len() > self.max_cache_size {
+            let oldest = cache
+                .iter()
+                .min_by_key(|(_, image)| image.fetched_at)
+                .map(|(hash, _)| hash.clone())
+                .expect("the cache is not empty while its size is positive");
+            size -= cache.remove(&oldest).map_or(0, |image| image.data.len());
+        }
+        cache.insert(hash.to_owned(), image);
+    }
+
+    /// Returns how long clients may cache the images.
+    pub(crate) fn ttl_secs(&self) -> u64 {
+        self.ttl.as_secs()
+    }
+
+    fn lock_urls(&self) -> std::sync::MutexGuard<'_, HashMap<String, String>> {
+        self.urls.lock().unwrap_or_else(|error| error.into_inner())
+    }
+
+    fn lock_cache(&self) -> std::sync::MutexGuard<'_, HashMap<String, CachedImage>> {
+        self.cache.lock().unwrap_or_else(|error| error.into_inner())
+    }
+}
@@ -0,0 +1,110 @@
+//! Append-only log of menu events.
+//!
+//! Each event is stored as a single JSON line, so the log can be inspected using common tools
+//! when debugging why a notification wasn't sent and the events can be read back using
+//! [`read_events`] to deliver them to other sinks later.
+//!
+//! ```
+//! use zomato::sink::MenuEvent;
+//! use zomato::sink::event_log::{read_events, EventLog};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let dir = mktemp::Temp::new_dir()?;
+//! # let path = dir.as_path().join("events.jsonl");
+//! let menus = zomato::parse_daily_menu(zomato::testing::FIXTURE)?;
+//! let log = EventLog::open(&path)?;
+//! log.append(&MenuEvent::new("bratislava", "u-zlateho-bazanta", menus))?;
+//!
+//! let events = read_events(&path)?.collect::<Result<Vec<_>, _>>()?;
+//! assert_eq!(events[0].restaurant, "u-zlateho-bazanta");
+//! assert_eq!(events[0].menus[0].items.len(), 3);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use crate::Error;
+use super::{DeliveryError, MenuEvent, Receipt, Sink, SinkFuture};
+
+/// Sink appending the events into a file.
+pub struct EventLog {
+    file: Mutex<File>,
+}
+
+impl EventLog {
+    /// Opens the log at given path for appending, creating it if it doesn't exist.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(path)?;
+
+        Ok(EventLog {
+            file: Mutex::new(file),
+        })
+    }
+
+    /// Appends the event into the log.
+    pub fn append(&self, event: &MenuEvent) -> io::Result<()> {
+        let mut line = serde_json::to_vec(event).map_err(io::Error::other)?;
+        line.push(b'\n');
+        let mut file = self.file.lock().unwrap_or_else(|error| error.into_inner());
+        // Single write call so that lines aren't interleaved.
+        file.write_all(&line)?;
+        file.flush()
+    }
+}
+
+impl Sink for EventLog {
+    fn deliver<'a>(&'a self, event: &'a MenuEvent) -> SinkFuture<'a> {
+        Box::pin(async move {
+            // Disk may get freed, so it's worth retrying.
+            self.append(event).map_err(DeliveryError::retriable)?;
+            Ok(Receipt::new())
+        })
+    }
+}
+
+/// Iterator over events stored in the log.
+///
+/// Returned by [`read_events`].
+pub struct Events {
+    lines: io::Lines<BufReader<File>>,
+    line_number: usize,
+}
+
+impl Iterator for Events {
+    type Item = Result<MenuEvent, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        use anyhow::Context;
+
+        loop {
+            let line = self.lines.next()?;
+            self.line_number += 1;
+            let line = match line {
+                Ok(line) => line,
+                Err(error) => return Some(Err(Error(error.into()))),
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            let event = serde_json::from_str(&line)
+                .with_context(|| format!("invalid event on line {}", self.line_number))
+                .map_err(Error);
+            return Some(event);
+        }
+    }
+}
+
+/// Reads events stored in the log at given path, in the order they were appended.
+pub fn read_events<P: AsRef<Path>>(path: P) -> io::Result<Events> {
+    let file = File::open(path)?;
+    Ok(Events {
+        lines: BufReader::new(file).lines(),
+        line_number: 0,
+    })
+}
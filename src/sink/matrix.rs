@@ -0,0 +1,167 @@
+//! Posting menus to Matrix rooms.
+//!
+//! The menu is sent as `m.text` message with both plain text and `org.matrix.custom.html` body
+//! using the [client-server API](https://spec.matrix.org/latest/client-server-api/#put_matrixclientv3roomsroomidsendeventtypetxnid).
+//! The access token has to belong to a user who already joined the room.
+//!
+//! ```no_run
+//! use zomato::sink::matrix::MatrixRoom;
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+//! let menus = zomato::parse_daily_menu(zomato::testing::FIXTURE)?;
+//! MatrixRoom::new("https://matrix.example.org", "syt_access_token", "!lunch:example.org")
+//!     .send("U Zlatého Bažanta", menus.first())
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+use serde_derive::Deserialize;
+use crate::export::html::escape;
+use crate::Menu;
+use super::{DeliveryError, MenuEvent, Receipt, Sink, SinkFuture};
+
+/// Sink posting the menus to a Matrix room.
+///
+/// Only the first menu of each event is sent since that's the one for today.
+pub struct MatrixRoom {
+    http: reqwest::Client,
+    homeserver: String,
+    access_token: String,
+    room_id: String,
+    /// Makes transaction ids unique within this process.
+    transaction_counter: AtomicU64,
+}
+
+#[derive(Deserialize)]
+struct SendResponse {
+    event_id: String,
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    errcode: String,
+    #[serde(default)]
+    error: String,
+}
+
+impl MatrixRoom {
+    /// Creates sink posting to the room as the user authenticated by the access token.
+    ///
+    /// The homeserver is the base URL of the client API, e.g. `https://matrix.org`.
+    pub fn new(homeserver: impl Into<String>, access_token: impl Into<String>, room_id: impl Into<String>) -> Self {
+        MatrixRoom {
+            http: reqwest::Client::new(),
+            homeserver: homeserver.into().trim_end_matches('/').to_owned(),
+            access_token: access_token.into(),
+            room_id: room_id.into(),
+            transaction_counter: AtomicU64::new(0),
+        }
+    }
+
+    /// Sends the menu of the restaurant, `None` is sent as missing menu.
+    ///
+    /// The returned receipt contains the id of the created event.
+    pub async fn send(&self, restaurant_name: &str, menu: Option<&Menu>) -> Result<Receipt, DeliveryError> {
+        let mut url = reqwest::Url::parse(&self.homeserver).map_err(DeliveryError::permanent)?;
+        url.path_segments_mut()
+            .map_err(|_| DeliveryError::permanent("invalid homeserver URL"))?
+            .pop_if_empty()
+            .extend(&["_matrix", "client", "v3", "rooms", &self.room_id, "send", "m.room.message", &self.transaction_id()]);
+        let body = serde_json::json!({
+            "msgtype": "m.text",
+            "body": format_plain(restaurant_name, menu),
+            "format": "org.matrix.custom.html",
+            "formatted_body": format_html(restaurant_name, menu),
+        });
+
+        let response = self.http
+            .put(url)
+            .bearer_auth(&self.access_token)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body.to_string())
+            .send()
+            .await
+            .map_err(DeliveryError::retriable)?;
+        let status = response.status();
+        let text = response.text().await.map_err(DeliveryError::retriable)?;
+
+        if status.is_success() {
+            let response = serde_json::from_str::<SendResponse>(&text).map_err(DeliveryError::retriable)?;
+            return Ok(Receipt::with_message_id(response.event_id));
+        }
+        let message = match serde_json::from_str::<ErrorResponse>(&text) {
+            Ok(error) => format!("Matrix refused the message: {} {}", error.errcode, error.error),
+            Err(_) => format!("Matrix returned status {}", status),
+        };
+        if status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            Err(DeliveryError::retriable(message))
+        } else {
+            Err(DeliveryError::permanent(message))
+        }
+    }
+
+    /// Returns an id the homeserver uses to deduplicate retried requests.
+    fn transaction_id(&self) -> String {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis();
+        format!("zomato-{}-{}", now, self.transaction_counter.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+impl Sink for MatrixRoom {
+    fn deliver<'a>(&'a self, event: &'a MenuEvent) -> SinkFuture<'a> {
+        Box::pin(self.send(&event.restaurant, event.menus.first()))
+    }
+}
+
+/// Formats the menu as plain text body of the message.
+pub fn format_plain(restaurant_name: &str, menu: Option<&Menu>) -> String {
+    let menu = match menu {
+        Some(menu) if !menu.items.is_empty() => menu,
+        _ => return format!("Lunch at {}\nNo daily menu available", restaurant_name),
+    };
+
+    let mut message = format!("Lunch at {} ({})", restaurant_name, menu.date);
+    for item in &menu.items {
+        message.push_str("\n• ");
+        message.push_str(&item.description);
+        if !item.price.is_empty() {
+            message.push_str(" – ");
+            message.push_str(&item.price);
+        }
+    }
+    message
+}
+
+/// Formats the menu as HTML body of the message.
+///
+/// ```
+/// let menus = zomato::parse_daily_menu(zomato::testing::FIXTURE)?;
+/// let html = zomato::sink::matrix::format_html("U Zlatého Bažanta", menus.first());
+/// assert!(html.starts_with("<h4>Lunch at U Zlatého Bažanta (Today)</h4>\n<ul>\n"));
+/// assert!(html.contains("<li>Vyprážaný syr, hranolky, tatárska omáčka (1,3,7) – <em>€6.00</em></li>"));
+/// # Ok::<_, zomato::Error>(())
+/// ```
+pub fn format_html(restaurant_name: &str, menu: Option<&Menu>) -> String {
+    let menu = match menu {
+        Some(menu) if !menu.items.is_empty() => menu,
+        _ => return format!("<h4>Lunch at {}</h4>\n<p>No daily menu available</p>", escape(restaurant_name)),
+    };
+
+    let mut message = format!("<h4>Lunch at {} ({})</h4>\n<ul>\n", escape(restaurant_name), escape(&menu.date));
+    for item in &menu.items {
+        message.push_str("<li>");
+        message.push_str(&escape(&item.description));
+        if !item.price.is_empty() {
+            message.push_str(" – <em>");
+            message.push_str(&escape(&item.price));
+            message.push_str("</em>");
+        }
+        message.push_str("</li>\n");
+    }
+    message.push_str("</ul>");
+    message
+}
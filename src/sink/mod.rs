@@ -0,0 +1,161 @@
+//! Destinations menu updates are delivered to.
+//!
+//! When watching restaurants (see [`Client::watch`](crate::Client::watch)) each observed change
+//! is described by a [`MenuEvent`] which can be passed to any number of [`Sink`]s - e.g. posted to
+//! a chat or recorded into a log.
+//!
+//! ```
+//! use futures_util::StreamExt;
+//! use zomato::sink::{MenuEvent, Sink};
+//! use zomato::sink::event_log::EventLog;
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+//! # let client = zomato::testing::fixture_client();
+//! # let dir = mktemp::Temp::new_dir()?;
+//! # let path = dir.as_path().join("events.jsonl");
+//! let sink = EventLog::open(&path)?;
+//! let updates = client.watch("bratislava", "u-zlateho-bazanta", std::time::Duration::from_secs(600));
+//! futures_util::pin_mut!(updates);
+//! let update = updates.next().await.expect("the stream is infinite")?;
+//! sink.deliver(&MenuEvent::new("bratislava", "u-zlateho-bazanta", update.menus)).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+#[cfg(feature = "email")]
+pub mod email;
+pub mod event_log;
+#[cfg(feature = "matrix")]
+pub mod matrix;
+#[cfg(feature = "mqtt")]
+pub mod mqtt;
+#[cfg(feature = "notify")]
+pub mod notify;
+mod replay;
+mod status;
+#[cfg(feature = "telegram")]
+pub mod telegram;
+// futures of reqwest are not Send in browsers
+#[cfg(all(feature = "webhook", not(target_arch = "wasm32")))]
+pub mod webhook;
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::SystemTime;
+use serde_derive::{Deserialize, Serialize};
+use crate::Menu;
+
+pub use replay::{replay_events, ReplayFailure, ReplayReport};
+pub use status::{DeliveryTracker, SinkStatus};
+
+/// Underlying error returned by sinks.
+pub type SinkError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Future returned by [`Sink::deliver`].
+pub type SinkFuture<'a> = Pin<Box<dyn Future<Output = Result<Receipt, DeliveryError>> + Send + 'a>>;
+
+/// Confirmation of successful delivery.
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct Receipt {
+    /// Identifier of the created message if the destination provides one.
+    pub message_id: Option<String>,
+}
+
+impl Receipt {
+    /// Creates receipt without any details.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates receipt of a message with given identifier.
+    pub fn with_message_id(message_id: impl Into<String>) -> Self {
+        Receipt {
+            message_id: Some(message_id.into()),
+        }
+    }
+}
+
+/// Error returned when delivery fails.
+#[derive(Debug, thiserror::Error)]
+pub enum DeliveryError {
+    /// The delivery might succeed if it's attempted later, e.g. network error.
+    #[error("{0}")]
+    Retriable(SinkError),
+    /// The delivery will fail again, e.g. invalid credentials or malformed message.
+    #[error("{0}")]
+    Permanent(SinkError),
+}
+
+impl DeliveryError {
+    /// Creates retriable error.
+    pub fn retriable(error: impl Into<SinkError>) -> Self {
+        DeliveryError::Retriable(error.into())
+    }
+
+    /// Creates permanent error.
+    pub fn permanent(error: impl Into<SinkError>) -> Self {
+        DeliveryError::Permanent(error.into())
+    }
+
+    /// Returns `true` if the delivery might succeed later.
+    pub fn is_retriable(&self) -> bool {
+        matches!(self, DeliveryError::Retriable(_))
+    }
+}
+
+/// Change of the menu of a restaurant.
+#[derive(Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct MenuEvent {
+    /// When the change was observed.
+    #[serde(with = "crate::timestamp::rfc3339")]
+    pub timestamp: SystemTime,
+    /// City in which the restaurant is located.
+    pub city: String,
+    /// Name of the restaurant as used in the URL.
+    pub restaurant: String,
+    /// The new content of the menu.
+    pub menus: Vec<Menu>,
+}
+
+impl MenuEvent {
+    /// Creates event observed just now.
+    pub fn new(city: impl Into<String>, restaurant: impl Into<String>, menus: Vec<Menu>) -> Self {
+        MenuEvent {
+            timestamp: SystemTime::now(),
+            city: city.into(),
+            restaurant: restaurant.into(),
+            menus,
+        }
+    }
+}
+
+/// Destination of menu events.
+pub trait Sink: Send + Sync {
+    /// Delivers the event.
+    ///
+    /// Sinks should classify their errors so that callers know whether retrying makes sense.
+    fn deliver<'a>(&'a self, event: &'a MenuEvent) -> SinkFuture<'a>;
+}
+
+impl<T: Sink + ?Sized> Sink for Arc<T> {
+    fn deliver<'a>(&'a self, event: &'a MenuEvent) -> SinkFuture<'a> {
+        (**self).deliver(event)
+    }
+}
+
+impl<T: Sink + ?Sized> Sink for Box<T> {
+    fn deliver<'a>(&'a self, event: &'a MenuEvent) -> SinkFuture<'a> {
+        (**self).deliver(event)
+    }
+}
+
+/// Delivers the event to all sinks concurrently.
+///
+/// The results are in the same order as the sinks.
+pub async fn deliver_to_all(event: &MenuEvent, sinks: &[&dyn Sink]) -> Vec<Result<Receipt, DeliveryError>> {
+    futures_util::future::join_all(sinks.iter().map(|sink| sink.deliver(event))).await
+}
@@ -0,0 +1,171 @@
+//! Publishing menus to MQTT brokers.
+//!
+//! Today's menu is published as JSON, so that home-automation systems (e.g. Home Assistant's MQTT
+//! sensor) and e-ink displays can show it without custom glue code. The message looks like
+//! `{"city": "bratislava", "restaurant": "u-zlateho-bazanta", "date": "Today", "items": [...]}`
+//! where the items are serialized [`MenuItem`](crate::MenuItem)s. If the restaurant doesn't
+//! publish a menu `date` is `null` and `items` is empty.
+//!
+//! Messages are retained by default so that displays connecting later see the current menu.
+//!
+//! ```no_run
+//! use zomato::sink::mqtt::{MqttPublisher, QoS};
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+//! let menus = zomato::parse_daily_menu(zomato::testing::FIXTURE)?;
+//! MqttPublisher::new("homeassistant.local", 1883)
+//!     .credentials("zomato", "password")
+//!     .topic("lunch/{restaurant}")
+//!     .qos(QoS::AtLeastOnce)
+//!     .publish("bratislava", "u-zlateho-bazanta", menus.first())
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use rumqttc::{AsyncClient, ConnectionError, Event, EventLoop, MqttOptions, Outgoing, Packet};
+use crate::Menu;
+use super::{DeliveryError, MenuEvent, Receipt, Sink, SinkFuture};
+
+/// Topic used if none is configured.
+pub const DEFAULT_TOPIC: &str = "zomato/{city}/{restaurant}";
+
+/// Delivery guarantee of published messages.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum QoS {
+    /// The message is sent once without confirmation.
+    AtMostOnce,
+    /// The message is resent until the broker confirms it, it may be duplicated.
+    #[default]
+    AtLeastOnce,
+    /// The message is delivered exactly once using a four-way handshake.
+    ExactlyOnce,
+}
+
+impl From<QoS> for rumqttc::QoS {
+    fn from(qos: QoS) -> Self {
+        match qos {
+            QoS::AtMostOnce => rumqttc::QoS::AtMostOnce,
+            QoS::AtLeastOnce => rumqttc::QoS::AtLeastOnce,
+            QoS::ExactlyOnce => rumqttc::QoS::ExactlyOnce,
+        }
+    }
+}
+
+/// Sink publishing today's menu to an MQTT topic.
+///
+/// A new connection is made for each message since menus change rarely.
+pub struct MqttPublisher {
+    host: String,
+    port: u16,
+    client_id: String,
+    credentials: Option<(String, String)>,
+    topic: String,
+    qos: QoS,
+    retain: bool,
+}
+
+impl MqttPublisher {
+    /// Creates publisher connecting to the broker without encryption.
+    pub fn new(host: impl Into<String>, port: u16) -> Self {
+        MqttPublisher {
+            host: host.into(),
+            port,
+            client_id: "zomato".to_owned(),
+            credentials: None,
+            topic: DEFAULT_TOPIC.to_owned(),
+            qos: QoS::default(),
+            retain: true,
+        }
+    }
+
+    /// Authenticates to the broker using user name and password.
+    pub fn credentials(mut self, user: impl Into<String>, password: impl Into<String>) -> Self {
+        self.credentials = Some((user.into(), password.into()));
+        self
+    }
+
+    /// Sets the client id presented to the broker, `zomato` by default.
+    ///
+    /// Brokers disconnect clients with duplicate ids so it has to be unique.
+    pub fn client_id(mut self, client_id: impl Into<String>) -> Self {
+        self.client_id = client_id.into();
+        self
+    }
+
+    /// Sets the topic, `{city}` and `{restaurant}` are replaced with the respective values.
+    ///
+    /// The default is [`DEFAULT_TOPIC`].
+    pub fn topic(mut self, topic: impl Into<String>) -> Self {
+        self.topic = topic.into();
+        self
+    }
+
+    /// Sets the delivery guarantee, [`QoS::AtLeastOnce`] by default.
+    pub fn qos(mut self, qos: QoS) -> Self {
+        self.qos = qos;
+        self
+    }
+
+    /// Sets whether the broker keeps the message for future subscribers, `true` by default.
+    pub fn retain(mut self, retain: bool) -> Self {
+        self.retain = retain;
+        self
+    }
+
+    /// Publishes the menu of the restaurant and waits until it's delivered to the broker.
+    pub async fn publish(&self, city: &str, restaurant: &str, menu: Option<&Menu>) -> Result<Receipt, DeliveryError> {
+        let topic = self.topic.replace("{city}", city).replace("{restaurant}", restaurant);
+        let payload = serde_json::json!({
+            "city": city,
+            "restaurant": restaurant,
+            "date": menu.map(|menu| &menu.date),
+            "items": menu.map(|menu| &*menu.items).unwrap_or_default(),
+        });
+
+        let mut options = MqttOptions::new(&self.client_id, &self.host, self.port);
+        if let Some((user, password)) = &self.credentials {
+            options.set_credentials(user, password);
+        }
+        let (client, mut event_loop) = AsyncClient::new(options, 10);
+        client
+            .publish(topic, self.qos.into(), self.retain, payload.to_string())
+            .await
+            .map_err(DeliveryError::permanent)?;
+        poll_until(&mut event_loop, |event| is_delivered(self.qos, event)).await?;
+
+        client.disconnect().await.map_err(DeliveryError::permanent)?;
+        poll_until(&mut event_loop, |event| matches!(event, Event::Outgoing(Outgoing::Disconnect))).await?;
+        Ok(Receipt::new())
+    }
+}
+
+impl Sink for MqttPublisher {
+    fn deliver<'a>(&'a self, event: &'a MenuEvent) -> SinkFuture<'a> {
+        Box::pin(self.publish(&event.city, &event.restaurant, event.menus.first()))
+    }
+}
+
+/// Returns `true` if the event completes delivery with given guarantee.
+fn is_delivered(qos: QoS, event: &Event) -> bool {
+    matches!(
+        (qos, event),
+        (QoS::AtMostOnce, Event::Outgoing(Outgoing::Publish(_)))
+            | (QoS::AtLeastOnce, Event::Incoming(Packet::PubAck(_)))
+            | (QoS::ExactlyOnce, Event::Incoming(Packet::PubComp(_)))
+    )
+}
+
+/// Drives the connection until the event is observed.
+async fn poll_until(event_loop: &mut EventLoop, mut done: impl FnMut(&Event) -> bool) -> Result<(), DeliveryError> {
+    loop {
+        match event_loop.poll().await {
+            Ok(event) if done(&event) => return Ok(()),
+            Ok(_) => (),
+            // Invalid credentials or client id won't get better
+            Err(error @ ConnectionError::ConnectionRefused(_)) => return Err(DeliveryError::permanent(error)),
+            Err(error) => return Err(DeliveryError::retriable(error)),
+        }
+    }
+}
@@ -0,0 +1,81 @@
+//! Desktop notifications.
+//!
+//! Shows today's menu as a desktop notification, which is handy when run from a timer in the
+//! morning. Only the first menu of each event is shown since notifications have limited space.
+//!
+//! ```no_run
+//! use zomato::sink::notify::DesktopNotifier;
+//!
+//! let menus = zomato::parse_daily_menu(zomato::testing::FIXTURE)?;
+//! DesktopNotifier::new().notify("U Zlatého Bažanta", menus.first())?;
+//! # Ok::<_, Box<dyn std::error::Error>>(())
+//! ```
+
+use notify_rust::Notification;
+use crate::Menu;
+use super::{DeliveryError, MenuEvent, Receipt, Sink, SinkFuture};
+
+/// Sink showing the menus as desktop notifications.
+pub struct DesktopNotifier {
+    app_name: String,
+}
+
+impl DesktopNotifier {
+    /// Creates notifier presenting itself as `zomato`.
+    pub fn new() -> Self {
+        Self::with_app_name("zomato")
+    }
+
+    /// Creates notifier presenting itself with given application name.
+    pub fn with_app_name(app_name: impl Into<String>) -> Self {
+        DesktopNotifier {
+            app_name: app_name.into(),
+        }
+    }
+
+    /// Shows the menu of the restaurant, `None` is shown as missing menu.
+    pub fn notify(&self, restaurant_name: &str, menu: Option<&Menu>) -> Result<(), notify_rust::error::Error> {
+        Notification::new()
+            .appname(&self.app_name)
+            .summary(&format!("Lunch at {}", restaurant_name))
+            .body(&body(menu))
+            .show()?;
+        Ok(())
+    }
+}
+
+impl Default for DesktopNotifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sink for DesktopNotifier {
+    fn deliver<'a>(&'a self, event: &'a MenuEvent) -> SinkFuture<'a> {
+        Box::pin(async move {
+            // The notification daemon may be just starting, so the failure is worth retrying.
+            self.notify(&event.restaurant, event.menus.first()).map_err(DeliveryError::retriable)?;
+            Ok(Receipt::new())
+        })
+    }
+}
+
+fn body(menu: Option<&Menu>) -> String {
+    let menu = match menu {
+        Some(menu) if !menu.items.is_empty() => menu,
+        _ => return "No daily menu available".to_owned(),
+    };
+
+    let mut body = String::new();
+    for item in &menu.items {
+        if !body.is_empty() {
+            body.push('\n');
+        }
+        body.push_str(&item.description);
+        if !item.price.is_empty() {
+            body.push_str(" – ");
+            body.push_str(&item.price);
+        }
+    }
+    body
+}
@@ -0,0 +1,67 @@
+use crate::Error;
+use super::{deliver_to_all, DeliveryError, MenuEvent, Sink};
+
+/// Summary of [`replay_events`].
+pub struct ReplayReport {
+    /// Number of events read.
+    pub replayed: usize,
+    /// Deliveries that failed.
+    pub failed: Vec<ReplayFailure>,
+}
+
+/// Event which failed to be delivered to a sink.
+pub struct ReplayFailure {
+    /// Zero-based position of the event in the replayed sequence.
+    pub event: usize,
+    /// Index of the sink in the slice passed to [`replay_events`].
+    pub sink: usize,
+    /// Error returned by the sink.
+    pub error: DeliveryError,
+}
+
+/// Delivers previously recorded events to the sinks again.
+///
+/// The events are delivered one by one in their original order, each event is delivered to all
+/// sinks concurrently. Delivery failures don't stop the replay, they are collected in the report
+/// so that the failed deliveries can be retried. Failure to read an event stops the replay
+/// though, since the following events can't be trusted.
+///
+/// ```
+/// use zomato::sink::{replay_events, MenuEvent, Sink};
+/// use zomato::sink::event_log::{read_events, EventLog};
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # let dir = mktemp::Temp::new_dir()?;
+/// # let (old_path, new_path) = (dir.as_path().join("old.jsonl"), dir.as_path().join("new.jsonl"));
+/// # let menus = zomato::parse_daily_menu(zomato::testing::FIXTURE)?;
+/// # EventLog::open(&old_path)?.append(&MenuEvent::new("bratislava", "u-zlateho-bazanta", menus))?;
+/// let new_log = EventLog::open(&new_path)?;
+/// let report = replay_events(read_events(&old_path)?, &[&new_log]).await?;
+/// assert_eq!(report.replayed, 1);
+/// assert!(report.failed.is_empty());
+/// # Ok(())
+/// # }
+/// ```
+pub async fn replay_events<I>(events: I, sinks: &[&dyn Sink]) -> Result<ReplayReport, Error> where I: IntoIterator<Item = Result<MenuEvent, Error>> {
+    let mut report = ReplayReport {
+        replayed: 0,
+        failed: Vec::new(),
+    };
+
+    for (index, event) in events.into_iter().enumerate() {
+        let event = event?;
+        let results = deliver_to_all(&event, sinks).await;
+        for (sink, result) in results.into_iter().enumerate() {
+            if let Err(error) = result {
+                report.failed.push(ReplayFailure {
+                    event: index,
+                    sink,
+                    error,
+                });
+            }
+        }
+        report.replayed += 1;
+    }
+    Ok(report)
+}
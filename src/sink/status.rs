@@ -0,0 +1,97 @@
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::SystemTime;
+use serde_derive::Serialize;
+use super::{DeliveryError, Receipt};
+
+/// Health of a single sink as recorded by [`DeliveryTracker`].
+///
+/// This serializes into JSON suitable for status endpoints.
+#[derive(Clone, Debug, Serialize)]
+#[non_exhaustive]
+pub struct SinkStatus {
+    /// Name under which the sink was recorded.
+    pub name: String,
+    /// Number of successful deliveries.
+    pub delivered: u64,
+    /// Number of failed deliveries.
+    pub failed: u64,
+    /// When the last successful delivery happened.
+    #[serde(serialize_with = "crate::timestamp::rfc3339::serialize_option")]
+    pub last_success: Option<SystemTime>,
+    /// Identifier of the last delivered message if the sink provides it.
+    pub last_message_id: Option<String>,
+    /// When the current streak of failures started, `None` if the last delivery succeeded.
+    #[serde(serialize_with = "crate::timestamp::rfc3339::serialize_option")]
+    pub failing_since: Option<SystemTime>,
+    /// Error of the last failed delivery.
+    pub last_error: Option<String>,
+    /// Whether the last error was retriable.
+    pub last_error_retriable: bool,
+}
+
+/// Aggregates delivery results of named sinks.
+///
+/// ```
+/// use zomato::sink::{DeliveryError, DeliveryTracker, Receipt};
+///
+/// let tracker = DeliveryTracker::new();
+/// tracker.record("telegram", &Ok(Receipt::with_message_id("42")));
+/// tracker.record("webhook", &Err(DeliveryError::retriable("connection refused")));
+///
+/// let status = tracker.status();
+/// assert_eq!(status[0].name, "telegram");
+/// assert_eq!(status[0].last_message_id.as_deref(), Some("42"));
+/// assert!(status[1].failing_since.is_some());
+/// ```
+#[derive(Default)]
+pub struct DeliveryTracker {
+    sinks: Mutex<BTreeMap<String, SinkStatus>>,
+}
+
+impl DeliveryTracker {
+    /// Creates tracker without any records.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Records result of delivery to the sink with given name.
+    pub fn record(&self, sink: &str, result: &Result<Receipt, DeliveryError>) {
+        self.record_at(sink, result, SystemTime::now())
+    }
+
+    /// Records result of delivery that happened at given time.
+    pub fn record_at(&self, sink: &str, result: &Result<Receipt, DeliveryError>, time: SystemTime) {
+        let mut sinks = self.sinks.lock().unwrap_or_else(|error| error.into_inner());
+        let status = sinks.entry(sink.to_owned()).or_insert_with(|| SinkStatus {
+            name: sink.to_owned(),
+            delivered: 0,
+            failed: 0,
+            last_success: None,
+            last_message_id: None,
+            failing_since: None,
+            last_error: None,
+            last_error_retriable: false,
+        });
+        match result {
+            Ok(receipt) => {
+                status.delivered += 1;
+                status.last_success = Some(time);
+                status.last_message_id = receipt.message_id.clone();
+                status.failing_since = None;
+            },
+            Err(error) => {
+                status.failed += 1;
+                status.failing_since.get_or_insert(time);
+                status.last_error = Some(error.to_string());
+                status.last_error_retriable = error.is_retriable();
+            },
+        }
+    }
+
+    /// Returns the current status of all recorded sinks, ordered by name.
+    pub fn status(&self) -> Vec<SinkStatus> {
+        let sinks = self.sinks.lock().unwrap_or_else(|error| error.into_inner());
+        sinks.values().cloned().collect()
+    }
+}
@@ -0,0 +1,196 @@
+//! Sending menus to Telegram chats.
+//!
+//! The menu is formatted using `MarkdownV2` and sent by a bot using the
+//! [Bot API](https://core.telegram.org/bots/api#sendmessage). Create the bot by talking to
+//! `@BotFather` and add it to the chat, the chat id of groups is negative.
+//!
+//! ```no_run
+//! use zomato::sink::telegram::TelegramBot;
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+//! let menus = zomato::parse_daily_menu(zomato::testing::FIXTURE)?;
+//! TelegramBot::new("123456:ABC-DEF", "-1001234567890")
+//!     .send("U Zlatého Bažanta", menus.first())
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use serde_derive::Deserialize;
+use crate::format::{shorten, Truncation};
+use crate::Menu;
+use super::{DeliveryError, MenuEvent, Receipt, Sink, SinkFuture};
+
+/// URL of the official Bot API server.
+pub const DEFAULT_API_URL: &str = "https://api.telegram.org";
+
+/// Sink sending the menus to a Telegram chat.
+///
+/// Only the first menu of each event is sent since that's the one for today.
+pub struct TelegramBot {
+    http: reqwest::Client,
+    api_url: String,
+    token: String,
+    chat_id: String,
+    formatter: TelegramFormatter,
+}
+
+#[derive(Deserialize)]
+struct ApiResponse {
+    ok: bool,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    error_code: Option<u16>,
+    #[serde(default)]
+    result: Option<SentMessage>,
+}
+
+#[derive(Deserialize)]
+struct SentMessage {
+    message_id: i64,
+}
+
+impl TelegramBot {
+    /// Creates bot authenticated using the token sending messages to given chat.
+    ///
+    /// The chat is either numeric id or `@channelusername`.
+    pub fn new(token: impl Into<String>, chat_id: impl Into<String>) -> Self {
+        TelegramBot {
+            http: reqwest::Client::new(),
+            api_url: DEFAULT_API_URL.to_owned(),
+            token: token.into(),
+            chat_id: chat_id.into(),
+            formatter: TelegramFormatter::default(),
+        }
+    }
+
+    /// Formats the messages using the formatter, e.g. to shorten long descriptions.
+    pub fn formatter(mut self, formatter: TelegramFormatter) -> Self {
+        self.formatter = formatter;
+        self
+    }
+
+    /// Uses a different Bot API server, e.g. a self-hosted one.
+    pub fn api_url(mut self, url: impl Into<String>) -> Self {
+        self.api_url = url.into().trim_end_matches('/').to_owned();
+        self
+    }
+
+    /// Sends the menu of the restaurant, `None` is sent as missing menu.
+    ///
+    /// The returned receipt contains the id of the sent message.
+    pub async fn send(&self, restaurant_name: &str, menu: Option<&Menu>) -> Result<Receipt, DeliveryError> {
+        let body = serde_json::json!({
+            "chat_id": self.chat_id,
+            "text": self.formatter.format(restaurant_name, menu),
+            "parse_mode": "MarkdownV2",
+        });
+        let response = self.http
+            .post(format!("{}/bot{}/sendMessage", self.api_url, self.token))
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body.to_string())
+            .send()
+            .await
+            .map_err(|error| DeliveryError::retriable(error.without_url()))?;
+        let status = response.status();
+        let text = response.text().await.map_err(|error| DeliveryError::retriable(error.without_url()))?;
+        let response = serde_json::from_str::<ApiResponse>(&text)
+            .map_err(|_| DeliveryError::retriable(format!("Telegram returned status {}", status)))?;
+
+        match response {
+            ApiResponse { ok: true, result: Some(message), .. } => Ok(Receipt::with_message_id(message.message_id.to_string())),
+            ApiResponse { ok: true, result: None, .. } => Ok(Receipt::new()),
+            ApiResponse { description, error_code, .. } => {
+                let message = format!("Telegram refused the message: {}", description.as_deref().unwrap_or("unknown error"));
+                // Rate limiting and server errors go away, the rest won't
+                match error_code {
+                    Some(429) | Some(500..=599) => Err(DeliveryError::retriable(message)),
+                    _ => Err(DeliveryError::permanent(message)),
+                }
+            },
+        }
+    }
+}
+
+impl Sink for TelegramBot {
+    fn deliver<'a>(&'a self, event: &'a MenuEvent) -> SinkFuture<'a> {
+        Box::pin(self.send(&event.restaurant, event.menus.first()))
+    }
+}
+
+/// Formats the menu as `MarkdownV2` message.
+///
+/// ```
+/// let menus = zomato::parse_daily_menu(zomato::testing::FIXTURE)?;
+/// let message = zomato::sink::telegram::format_menu("U Zlatého Bažanta", menus.first());
+/// assert!(message.starts_with("*Lunch at U Zlatého Bažanta* \\(Today\\)\n"));
+/// assert!(message.contains("• Vyprážaný syr, hranolky, tatárska omáčka \\(1,3,7\\) – _€6\\.00_"));
+/// # Ok::<_, zomato::Error>(())
+/// ```
+pub fn format_menu(restaurant_name: &str, menu: Option<&Menu>) -> String {
+    TelegramFormatter::default().format(restaurant_name, menu)
+}
+
+/// Formats the menu as `MarkdownV2` message with descriptions shortened to the maximum width.
+///
+/// ```
+/// use zomato::format::Truncation;
+/// use zomato::sink::telegram::TelegramFormatter;
+///
+/// let menus = zomato::parse_daily_menu(zomato::testing::FIXTURE)?;
+/// let formatter = TelegramFormatter { max_width: Some(20), truncation: Truncation::End };
+/// let message = formatter.format("U Zlatého Bažanta", menus.first());
+/// assert!(message.contains("• Hovädzí vývar s rez… – _€1\\.20_"));
+/// # Ok::<_, zomato::Error>(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct TelegramFormatter {
+    /// Maximum width of each description, measured before escaping.
+    pub max_width: Option<usize>,
+    /// How to shorten descriptions exceeding `max_width`.
+    pub truncation: Truncation,
+}
+
+impl TelegramFormatter {
+    /// Formats the menu of the restaurant, `None` is formatted as missing menu.
+    pub fn format(&self, restaurant_name: &str, menu: Option<&Menu>) -> String {
+        let mut message = format!("*Lunch at {}*", escape(restaurant_name));
+        let menu = match menu {
+            Some(menu) if !menu.items.is_empty() => menu,
+            _ => {
+                message.push_str("\nNo daily menu available");
+                return message;
+            },
+        };
+
+        message.push_str(" \\(");
+        message.push_str(&escape(&menu.date));
+        message.push_str("\\)");
+        for item in &menu.items {
+            message.push_str("\n• ");
+            message.push_str(&escape(&shorten(&item.description, self.max_width, self.truncation)));
+            if !item.price.is_empty() {
+                message.push_str(" – _");
+                message.push_str(&escape(&item.price));
+                message.push('_');
+            }
+        }
+        message
+    }
+}
+
+/// Escapes characters which have special meaning in `MarkdownV2`.
+fn escape(text: &str) -> String {
+    const SPECIAL: &[char] = &['_', '*', '[', ']', '(', ')', '~', '`', '>', '#', '+', '-', '=', '|', '{', '}', '.', '!', '\\'];
+
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if SPECIAL.contains(&c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
@@ -0,0 +1,214 @@
+//! Pushing menus to HTTP webhooks.
+//!
+//! The menus are POSTed as JSON, which can be consumed by custom services, or as a text message
+//! understood by incoming webhooks of chats, see [`PayloadFormat`]. If a secret is configured
+//! the body is signed using HMAC-SHA256 and the signature is sent in the [`SIGNATURE_HEADER`]
+//! header so that the receiver can verify the request came from us.
+//!
+//! Requires the `webhook` feature.
+//!
+//! ```no_run
+//! use zomato::sink::webhook::{PayloadFormat, Webhook};
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+//! let menus = zomato::parse_daily_menu(zomato::testing::FIXTURE)?;
+//! Webhook::new("https://example.com/hooks/lunch")
+//!     .secret("correct horse battery staple")
+//!     .post(&menus)
+//!     .await?;
+//! Webhook::new("https://hooks.slack.com/services/T000/B000/XXXX")
+//!     .payload_format(PayloadFormat::Slack)
+//!     .post(&menus)
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::str::FromStr;
+use hmac::{Hmac, Mac};
+use reqwest::StatusCode;
+use sha2::Sha256;
+use crate::format::{escape_slack, TableFormatter};
+use crate::Menu;
+use super::{DeliveryError, MenuEvent, Receipt, Sink, SinkFuture};
+
+/// Name of the header containing the signature of the body.
+///
+/// The value has the form `sha256=<hex>`, see [`sign`].
+pub const SIGNATURE_HEADER: &str = "X-Zomato-Signature";
+
+/// Shape of the body POSTed to the webhook.
+///
+/// The chat formats send the menus as a table in a code block, so that the prices are aligned.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum PayloadFormat {
+    /// The menus, or the whole event when used as a [`Sink`], serialized as JSON.
+    #[default]
+    Json,
+    /// `{"text": …}` accepted by incoming webhooks of Slack.
+    Slack,
+    /// `{"text": …}` accepted by incoming webhooks of Mattermost.
+    Mattermost,
+    /// `{"content": …}` accepted by webhooks of Discord.
+    Discord,
+}
+
+/// Error returned when the name of payload format is unknown.
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("unknown payload format '{input}', use json, slack, mattermost or discord")]
+pub struct ParsePayloadFormatError {
+    input: String,
+}
+
+/// Parses `json`, `slack`, `mattermost` or `discord`.
+impl FromStr for PayloadFormat {
+    type Err = ParsePayloadFormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(PayloadFormat::Json),
+            "slack" => Ok(PayloadFormat::Slack),
+            "mattermost" => Ok(PayloadFormat::Mattermost),
+            "discord" => Ok(PayloadFormat::Discord),
+            _ => Err(ParsePayloadFormatError { input: s.to_owned() }),
+        }
+    }
+}
+
+impl PayloadFormat {
+    /// Serializes the body, `value` is used by the JSON format and the menus by the others.
+    ///
+    /// ```
+    /// use zomato::sink::webhook::PayloadFormat;
+    ///
+    /// let menus = zomato::parse_daily_menu(zomato::testing::FIXTURE)?;
+    /// let body = PayloadFormat::Discord.body("Lunch", &menus, &menus)?;
+    /// let body = serde_json::from_slice::<serde_json::Value>(&body)?;
+    /// let content = body["content"].as_str().unwrap();
+    /// assert!(content.starts_with("Lunch\n```\nToday\nHovädzí vývar s rezancami (1,3,9)"));
+    /// assert!(content.ends_with("| €6.00\n```"));
+    ///
+    /// let body = PayloadFormat::Json.body("Lunch", &menus, &menus)?;
+    /// assert!(body.starts_with(b"[{"));
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn body<T: serde::Serialize + ?Sized>(self, heading: &str, value: &T, menus: &[Menu]) -> Result<Vec<u8>, serde_json::Error> {
+        let key = match self {
+            PayloadFormat::Json => return serde_json::to_vec(value),
+            PayloadFormat::Slack | PayloadFormat::Mattermost => "text",
+            PayloadFormat::Discord => "content",
+        };
+        let mut table = String::new();
+        TableFormatter::default().format(menus, &mut table).map_err(serde::ser::Error::custom)?;
+        let mut message = format!("{}\n```\n{}```", heading, table);
+        // Slack requires escaping these even in code blocks
+        if self == PayloadFormat::Slack {
+            message = escape_slack(&message);
+        }
+        serde_json::to_vec(&serde_json::json!({ key: message }))
+    }
+}
+
+/// POSTs the menus as JSON array to the URL without signing them.
+///
+/// This creates a new HTTP client for each call, use [`Webhook`] if you post repeatedly.
+pub async fn post(url: &str, menus: &[Menu]) -> Result<Receipt, DeliveryError> {
+    Webhook::new(url).post(menus).await
+}
+
+/// Computes the value of [`SIGNATURE_HEADER`] for given body.
+///
+/// ```
+/// let signature = zomato::sink::webhook::sign(b"Jefe", b"what do ya want for nothing?");
+/// assert_eq!(signature, "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843");
+/// ```
+pub fn sign(secret: &[u8], body: &[u8]) -> String {
+    use std::fmt::Write;
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    let mut signature = String::from("sha256=");
+    for byte in mac.finalize().into_bytes() {
+        write!(signature, "{:02x}", byte).expect("writing to string doesn't fail");
+    }
+    signature
+}
+
+/// Sink POSTing the events as JSON to the URL.
+pub struct Webhook {
+    http: reqwest::Client,
+    url: String,
+    secret: Option<Vec<u8>>,
+    payload_format: PayloadFormat,
+}
+
+impl Webhook {
+    /// Creates webhook posting to given URL.
+    pub fn new(url: impl Into<String>) -> Self {
+        Webhook {
+            http: reqwest::Client::new(),
+            url: url.into(),
+            secret: None,
+            payload_format: PayloadFormat::Json,
+        }
+    }
+
+    /// Signs the bodies using given secret.
+    pub fn secret(mut self, secret: impl AsRef<[u8]>) -> Self {
+        self.secret = Some(secret.as_ref().to_owned());
+        self
+    }
+
+    /// Sends the body in given format, JSON by default.
+    pub fn payload_format(mut self, format: PayloadFormat) -> Self {
+        self.payload_format = format;
+        self
+    }
+
+    /// POSTs the menus as JSON array or as a message headed "Lunch".
+    pub async fn post(&self, menus: &[Menu]) -> Result<Receipt, DeliveryError> {
+        self.post_body("Lunch", menus, menus).await
+    }
+
+    async fn post_body<T: serde::Serialize + ?Sized>(&self, heading: &str, value: &T, menus: &[Menu]) -> Result<Receipt, DeliveryError> {
+        let body = self.payload_format.body(heading, value, menus).map_err(DeliveryError::permanent)?;
+        let mut request = self.http
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json");
+        if let Some(secret) = &self.secret {
+            request = request.header(SIGNATURE_HEADER, sign(secret, &body));
+        }
+        let response = request
+            .body(body)
+            .send()
+            .await
+            .map_err(DeliveryError::retriable)?;
+
+        let status = response.status();
+        if status.is_success() {
+            Ok(Receipt::new())
+        } else if is_retriable(status) {
+            Err(DeliveryError::retriable(format!("webhook returned status {}", status)))
+        } else {
+            Err(DeliveryError::permanent(format!("webhook returned status {}", status)))
+        }
+    }
+}
+
+impl Sink for Webhook {
+    /// POSTs the whole event (including city and restaurant) as JSON object or as a message
+    /// headed by the restaurant.
+    fn deliver<'a>(&'a self, event: &'a MenuEvent) -> SinkFuture<'a> {
+        Box::pin(async move {
+            let heading = format!("Lunch at {}/{}", event.city, event.restaurant);
+            self.post_body(&heading, event, &event.menus).await
+        })
+    }
+}
+
+/// Client errors won't go away by retrying, except for timeouts and rate limiting.
+fn is_retriable(status: StatusCode) -> bool {
+    !status.is_client_error() || status == StatusCode::REQUEST_TIMEOUT || status == StatusCode::TOO_MANY_REQUESTS
+}
@@ -0,0 +1,151 @@
+//! Helpers for testing code using this crate without accessing the network.
+//!
+//! The examples in the documentation of this crate use these helpers so that they can actually
+//! run. You can use them in your own tests in the same way.
+//!
+//! ```
+//! use zomato::testing::{MockTransport, FIXTURE};
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), zomato::Error> {
+//! let transport = std::sync::Arc::new(MockTransport::new().with_page("https://www.zomato.com/bratislava/foo/daily-menu", FIXTURE));
+//! let client = zomato::Client::with_transport(transport.clone());
+//! let menus = client.get_daily_menu("bratislava", "foo").await?;
+//! assert_eq!(menus[0].items.len(), 3);
+//! assert_eq!(transport.requests(), ["https://www.zomato.com/bratislava/foo/daily-menu"]);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use crate::Client;
+use crate::transport::{BytesFuture, Response, Transport, TransportFuture};
+
+pub use zomato_core::testing::FIXTURE;
+
+/// Two pages of search results for restaurants in Bratislava.
+///
+/// The first page contains two restaurants and indicates there are more results, the second one
+/// contains one restaurant.
+pub const SEARCH_FIXTURES: [&str; 2] = [
+    include_str!("../fixtures/search-1.html"),
+    include_str!("../fixtures/search-2.html"),
+];
+
+/// Two pages of reviews of U Zlatého Bažanta.
+///
+/// The first page contains two reviews and indicates there are more, the second one contains one
+/// review.
+pub const REVIEWS_FIXTURES: [&str; 2] = [
+    include_str!("../fixtures/reviews-1.html"),
+    include_str!("../fixtures/reviews-2.html"),
+];
+
+/// Photos of U Zlatého Bažanta, two of them are daily menus.
+pub const PHOTOS_FIXTURE: &str = include_str!("../fixtures/photos.html");
+
+/// Restaurants near the center of Bratislava (48.1439, 17.1097).
+///
+/// U Zlatého Bažanta is about 180 m far, Bistro Pod Hradom about 750 m and Pizzeria Napoli about
+/// 4.2 km.
+pub const NEARBY_FIXTURE: &str = include_str!("../fixtures/nearby.html");
+
+/// Directory of cities in Slovakia (Bratislava and Košice) and Czech Republic (Praha and Brno).
+pub const DIRECTORY_FIXTURE: &str = include_str!("../fixtures/directory.html");
+
+/// Transport serving pre-configured pages.
+///
+/// It also records the URLs of requests so that tests can check which pages were fetched. Requests
+/// for unknown pages result in 404 responses.
+///
+/// Binary data such as images are served from the data added using
+/// [`with_bytes`](Self::with_bytes), or from the pages if there are none for the URL.
+#[derive(Default)]
+pub struct MockTransport {
+    pages: HashMap<String, String>,
+    bytes: HashMap<String, Vec<u8>>,
+    fallback: Option<String>,
+    queued: Mutex<HashMap<String, VecDeque<Response>>>,
+    requests: Mutex<Vec<String>>,
+}
+
+impl MockTransport {
+    /// Creates transport that doesn't serve any pages.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates transport serving the same page for all URLs.
+    pub fn serving(page: impl Into<String>) -> Self {
+        MockTransport {
+            fallback: Some(page.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Adds page served at given URL.
+    pub fn with_page(mut self, url: impl Into<String>, page: impl Into<String>) -> Self {
+        self.pages.insert(url.into(), page.into());
+        self
+    }
+
+    /// Adds binary data served at given URL by [`Transport::get_bytes`].
+    pub fn with_bytes(mut self, url: impl Into<String>, data: impl Into<Vec<u8>>) -> Self {
+        self.bytes.insert(url.into(), data.into());
+        self
+    }
+
+    /// Queues a response served once for given URL before its page.
+    ///
+    /// This allows simulating errors such as rate limiting, multiple responses for the same URL
+    /// are served in the order in which they were queued.
+    pub fn queue_response(mut self, url: impl Into<String>, response: Response) -> Self {
+        self.queued.get_mut().expect("poisoned mutex").entry(url.into()).or_default().push_back(response);
+        self
+    }
+
+    /// Returns URLs of all requests made so far, in order.
+    pub fn requests(&self) -> Vec<String> {
+        self.requests.lock().expect("poisoned mutex").clone()
+    }
+}
+
+impl Transport for MockTransport {
+    fn get<'a>(&'a self, url: &'a str) -> TransportFuture<'a> {
+        self.requests.lock().expect("poisoned mutex").push(url.to_owned());
+        let queued = self.queued.lock().expect("poisoned mutex").get_mut(url).and_then(VecDeque::pop_front);
+        let response = match (queued, self.pages.get(url).or(self.fallback.as_ref())) {
+            (Some(response), _) => response,
+            (None, Some(page)) => Response::new(200, page.clone()),
+            (None, None) => Response::new(404, format!("page {} not found", url)),
+        };
+        Box::pin(async move { Ok(response) })
+    }
+
+    fn get_bytes<'a>(&'a self, url: &'a str, limit: usize) -> BytesFuture<'a> {
+        self.requests.lock().expect("poisoned mutex").push(url.to_owned());
+        let data = self.bytes.get(url).cloned().or_else(|| Some(self.pages.get(url).or(self.fallback.as_ref())?.clone().into_bytes()));
+        let result = match data {
+            Some(data) if data.len() > limit => Err(format!("{} is larger than {} bytes", url, limit).into()),
+            Some(data) => Ok(Response::binary(200, data)),
+            None => Ok(Response::binary(404, format!("page {} not found", url))),
+        };
+        Box::pin(async move { result })
+    }
+}
+
+/// Creates client serving [`FIXTURE`] for all URLs.
+pub fn fixture_client() -> Client {
+    Client::with_transport(MockTransport::serving(FIXTURE))
+}
+
+/// Creates client serving [`SEARCH_FIXTURES`] as results of searching for "lunch" in Bratislava.
+///
+/// [`FIXTURE`] is served for all other URLs so the menus of found restaurants can be fetched.
+pub fn search_client() -> Client {
+    let transport = MockTransport::serving(FIXTURE)
+        .with_page("https://www.zomato.com/bratislava/restaurants?q=lunch&page=1", SEARCH_FIXTURES[0])
+        .with_page("https://www.zomato.com/bratislava/restaurants?q=lunch&page=2", SEARCH_FIXTURES[1]);
+    Client::with_transport(transport)
+}
@@ -0,0 +1,124 @@
+//! Limiting the rate of requests made by the client.
+//!
+//! The throttle is shared by all clones of [`Client`](crate::Client), so the limit applies to
+//! all concurrent tasks using them. Besides the configured rate it also pauses all requests when
+//! the server asks for it using `Retry-After`.
+//!
+//! The clock is only read if there is a limit, so that the client works in browsers where
+//! `Instant::now` panics.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use crate::transport::Response;
+
+/// How many times a request is retried after being rate limited by the server.
+const MAX_RETRIES: u32 = 3;
+
+/// Longest `Retry-After` the client waits for by default.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) const DEFAULT_MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+/// Timers of tokio don't work in browsers, so rate limited requests are not retried there.
+#[cfg(target_arch = "wasm32")]
+pub(crate) const DEFAULT_MAX_RETRY_AFTER: Duration = Duration::ZERO;
+
+#[derive(Copy, Clone)]
+struct Rate {
+    /// Time needed to get a new token.
+    interval: Duration,
+    /// Maximum number of tokens.
+    burst: u32,
+}
+
+struct State {
+    tokens: f64,
+    /// `None` until the first token is taken.
+    updated: Option<Instant>,
+    paused_until: Option<Instant>,
+}
+
+pub(crate) struct Throttle {
+    rate: Option<Rate>,
+    max_retry_after: Duration,
+    state: Mutex<State>,
+}
+
+impl Throttle {
+    /// Creates token bucket allowing `requests_per_minute` on average with bursts of at most
+    /// `burst` requests, unlimited if `None`.
+    pub(crate) fn new(rate: Option<(u32, u32)>, max_retry_after: Duration) -> Self {
+        let rate = rate.map(|(requests_per_minute, burst)| Rate {
+            interval: Duration::from_secs(60) / requests_per_minute,
+            burst: burst.max(1),
+        });
+        Throttle {
+            rate,
+            max_retry_after,
+            state: Mutex::new(State {
+                tokens: rate.map_or(0.0, |rate| rate.burst.into()),
+                updated: None,
+                paused_until: None,
+            }),
+        }
+    }
+
+    /// Waits until a request may be made.
+    pub(crate) async fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut state = self.state.lock().expect("poisoned mutex");
+                if self.rate.is_none() && state.paused_until.is_none() {
+                    return;
+                }
+                state.take_token(self.rate, Instant::now())
+            };
+            match wait {
+                Some(wait) => crate::runtime::sleep(wait).await,
+                None => return,
+            }
+        }
+    }
+
+    /// Returns how long to wait before retrying the response, `None` if it shouldn't be retried.
+    ///
+    /// The wait applies to all requests, so they are paused too.
+    pub(crate) fn retry_delay<B>(&self, response: &Response<B>, retries: u32) -> Option<Duration> {
+        if response.status != 429 || retries >= MAX_RETRIES || self.max_retry_after.is_zero() {
+            return None;
+        }
+        let delay = response.retry_after.filter(|delay| *delay <= self.max_retry_after)?;
+        let until = Instant::now() + delay;
+        let mut state = self.state.lock().expect("poisoned mutex");
+        state.paused_until = Some(state.paused_until.map_or(until, |paused_until| paused_until.max(until)));
+        Some(delay)
+    }
+}
+
+impl Default for Throttle {
+    fn default() -> Self {
+        Throttle::new(None, DEFAULT_MAX_RETRY_AFTER)
+    }
+}
+
+impl State {
+    /// Takes a token if available, otherwise returns how long to wait for one.
+    fn take_token(&mut self, rate: Option<Rate>, now: Instant) -> Option<Duration> {
+        if let Some(paused_until) = self.paused_until {
+            if now < paused_until {
+                return Some(paused_until - now);
+            }
+            self.paused_until = None;
+        }
+
+        let rate = rate?;
+        let elapsed = self.updated.map_or(Duration::ZERO, |updated| now.saturating_duration_since(updated));
+        self.tokens = (self.tokens + elapsed.as_secs_f64() / rate.interval.as_secs_f64()).min(rate.burst.into());
+        self.updated = Some(now);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            None
+        } else {
+            Some(rate.interval.mul_f64(1.0 - self.tokens))
+        }
+    }
+}
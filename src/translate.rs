@@ -0,0 +1,291 @@
+//! Translation of menus.
+//!
+//! This crate doesn't talk to any translation service itself, implement [`Translator`] for the
+//! one you use. Since translation services are slow and often paid while restaurants repeat
+//! their dishes constantly, wrap the translator in [`CachedTranslator`] to translate each dish
+//! only once. The cache can be stored in a file so that it's shared across days.
+//!
+//! ```
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use std::sync::atomic::{AtomicUsize, Ordering};
+//! use zomato::pipeline::Transform;
+//! use zomato::translate::{CachedTranslator, TranslateFuture, TranslateMenus, TranslationCache, Translator};
+//!
+//! /// Pretends to translate by shouting.
+//! struct Shout(AtomicUsize);
+//!
+//! impl Translator for Shout {
+//!     fn translate<'a>(&'a self, texts: &'a [String], _target_language: &'a str) -> TranslateFuture<'a> {
+//!         self.0.fetch_add(texts.len(), Ordering::Relaxed);
+//!         Box::pin(async move { Ok(texts.iter().map(|text| text.to_uppercase()).collect()) })
+//!     }
+//! }
+//!
+//! # let dir = mktemp::Temp::new_dir()?;
+//! # let path = dir.as_path().join("translations.jsonl");
+//! let translator = CachedTranslator::new(Shout(AtomicUsize::new(0)), TranslationCache::open(&path)?);
+//! let transform = TranslateMenus::new(translator, "en");
+//!
+//! let menus = zomato::parse_daily_menu(zomato::testing::FIXTURE)?;
+//! let translated = transform.apply(menus).await?;
+//! assert_eq!(translated[0].items[0].description, "HOVÄDZÍ VÝVAR S REZANCAMI (1,3,9)");
+//!
+//! // The next day the dishes are served again
+//! let menus = zomato::parse_daily_menu(zomato::testing::FIXTURE)?;
+//! transform.apply(menus).await?;
+//! assert_eq!(transform.translator().inner().0.load(Ordering::Relaxed), 3);
+//! assert_eq!(TranslationCache::open(&path)?.len(), 3);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::future::Future;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use serde_derive::{Deserialize, Serialize};
+use crate::pipeline::{Transform, TransformFuture};
+use crate::{Error, Menu};
+
+/// Future returned by [`Translator::translate`].
+pub type TranslateFuture<'a> = Pin<Box<dyn Future<Output = Result<Vec<String>, Error>> + Send + 'a>>;
+
+/// Service translating texts.
+pub trait Translator: Send + Sync {
+    /// Translates the texts into the language with given code (e.g. `en`).
+    ///
+    /// The returned vector must have the same length as `texts`, the translations are matched by
+    /// position.
+    fn translate<'a>(&'a self, texts: &'a [String], target_language: &'a str) -> TranslateFuture<'a>;
+}
+
+impl<T: Translator + ?Sized> Translator for Arc<T> {
+    fn translate<'a>(&'a self, texts: &'a [String], target_language: &'a str) -> TranslateFuture<'a> {
+        (**self).translate(texts, target_language)
+    }
+}
+
+impl<T: Translator + ?Sized> Translator for Box<T> {
+    fn translate<'a>(&'a self, texts: &'a [String], target_language: &'a str) -> TranslateFuture<'a> {
+        (**self).translate(texts, target_language)
+    }
+}
+
+/// Stored translations keyed by normalized text and target language.
+///
+/// The texts are normalized by decoding HTML entities, collapsing whitespace and ignoring case,
+/// so that trivial differences in how the restaurants type their dishes don't cause cache misses.
+///
+/// The cache is either kept in memory or backed by a file into which new translations are
+/// appended as JSON lines.
+pub struct TranslationCache {
+    translations: Mutex<HashMap<(String, String), String>>,
+    file: Option<Mutex<File>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    language: String,
+    text: String,
+    translation: String,
+}
+
+impl TranslationCache {
+    /// Creates empty cache which isn't persisted.
+    pub fn in_memory() -> Self {
+        TranslationCache {
+            translations: Default::default(),
+            file: None,
+        }
+    }
+
+    /// Loads the cache from the file at given path, creating it if it doesn't exist.
+    ///
+    /// New translations are appended to the file.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        Self::open_internal(path.as_ref()).map_err(Error)
+    }
+
+    fn open_internal(path: &Path) -> Result<Self, anyhow::Error> {
+        use anyhow::Context;
+
+        let file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(path)?;
+
+        let mut translations = HashMap::new();
+        for (number, line) in BufReader::new(&file).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry = serde_json::from_str::<Entry>(&line).with_context(|| format!("invalid translation on line {}", number + 1))?;
+            translations.insert((entry.language, entry.text), entry.translation);
+        }
+
+        Ok(TranslationCache {
+            translations: Mutex::new(translations),
+            file: Some(Mutex::new(file)),
+        })
+    }
+
+    /// Returns the number of stored translations.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns `true` if no translations are stored.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the stored translation of the text.
+    pub fn get(&self, text: &str, target_language: &str) -> Option<String> {
+        self.lock().get(&(target_language.to_owned(), key(text))).cloned()
+    }
+
+    /// Stores the translation of the text.
+    pub fn insert(&self, text: &str, target_language: &str, translation: String) -> io::Result<()> {
+        let entry = Entry {
+            language: target_language.to_owned(),
+            text: key(text),
+            translation,
+        };
+        if let Some(file) = &self.file {
+            let mut line = serde_json::to_vec(&entry).map_err(io::Error::other)?;
+            line.push(b'\n');
+            let mut file = file.lock().unwrap_or_else(|error| error.into_inner());
+            // Single write call so that lines aren't interleaved.
+            file.write_all(&line)?;
+            file.flush()?;
+        }
+        self.lock().insert((entry.language, entry.text), entry.translation);
+        Ok(())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<(String, String), String>> {
+        self.translations.lock().unwrap_or_else(|error| error.into_inner())
+    }
+}
+
+fn key(text: &str) -> String {
+    zomato_core::normalize(text).to_lowercase()
+}
+
+/// Translator consulting the cache before calling the inner translator.
+///
+/// Only the texts missing from the cache are passed to the inner translator, in a single call.
+pub struct CachedTranslator<T> {
+    inner: T,
+    cache: TranslationCache,
+}
+
+impl<T: Translator> CachedTranslator<T> {
+    /// Wraps the translator.
+    pub fn new(inner: T, cache: TranslationCache) -> Self {
+        CachedTranslator {
+            inner,
+            cache,
+        }
+    }
+
+    /// Returns the wrapped translator.
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Returns the cache.
+    pub fn cache(&self) -> &TranslationCache {
+        &self.cache
+    }
+
+    async fn translate_internal(&self, texts: &[String], target_language: &str) -> Result<Vec<String>, Error> {
+        let mut translations = texts.iter().map(|text| self.cache.get(text, target_language)).collect::<Vec<_>>();
+        let mut missing = Vec::new();
+        let mut missing_positions = HashMap::<String, Vec<usize>>::new();
+        for (position, text) in texts.iter().enumerate() {
+            if translations[position].is_none() {
+                let positions = missing_positions.entry(key(text)).or_default();
+                // Same dish may appear multiple times, translate it once
+                if positions.is_empty() {
+                    missing.push(text.clone());
+                }
+                positions.push(position);
+            }
+        }
+
+        if !missing.is_empty() {
+            let translated = self.inner.translate(&missing, target_language).await?;
+            if translated.len() != missing.len() {
+                return Err(Error(anyhow::anyhow!("translator returned {} translations for {} texts", translated.len(), missing.len())));
+            }
+            for (text, translation) in missing.iter().zip(translated) {
+                for position in &missing_positions[&key(text)] {
+                    translations[*position] = Some(translation.clone());
+                }
+                self.cache.insert(text, target_language, translation).map_err(|error| Error(error.into()))?;
+            }
+        }
+
+        Ok(translations.into_iter().map(|translation| translation.expect("all translations filled")).collect())
+    }
+}
+
+impl<T: Translator> Translator for CachedTranslator<T> {
+    fn translate<'a>(&'a self, texts: &'a [String], target_language: &'a str) -> TranslateFuture<'a> {
+        Box::pin(self.translate_internal(texts, target_language))
+    }
+}
+
+/// Pipeline stage translating the descriptions of the dishes.
+///
+/// Menu headings are left intact since they are used to compute the dates.
+pub struct TranslateMenus<T> {
+    translator: T,
+    target_language: String,
+}
+
+impl<T: Translator> TranslateMenus<T> {
+    /// Creates transform translating into the language with given code.
+    pub fn new(translator: T, target_language: impl Into<String>) -> Self {
+        TranslateMenus {
+            translator,
+            target_language: target_language.into(),
+        }
+    }
+
+    /// Returns the translator.
+    pub fn translator(&self) -> &T {
+        &self.translator
+    }
+
+    async fn apply_internal(&self, mut menus: Vec<Menu>) -> Result<Vec<Menu>, Error> {
+        let texts = menus
+            .iter()
+            .flat_map(|menu| menu.items.iter().map(|item| item.description.clone()))
+            .collect::<Vec<_>>();
+        if texts.is_empty() {
+            return Ok(menus);
+        }
+
+        let mut translations = self.translator.translate(&texts, &self.target_language).await?.into_iter();
+        for item in menus.iter_mut().flat_map(|menu| menu.items.iter_mut()) {
+            if let Some(translation) = translations.next() {
+                item.description = translation;
+            }
+        }
+        Ok(menus)
+    }
+}
+
+impl<T: Translator> Transform for TranslateMenus<T> {
+    fn apply(&self, menus: Vec<Menu>) -> TransformFuture<'_> {
+        Box::pin(self.apply_internal(menus))
+    }
+}
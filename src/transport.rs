@@ -0,0 +1,580 @@
+//! Abstraction over the way pages are downloaded.
+//!
+//! [`Client`](crate::Client) uses [`HttpTransport`] by default, but you can provide your own
+//! transport to e.g. serve pages from an archive or to use a different HTTP library. See also
+//! [`MockTransport`](crate::testing::MockTransport).
+//!
+//! When compiled for `wasm32`, [`HttpTransport`] uses the `fetch` API of the browser, so the
+//! requests are subject to CORS and usually have to go through a proxy. The browser follows
+//! redirects and picks the proxy itself, there are no connect or read timeouts and the whole page
+//! is always downloaded.
+//!
+//! [`HttpTransport`] is based on `reqwest`, which requires the tokio runtime. Applications using
+//! async-std, smol or other runtimes can disable the default features and enable the `isahc`
+//! feature instead, which makes [`IsahcTransport`] the default transport. If neither is enabled,
+//! the transport has to be provided using [`ClientBuilder::transport`](crate::ClientBuilder::transport).
+//! The blocking [`UreqTransport`] enabled by the `ureq` feature is used only by the
+//! [`blocking`](crate::blocking) API.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(feature = "isahc")]
+mod isahc;
+
+#[cfg(feature = "ureq")]
+mod ureq;
+
+#[cfg(feature = "isahc")]
+pub use self::isahc::IsahcTransport;
+#[cfg(feature = "ureq")]
+pub use self::ureq::UreqTransport;
+
+/// Error returned by transports.
+pub type TransportError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Future returned by [`Transport::get`].
+#[cfg(not(target_arch = "wasm32"))]
+pub type TransportFuture<'a> = Pin<Box<dyn Future<Output = Result<Response, TransportError>> + Send + 'a>>;
+
+/// Future returned by [`Transport::get`].
+///
+/// Futures of the browser's `fetch` are not `Send`, so this isn't either.
+#[cfg(target_arch = "wasm32")]
+pub type TransportFuture<'a> = Pin<Box<dyn Future<Output = Result<Response, TransportError>> + 'a>>;
+
+/// Future returned by [`Transport::get_bytes`].
+#[cfg(not(target_arch = "wasm32"))]
+pub type BytesFuture<'a> = Pin<Box<dyn Future<Output = Result<Response<Vec<u8>>, TransportError>> + Send + 'a>>;
+
+/// Future returned by [`Transport::get_bytes`].
+#[cfg(target_arch = "wasm32")]
+pub type BytesFuture<'a> = Pin<Box<dyn Future<Output = Result<Response<Vec<u8>>, TransportError>> + 'a>>;
+
+/// Downloads pages from Zomato.
+pub trait Transport: Send + Sync {
+    /// Downloads the page at given URL.
+    ///
+    /// Responses with non-success status must be returned as `Ok`, the client handles them.
+    fn get<'a>(&'a self, url: &'a str) -> TransportFuture<'a>;
+
+    /// Downloads the daily menu page at given URL.
+    ///
+    /// The page may end right after the data embedded in it, which is found using
+    /// [`zomato_core::StateScanner`], since the rest isn't needed to parse the menus. The HTTP
+    /// transports stop reading there unless configured otherwise. The default implementation
+    /// downloads the whole page using [`get`](Self::get).
+    fn get_daily_menu_page<'a>(&'a self, url: &'a str) -> TransportFuture<'a> {
+        self.get(url)
+    }
+
+    /// Downloads binary data such as an image at given URL, failing if it's longer than `limit`
+    /// bytes.
+    ///
+    /// Responses with non-success status must be returned as `Ok` like in [`get`](Self::get).
+    /// The default implementation fails, so transports which don't implement it can't be used to
+    /// download images.
+    fn get_bytes<'a>(&'a self, url: &'a str, limit: usize) -> BytesFuture<'a> {
+        let _ = (url, limit);
+        Box::pin(async { Err("the transport doesn't support downloading binary data".into()) })
+    }
+}
+
+/// Response returned by [`Transport`].
+///
+/// The body is `String` for pages and `Vec<u8>` for binary data.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct Response<B = String> {
+    /// HTTP status code.
+    pub status: u16,
+    /// Body of the response.
+    pub body: B,
+    /// How long the server asked to wait before the next request using `Retry-After`.
+    pub retry_after: Option<Duration>,
+}
+
+impl Response {
+    /// Creates response with given status and body.
+    pub fn new(status: u16, body: impl Into<String>) -> Self {
+        Response {
+            status,
+            body: body.into(),
+            retry_after: None,
+        }
+    }
+}
+
+impl Response<Vec<u8>> {
+    /// Creates response with given status and binary body.
+    pub fn binary(status: u16, body: impl Into<Vec<u8>>) -> Self {
+        Response {
+            status,
+            body: body.into(),
+            retry_after: None,
+        }
+    }
+}
+
+impl<B> Response<B> {
+    /// Sets the time the server asked to wait before the next request.
+    pub fn with_retry_after(mut self, retry_after: Duration) -> Self {
+        self.retry_after = Some(retry_after);
+        self
+    }
+
+    /// Returns `true` if the status code indicates success.
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+impl<T: Transport + ?Sized> Transport for Arc<T> {
+    fn get<'a>(&'a self, url: &'a str) -> TransportFuture<'a> {
+        (**self).get(url)
+    }
+
+    fn get_daily_menu_page<'a>(&'a self, url: &'a str) -> TransportFuture<'a> {
+        (**self).get_daily_menu_page(url)
+    }
+
+    fn get_bytes<'a>(&'a self, url: &'a str, limit: usize) -> BytesFuture<'a> {
+        (**self).get_bytes(url, limit)
+    }
+}
+
+impl<T: Transport + ?Sized> Transport for Box<T> {
+    fn get<'a>(&'a self, url: &'a str) -> TransportFuture<'a> {
+        (**self).get(url)
+    }
+
+    fn get_daily_menu_page<'a>(&'a self, url: &'a str) -> TransportFuture<'a> {
+        (**self).get_daily_menu_page(url)
+    }
+
+    fn get_bytes<'a>(&'a self, url: &'a str, limit: usize) -> BytesFuture<'a> {
+        (**self).get_bytes(url, limit)
+    }
+}
+
+/// Creates the error returned when the body is longer than the limit.
+#[cfg_attr(not(any(feature = "reqwest", feature = "isahc", feature = "ureq")), allow(dead_code))]
+fn too_large(url: &str, limit: usize) -> TransportError {
+    format!("{} is larger than {} bytes", url, limit).into()
+}
+
+/// Headers sent by the HTTP transports by default.
+///
+/// I found that zomato server has some problems when some headers are passed, so I copied
+/// everything from Mozilla Firefox.
+pub const DEFAULT_HEADERS: &[(&str, &str)] = &[
+    ("User-Agent", FIREFOX_60_USER_AGENT),
+    ("Accept", "text/html,application/xhtml+xml,application/xml;q=0.9,*/*;q=0.8"),
+    // This seems to be important
+    ("Accept-Encoding", "identity"),
+    // keep-alive must be lower case - not Keep-Alive!
+    ("Connection", "keep-alive"),
+    ("DNT", "1"),
+    ("Upgrade-Insecure-Requests", "1"),
+    ("Cache-Control", "max-age=0"),
+    ("Accept-Language", "en-US,en;q=0.5"),
+];
+
+const FIREFOX_60_USER_AGENT: &str = "Mozilla/5.0 (X11; Fedora; Linux x86_64; rv:60.0) Gecko/20100101 Firefox/60.0";
+
+/// Browser identified by the `User-Agent` header.
+///
+/// Only the `User-Agent` differs between the profiles, the other [`DEFAULT_HEADERS`] are kept
+/// since Zomato accepts them from all browsers. Set it using
+/// [`ClientBuilder::browser_profile`](crate::ClientBuilder::browser_profile) or rotate multiple
+/// profiles using [`ClientBuilder::rotate_browser_profiles`](crate::ClientBuilder::rotate_browser_profiles).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum BrowserProfile {
+    /// Firefox 60 on Linux, used by default.
+    Firefox60,
+    /// Recent Firefox on Linux.
+    ///
+    /// The version is updated in new releases of this crate.
+    Firefox,
+    /// Recent Chrome on Windows.
+    ///
+    /// The version is updated in new releases of this crate.
+    Chrome,
+}
+
+impl BrowserProfile {
+    /// Returns the value of the `User-Agent` header.
+    pub fn user_agent(self) -> &'static str {
+        match self {
+            BrowserProfile::Firefox60 => FIREFOX_60_USER_AGENT,
+            BrowserProfile::Firefox => "Mozilla/5.0 (X11; Linux x86_64; rv:144.0) Gecko/20100101 Firefox/144.0",
+            BrowserProfile::Chrome => "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/141.0.0.0 Safari/537.36",
+        }
+    }
+}
+
+/// Transport sending the requests through the inner transports in turns.
+///
+/// Used to rotate the `User-Agent`, each inner transport sends a different one.
+pub(crate) struct RotatingTransport {
+    transports: Vec<Arc<dyn Transport>>,
+    next: AtomicUsize,
+}
+
+impl RotatingTransport {
+    /// Creates the transport, `transports` must not be empty.
+    pub(crate) fn new(transports: Vec<Arc<dyn Transport>>) -> Self {
+        assert!(!transports.is_empty(), "rotating transport requires at least one transport");
+        RotatingTransport {
+            transports,
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    fn next(&self) -> &dyn Transport {
+        let index = self.next.fetch_add(1, Ordering::Relaxed) % self.transports.len();
+        &*self.transports[index]
+    }
+}
+
+impl Transport for RotatingTransport {
+    fn get<'a>(&'a self, url: &'a str) -> TransportFuture<'a> {
+        self.next().get(url)
+    }
+
+    fn get_daily_menu_page<'a>(&'a self, url: &'a str) -> TransportFuture<'a> {
+        self.next().get_daily_menu_page(url)
+    }
+
+    fn get_bytes<'a>(&'a self, url: &'a str, limit: usize) -> BytesFuture<'a> {
+        self.next().get_bytes(url, limit)
+    }
+}
+
+/// Longest time the HTTP transports wait for a connection to be established by default.
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Longest time the HTTP transports wait for the server to send data by default.
+pub const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Error returned when a request takes too long.
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum TimeoutError {
+    #[error("the server didn't send any data for {0:?}")]
+    #[cfg_attr(any(target_arch = "wasm32", not(any(feature = "reqwest", feature = "isahc"))), allow(dead_code))]
+    Read(Duration),
+    #[error("the request didn't finish within {0:?}")]
+    Total(Duration),
+    #[error("{0} didn't finish before the deadline")]
+    Deadline(crate::RestaurantRef),
+}
+
+/// Options of the HTTP transports configurable through [`ClientBuilder`](crate::ClientBuilder).
+#[derive(Clone, Debug)]
+#[cfg_attr(any(target_arch = "wasm32", not(any(feature = "reqwest", feature = "isahc", feature = "ureq"))), allow(dead_code))]
+pub(crate) struct HttpOptions {
+    /// Redirects are followed only to these hosts if specified.
+    pub(crate) allowed_hosts: Option<Vec<String>>,
+    pub(crate) proxy: Proxy,
+    pub(crate) connect_timeout: Duration,
+    pub(crate) read_timeout: Duration,
+    /// Download the rest of the page after the embedded data.
+    pub(crate) whole_page: bool,
+    #[cfg(feature = "cookies")]
+    pub(crate) cookies: Option<Arc<crate::cookies::CookieJar>>,
+}
+
+impl Default for HttpOptions {
+    fn default() -> Self {
+        HttpOptions {
+            allowed_hosts: None,
+            proxy: Proxy::Environment,
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            read_timeout: DEFAULT_READ_TIMEOUT,
+            whole_page: false,
+            #[cfg(feature = "cookies")]
+            cookies: None,
+        }
+    }
+}
+
+/// Finds the end of the data embedded in the page while it's being downloaded.
+///
+/// Everything the crate parses from the daily menu page is in the script assigning
+/// `__PRELOADED_STATE__`, which is a small part of the page, so the HTTP transports stop reading
+/// it there. The body is scanned incrementally as the chunks arrive.
+#[derive(Default)]
+#[cfg_attr(any(target_arch = "wasm32", not(any(feature = "reqwest", feature = "isahc", feature = "ureq"))), allow(dead_code))]
+pub(crate) struct StateScanner(zomato_core::StateScanner);
+
+#[cfg_attr(any(target_arch = "wasm32", not(any(feature = "reqwest", feature = "isahc", feature = "ureq"))), allow(dead_code))]
+impl StateScanner {
+    /// Returns the length of the body up to the end of the embedded data if it was received.
+    pub(crate) fn end(&mut self, body: &[u8]) -> Option<usize> {
+        // the last chunk may end in the middle of a character
+        let received = match std::str::from_utf8(body) {
+            Ok(received) => received,
+            Err(error) => std::str::from_utf8(&body[..error.valid_up_to()]).expect("valid up to the error"),
+        };
+        self.0.end(received)
+    }
+}
+
+/// Proxy used by the HTTP transports.
+#[derive(Clone, Debug)]
+#[cfg_attr(any(target_arch = "wasm32", not(any(feature = "reqwest", feature = "isahc", feature = "ureq"))), allow(dead_code))]
+pub(crate) enum Proxy {
+    /// Configured by `HTTP_PROXY` and `HTTPS_PROXY` environment variables.
+    Environment,
+    /// Connect directly even if the environment variables are set.
+    Disabled,
+    /// Proxy all requests through given URL.
+    Url(String),
+}
+
+/// Creates the transport used by [`Client`](crate::Client) unless a custom one is provided.
+///
+/// [`HttpTransport`] is preferred if both HTTP clients are enabled.
+#[cfg(feature = "reqwest")]
+pub(crate) fn default_transport<I, N, V>(headers: I, options: HttpOptions) -> Result<Arc<dyn Transport>, anyhow::Error> where I: IntoIterator<Item = (N, V)>, N: AsRef<str>, V: AsRef<str> {
+    Ok(Arc::new(HttpTransport::with_options(headers, options)?))
+}
+
+/// Creates the transport used by [`Client`](crate::Client) unless a custom one is provided.
+#[cfg(all(feature = "isahc", not(feature = "reqwest")))]
+pub(crate) fn default_transport<I, N, V>(headers: I, options: HttpOptions) -> Result<Arc<dyn Transport>, anyhow::Error> where I: IntoIterator<Item = (N, V)>, N: AsRef<str>, V: AsRef<str> {
+    Ok(Arc::new(IsahcTransport::with_options(headers, options)?))
+}
+
+/// Fails because no HTTP client is compiled in.
+#[cfg(not(any(feature = "reqwest", feature = "isahc")))]
+pub(crate) fn default_transport<I, N, V>(_headers: I, _options: HttpOptions) -> Result<Arc<dyn Transport>, anyhow::Error> where I: IntoIterator<Item = (N, V)>, N: AsRef<str>, V: AsRef<str> {
+    anyhow::bail!("no async HTTP client is enabled, enable the `rustls-tls`, `native-tls` or `isahc` feature or provide a transport")
+}
+
+/// Transport downloading the pages over HTTP(S) using `reqwest`.
+///
+/// Available with the `reqwest` feature, which is enabled by the default `rustls-tls` feature
+/// and by `native-tls`. This transport sends [headers](DEFAULT_HEADERS) which are known to work with Zomato unless
+/// configured otherwise. Connecting fails after [`DEFAULT_CONNECT_TIMEOUT`] and the request
+/// fails if the server doesn't send any data for [`DEFAULT_READ_TIMEOUT`].
+///
+/// The download of the daily menu page stops once the data embedded in it is received, the rest
+/// of the page is not needed to parse it. See [`ClientBuilder::whole_page`](crate::ClientBuilder::whole_page).
+#[cfg(feature = "reqwest")]
+#[derive(Clone)]
+pub struct HttpTransport {
+    http: reqwest::Client,
+    headers: reqwest::header::HeaderMap,
+    #[cfg(not(target_arch = "wasm32"))]
+    read_timeout: Duration,
+    #[cfg(not(target_arch = "wasm32"))]
+    whole_page: bool,
+}
+
+#[cfg(feature = "reqwest")]
+impl HttpTransport {
+    /// Creates HTTP transport with default settings.
+    pub fn new() -> Result<Self, crate::Error> {
+        Self::with_headers(DEFAULT_HEADERS.iter().copied())
+    }
+
+    /// Creates HTTP transport sending given headers instead of the default ones.
+    pub fn with_headers<I, N, V>(headers: I) -> Result<Self, crate::Error> where I: IntoIterator<Item = (N, V)>, N: AsRef<str>, V: AsRef<str> {
+        Self::with_options(headers, HttpOptions::default()).map_err(crate::Error)
+    }
+
+    /// Creates transport with given options.
+    pub(crate) fn with_options<I, N, V>(headers: I, options: HttpOptions) -> Result<Self, anyhow::Error> where I: IntoIterator<Item = (N, V)>, N: AsRef<str>, V: AsRef<str> {
+        use reqwest::header::{HeaderName, HeaderValue};
+
+        let mut header_map = reqwest::header::HeaderMap::new();
+        for (name, value) in headers {
+            let name = HeaderName::from_bytes(name.as_ref().as_bytes())?;
+            header_map.insert(name, HeaderValue::from_str(value.as_ref())?);
+        }
+
+        Ok(HttpTransport {
+            http: Self::http_client(&options)?,
+            headers: header_map,
+            #[cfg(not(target_arch = "wasm32"))]
+            read_timeout: options.read_timeout,
+            #[cfg(not(target_arch = "wasm32"))]
+            whole_page: options.whole_page,
+        })
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn http_client(_options: &HttpOptions) -> Result<reqwest::Client, anyhow::Error> {
+        Ok(reqwest::Client::builder().build()?)
+    }
+
+    /// Creates client which follows redirects only to the allowed hosts if specified.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn http_client(options: &HttpOptions) -> Result<reqwest::Client, anyhow::Error> {
+        use anyhow::Context;
+        use reqwest::redirect::Policy;
+
+        /// Same as the default of reqwest.
+        const MAX_REDIRECTS: usize = 10;
+
+        #[cfg(feature = "debug-log")]
+        let verbose = true;
+        #[cfg(not(feature = "debug-log"))]
+        let verbose = false;
+        let redirect = match options.allowed_hosts.clone() {
+            Some(allowed_hosts) => Policy::custom(move |attempt| {
+                if attempt.previous().len() >= MAX_REDIRECTS {
+                    return attempt.error("too many redirects");
+                }
+                match crate::policy::check_url(attempt.url().as_str(), &allowed_hosts) {
+                    Ok(()) => attempt.follow(),
+                    Err(error) => attempt.error(error),
+                }
+            }),
+            None => Policy::default(),
+        };
+        let mut builder = reqwest::Client::builder()
+            .connection_verbose(verbose)
+            .connect_timeout(options.connect_timeout)
+            .redirect(redirect);
+        match &options.proxy {
+            // reqwest reads the environment by default
+            Proxy::Environment => (),
+            Proxy::Disabled => builder = builder.no_proxy(),
+            Proxy::Url(url) => {
+                let proxy = reqwest::Proxy::all(url).context("invalid proxy URL")?;
+                builder = builder.proxy(proxy);
+            },
+        }
+        #[cfg(feature = "cookies")]
+        if let Some(cookies) = &options.cookies {
+            let cookies = crate::cookies::ScopedCookieJar::new(Arc::clone(cookies), options.allowed_hosts.clone());
+            builder = builder.cookie_provider(Arc::new(cookies));
+        }
+        Ok(builder.build()?)
+    }
+
+    /// Downloads the page, stopping after the embedded data if `truncate` is set.
+    async fn get_internal(&self, url: &str, truncate: bool) -> Result<Response, TransportError> {
+        let (response, status, retry_after) = self.send(url).await?;
+        let body = self.read_body(response, truncate).await?;
+        let body_decoded = String::from_utf8(body)?;
+        Ok(Response {
+            retry_after,
+            ..Response::new(status, body_decoded)
+        })
+    }
+
+    async fn get_bytes_internal(&self, url: &str, limit: usize) -> Result<Response<Vec<u8>>, TransportError> {
+        let (response, status, retry_after) = self.send(url).await?;
+        let body = self.read_limited(response, url, limit).await?;
+        Ok(Response {
+            retry_after,
+            ..Response::binary(status, body)
+        })
+    }
+
+    /// Sends the request, returning the response with its status and `Retry-After`.
+    async fn send(&self, url: &str) -> Result<(reqwest::Response, u16, Option<Duration>), TransportError> {
+        let req_builder = self.http
+            .request(reqwest::Method::GET, url)
+            .headers(self.headers.clone());
+
+        let response = self.read(req_builder.send()).await??;
+        let status = response.status().as_u16();
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(parse_retry_after);
+        Ok((response, status, retry_after))
+    }
+
+    /// Reads the whole body, failing if it's longer than the limit.
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn read_limited(&self, mut response: reqwest::Response, url: &str, limit: usize) -> Result<Vec<u8>, TransportError> {
+        let mut body = Vec::new();
+        while let Some(chunk) = self.read(response.chunk()).await?? {
+            if body.len() + chunk.len() > limit {
+                return Err(too_large(url, limit));
+            }
+            body.extend_from_slice(&chunk);
+        }
+        Ok(body)
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    async fn read_limited(&self, response: reqwest::Response, url: &str, limit: usize) -> Result<Vec<u8>, TransportError> {
+        let body = response.bytes().await?;
+        if body.len() > limit {
+            return Err(too_large(url, limit));
+        }
+        Ok(body.to_vec())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn read_body(&self, mut response: reqwest::Response, truncate: bool) -> Result<Vec<u8>, TransportError> {
+        let mut body = Vec::new();
+        let mut scanner = (truncate && !self.whole_page).then(StateScanner::default);
+        while let Some(chunk) = self.read(response.chunk()).await?? {
+            body.extend_from_slice(&chunk);
+            if let Some(end) = scanner.as_mut().and_then(|scanner| scanner.end(&body)) {
+                body.truncate(end);
+                break;
+            }
+        }
+        Ok(body)
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    async fn read_body(&self, response: reqwest::Response, _truncate: bool) -> Result<Vec<u8>, TransportError> {
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    /// Waits for the future receiving data from the server, failing after the read timeout.
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn read<F: Future>(&self, future: F) -> Result<F::Output, TimeoutError> {
+        crate::runtime::timeout(self.read_timeout, future)
+            .await
+            .map_err(|_| TimeoutError::Read(self.read_timeout))
+    }
+
+    /// The browser doesn't support timers of tokio, so there's no read timeout.
+    #[cfg(target_arch = "wasm32")]
+    async fn read<F: Future>(&self, future: F) -> Result<F::Output, TimeoutError> {
+        Ok(future.await)
+    }
+}
+
+#[cfg(feature = "reqwest")]
+impl Transport for HttpTransport {
+    fn get<'a>(&'a self, url: &'a str) -> TransportFuture<'a> {
+        Box::pin(self.get_internal(url, false))
+    }
+
+    fn get_daily_menu_page<'a>(&'a self, url: &'a str) -> TransportFuture<'a> {
+        Box::pin(self.get_internal(url, true))
+    }
+
+    fn get_bytes<'a>(&'a self, url: &'a str, limit: usize) -> BytesFuture<'a> {
+        Box::pin(self.get_bytes_internal(url, limit))
+    }
+}
+
+/// Parses the value of `Retry-After` header, which is either number of seconds or HTTP date.
+///
+/// Dates in the past result in zero duration.
+#[cfg(any(feature = "reqwest", feature = "isahc", feature = "ureq"))]
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    Some((date.with_timezone(&chrono::Utc) - chrono::Utc::now()).to_std().unwrap_or_default())
+}
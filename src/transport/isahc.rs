@@ -0,0 +1,210 @@
+//! Transport based on `isahc`, which doesn't depend on any async runtime.
+
+use std::future::Future;
+use std::time::Duration;
+use futures_util::AsyncReadExt;
+use isahc::config::{Configurable, RedirectPolicy};
+use isahc::http::header::{LOCATION, RETRY_AFTER};
+use isahc::{AsyncBody, HttpClient, Request};
+use super::{BytesFuture, HttpOptions, Proxy, Response, StateScanner, TimeoutError, Transport, TransportError, TransportFuture, DEFAULT_HEADERS};
+
+/// Same as the default of reqwest.
+const MAX_REDIRECTS: usize = 10;
+
+/// Transport downloading the pages over HTTP(S) using `isahc`.
+///
+/// Available with the `isahc` feature. Unlike [`HttpTransport`](super::HttpTransport) it
+/// doesn't require tokio: `libcurl` runs the requests in a background thread and the futures
+/// can be polled by async-std, smol or any other executor. TLS is provided by `libcurl`, so the
+/// `rustls-tls` and `native-tls` features don't affect it.
+///
+/// The transport sends the same [headers](DEFAULT_HEADERS) and uses the same
+/// [connect](super::DEFAULT_CONNECT_TIMEOUT) and [read](super::DEFAULT_READ_TIMEOUT) timeouts
+/// as [`HttpTransport`](super::HttpTransport) and also stops downloading the daily menu page once
+/// the data embedded in it is received.
+///
+/// ```
+/// # use std::io::{Read, Write};
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use zomato::transport::IsahcTransport;
+///
+/// # let server = std::net::TcpListener::bind("127.0.0.1:0")?;
+/// # let base_url = format!("http://{}", server.local_addr()?);
+/// # std::thread::spawn(move || for stream in server.incoming() {
+/// #     let mut stream = stream.unwrap();
+/// #     let _ = stream.read(&mut [0; 4096]);
+/// #     let header = format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n", zomato::testing::FIXTURE.len());
+/// #     let _ = stream.write_all(header.as_bytes()).and_then(|()| stream.write_all(zomato::testing::FIXTURE.as_bytes()));
+/// # });
+/// let client = zomato::Client::builder()
+///     .base_url(base_url)
+///     .transport(IsahcTransport::new()?)
+///     .build()?;
+/// // no tokio runtime is needed
+/// let menus = futures_executor::block_on(client.get_daily_menu("bratislava", "u-zlateho-bazanta"))?;
+/// assert_eq!(menus[0].date, "Today");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct IsahcTransport {
+    http: HttpClient,
+    allowed_hosts: Option<Vec<String>>,
+    read_timeout: Duration,
+    whole_page: bool,
+    #[cfg(feature = "cookies")]
+    cookies: Option<crate::cookies::ScopedCookieJar>,
+}
+
+impl IsahcTransport {
+    /// Creates HTTP transport with default settings.
+    pub fn new() -> Result<Self, crate::Error> {
+        Self::with_headers(DEFAULT_HEADERS.iter().copied())
+    }
+
+    /// Creates HTTP transport sending given headers instead of the default ones.
+    pub fn with_headers<I, N, V>(headers: I) -> Result<Self, crate::Error> where I: IntoIterator<Item = (N, V)>, N: AsRef<str>, V: AsRef<str> {
+        Self::with_options(headers, HttpOptions::default()).map_err(crate::Error)
+    }
+
+    /// Creates transport with given options.
+    pub(crate) fn with_options<I, N, V>(headers: I, options: HttpOptions) -> Result<Self, anyhow::Error> where I: IntoIterator<Item = (N, V)>, N: AsRef<str>, V: AsRef<str> {
+        use anyhow::Context;
+
+        // redirects are followed manually, so that their targets can be checked
+        let mut builder = HttpClient::builder()
+            .redirect_policy(RedirectPolicy::None)
+            .connect_timeout(options.connect_timeout);
+        for (name, value) in headers {
+            builder = builder.default_header(name.as_ref(), value.as_ref());
+        }
+        match &options.proxy {
+            // libcurl reads the environment by default
+            Proxy::Environment => (),
+            Proxy::Disabled => builder = builder.proxy(None),
+            Proxy::Url(url) => builder = builder.proxy(Some(url.parse().context("invalid proxy URL")?)),
+        }
+
+        #[cfg(feature = "cookies")]
+        let cookies = options.cookies.clone().map(|cookies| crate::cookies::ScopedCookieJar::new(cookies, options.allowed_hosts.clone()));
+        Ok(IsahcTransport {
+            http: builder.build()?,
+            allowed_hosts: options.allowed_hosts,
+            read_timeout: options.read_timeout,
+            whole_page: options.whole_page,
+            #[cfg(feature = "cookies")]
+            cookies,
+        })
+    }
+
+    /// Downloads the page, stopping after the embedded data if `truncate` is set.
+    async fn get_internal(&self, url: &str, truncate: bool) -> Result<Response, TransportError> {
+        let (mut response, status, retry_after) = self.send(url).await?;
+        let body = self.read_body(response.body_mut(), truncate).await?;
+        Ok(Response {
+            retry_after,
+            ..Response::new(status, String::from_utf8(body)?)
+        })
+    }
+
+    async fn get_bytes_internal(&self, url: &str, limit: usize) -> Result<Response<Vec<u8>>, TransportError> {
+        let (mut response, status, retry_after) = self.send(url).await?;
+        let mut body = Vec::new();
+        let mut buf = [0; 8192];
+        loop {
+            let len = self.read(response.body_mut().read(&mut buf)).await??;
+            if len == 0 {
+                break;
+            }
+            if body.len() + len > limit {
+                return Err(super::too_large(url, limit));
+            }
+            body.extend_from_slice(&buf[..len]);
+        }
+        Ok(Response {
+            retry_after,
+            ..Response::binary(status, body)
+        })
+    }
+
+    /// Sends the request following the redirects, returning the final response with its status
+    /// and `Retry-After`.
+    async fn send(&self, url: &str) -> Result<(isahc::Response<AsyncBody>, u16, Option<Duration>), TransportError> {
+        let mut url = url.to_owned();
+        let mut redirects = 0;
+        loop {
+            let request = Request::get(&url);
+            #[cfg(feature = "cookies")]
+            let request = match self.cookies.as_ref().and_then(|cookies| cookies.header(&url::Url::parse(&url).ok()?)) {
+                Some(cookie) => request.header(isahc::http::header::COOKIE, cookie),
+                None => request,
+            };
+            let response = self.read(self.http.send_async(request.body(())?)).await??;
+            #[cfg(feature = "cookies")]
+            if let (Some(cookies), Ok(parsed_url)) = (&self.cookies, url::Url::parse(&url)) {
+                let set_cookies = response.headers().get_all(isahc::http::header::SET_COOKIE);
+                cookies.store(&parsed_url, set_cookies.iter().filter_map(|value| value.to_str().ok()));
+            }
+            let location = response.headers().get(LOCATION).and_then(|value| value.to_str().ok());
+            match location {
+                Some(location) if response.status().is_redirection() => {
+                    if redirects >= MAX_REDIRECTS {
+                        return Err("too many redirects".into());
+                    }
+                    redirects += 1;
+                    url = url::Url::parse(&url)?.join(location)?.into();
+                    if let Some(allowed_hosts) = &self.allowed_hosts {
+                        crate::policy::check_url(&url, allowed_hosts)?;
+                    }
+                },
+                _ => {
+                    let status = response.status().as_u16();
+                    let retry_after = response
+                        .headers()
+                        .get(RETRY_AFTER)
+                        .and_then(|value| value.to_str().ok())
+                        .and_then(super::parse_retry_after);
+                    return Ok((response, status, retry_after));
+                },
+            }
+        }
+    }
+
+    async fn read_body(&self, body: &mut AsyncBody, truncate: bool) -> Result<Vec<u8>, TransportError> {
+        let mut result = Vec::new();
+        let mut buf = [0; 8192];
+        let mut scanner = (truncate && !self.whole_page).then(StateScanner::default);
+        loop {
+            let len = self.read(body.read(&mut buf)).await??;
+            if len == 0 {
+                return Ok(result);
+            }
+            result.extend_from_slice(&buf[..len]);
+            if let Some(end) = scanner.as_mut().and_then(|scanner| scanner.end(&result)) {
+                result.truncate(end);
+                return Ok(result);
+            }
+        }
+    }
+
+    /// Waits for the future receiving data from the server, failing after the read timeout.
+    async fn read<F: Future>(&self, future: F) -> Result<F::Output, TimeoutError> {
+        crate::runtime::timeout(self.read_timeout, future)
+            .await
+            .map_err(|_| TimeoutError::Read(self.read_timeout))
+    }
+}
+
+impl Transport for IsahcTransport {
+    fn get<'a>(&'a self, url: &'a str) -> TransportFuture<'a> {
+        Box::pin(self.get_internal(url, false))
+    }
+
+    fn get_daily_menu_page<'a>(&'a self, url: &'a str) -> TransportFuture<'a> {
+        Box::pin(self.get_internal(url, true))
+    }
+
+    fn get_bytes<'a>(&'a self, url: &'a str, limit: usize) -> BytesFuture<'a> {
+        Box::pin(self.get_bytes_internal(url, limit))
+    }
+}
@@ -0,0 +1,201 @@
+//! Blocking transport based on `ureq`.
+
+use std::io::Read;
+use super::{BytesFuture, HttpOptions, Proxy, Response, StateScanner, Transport, TransportError, TransportFuture, DEFAULT_HEADERS};
+
+/// Same as the default of reqwest.
+const MAX_REDIRECTS: usize = 10;
+
+/// Transport downloading the pages over HTTP(S) using `ureq`.
+///
+/// Available with the `ureq` feature, which doesn't need any async runtime and has much smaller
+/// dependency tree than [`HttpTransport`](super::HttpTransport), so it suits programs fetching a
+/// menu once, e.g. from cron. HTTPS uses rustls.
+///
+/// The request is made synchronously when the future is polled, blocking the thread, so this
+/// transport should be used only through the [`blocking`](crate::blocking) API and never inside
+/// an async runtime. It sends the same [headers](DEFAULT_HEADERS) and uses the same
+/// [connect](super::DEFAULT_CONNECT_TIMEOUT) and [read](super::DEFAULT_READ_TIMEOUT) timeouts
+/// as the other HTTP transports and also stops downloading the daily menu page once the data
+/// embedded in it is received.
+///
+/// ```
+/// # use std::io::{Read, Write};
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # let server = std::net::TcpListener::bind("127.0.0.1:0")?;
+/// # let base_url = format!("http://{}", server.local_addr()?);
+/// # std::thread::spawn(move || for stream in server.incoming() {
+/// #     let mut stream = stream.unwrap();
+/// #     let _ = stream.read(&mut [0; 4096]);
+/// #     let header = format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n", zomato::testing::FIXTURE.len());
+/// #     let _ = stream.write_all(header.as_bytes()).and_then(|()| stream.write_all(zomato::testing::FIXTURE.as_bytes()));
+/// # });
+/// // uses UreqTransport
+/// let client = zomato::Client::builder().base_url(base_url).build_blocking()?;
+/// let menus = client.get_daily_menu("bratislava", "u-zlateho-bazanta")?;
+/// assert_eq!(menus[0].date, "Today");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct UreqTransport {
+    agent: ureq::Agent,
+    headers: Vec<(String, String)>,
+    allowed_hosts: Option<Vec<String>>,
+    whole_page: bool,
+    #[cfg(feature = "cookies")]
+    cookies: Option<crate::cookies::ScopedCookieJar>,
+}
+
+impl UreqTransport {
+    /// Creates HTTP transport with default settings.
+    pub fn new() -> Result<Self, crate::Error> {
+        Self::with_headers(DEFAULT_HEADERS.iter().copied())
+    }
+
+    /// Creates HTTP transport sending given headers instead of the default ones.
+    pub fn with_headers<I, N, V>(headers: I) -> Result<Self, crate::Error> where I: IntoIterator<Item = (N, V)>, N: AsRef<str>, V: AsRef<str> {
+        Self::with_options(headers, HttpOptions::default()).map_err(crate::Error)
+    }
+
+    /// Creates transport with given options.
+    pub(crate) fn with_options<I, N, V>(headers: I, options: HttpOptions) -> Result<Self, anyhow::Error> where I: IntoIterator<Item = (N, V)>, N: AsRef<str>, V: AsRef<str> {
+        use anyhow::Context;
+
+        let headers = headers
+            .into_iter()
+            .map(|(name, value)| check_header(name.as_ref(), value.as_ref()))
+            .collect::<Result<_, _>>()?;
+        // redirects are followed manually, so that their targets can be checked
+        let mut builder = ureq::AgentBuilder::new()
+            .redirects(0)
+            .timeout_connect(options.connect_timeout)
+            .timeout_read(options.read_timeout);
+        match &options.proxy {
+            Proxy::Environment => builder = builder.try_proxy_from_env(true),
+            Proxy::Disabled => (),
+            Proxy::Url(url) => builder = builder.proxy(ureq::Proxy::new(url).context("invalid proxy URL")?),
+        }
+
+        #[cfg(feature = "cookies")]
+        let cookies = options.cookies.clone().map(|cookies| crate::cookies::ScopedCookieJar::new(cookies, options.allowed_hosts.clone()));
+        Ok(UreqTransport {
+            agent: builder.build(),
+            headers,
+            allowed_hosts: options.allowed_hosts,
+            whole_page: options.whole_page,
+            #[cfg(feature = "cookies")]
+            cookies,
+        })
+    }
+
+    /// Downloads the page, stopping after the embedded data if `truncate` is set.
+    fn get_blocking(&self, url: &str, truncate: bool) -> Result<Response, TransportError> {
+        let response = self.send(url)?;
+        let status = response.status();
+        let retry_after = response.header("Retry-After").and_then(super::parse_retry_after);
+        let body = self.read_body(response.into_reader(), truncate)?;
+        Ok(Response {
+            retry_after,
+            ..Response::new(status, String::from_utf8(body)?)
+        })
+    }
+
+    fn get_bytes_blocking(&self, url: &str, limit: usize) -> Result<Response<Vec<u8>>, TransportError> {
+        let response = self.send(url)?;
+        let status = response.status();
+        let retry_after = response.header("Retry-After").and_then(super::parse_retry_after);
+        let mut body = Vec::new();
+        // One more byte to find out whether the limit was exceeded
+        response.into_reader().take(limit as u64 + 1).read_to_end(&mut body)?;
+        if body.len() > limit {
+            return Err(super::too_large(url, limit));
+        }
+        Ok(Response {
+            retry_after,
+            ..Response::binary(status, body)
+        })
+    }
+
+    /// Sends the request following the redirects, returning the final response.
+    fn send(&self, url: &str) -> Result<ureq::Response, TransportError> {
+        let mut url = url.to_owned();
+        let mut redirects = 0;
+        loop {
+            let request = self.headers.iter().fold(self.agent.get(&url), |request, (name, value)| request.set(name, value));
+            #[cfg(feature = "cookies")]
+            let request = match self.cookies.as_ref().and_then(|cookies| cookies.header(&url::Url::parse(&url).ok()?)) {
+                Some(cookie) => request.set("Cookie", &cookie),
+                None => request,
+            };
+            let response = match request.call() {
+                Ok(response) => response,
+                // non-success responses are handled by the client
+                Err(ureq::Error::Status(_, response)) => response,
+                Err(error) => return Err(error.into()),
+            };
+            #[cfg(feature = "cookies")]
+            if let (Some(cookies), Ok(parsed_url)) = (&self.cookies, url::Url::parse(&url)) {
+                cookies.store(&parsed_url, response.all("set-cookie"));
+            }
+            match response.header("Location") {
+                Some(location) if (300..400).contains(&response.status()) => {
+                    if redirects >= MAX_REDIRECTS {
+                        return Err("too many redirects".into());
+                    }
+                    redirects += 1;
+                    url = url::Url::parse(&url)?.join(location)?.into();
+                    if let Some(allowed_hosts) = &self.allowed_hosts {
+                        crate::policy::check_url(&url, allowed_hosts)?;
+                    }
+                },
+                _ => return Ok(response),
+            }
+        }
+    }
+
+    fn read_body(&self, mut reader: impl Read, truncate: bool) -> Result<Vec<u8>, std::io::Error> {
+        let mut body = Vec::new();
+        let mut buf = [0; 8192];
+        let mut scanner = (truncate && !self.whole_page).then(StateScanner::default);
+        loop {
+            let len = match reader.read(&mut buf) {
+                Ok(0) => return Ok(body),
+                Ok(len) => len,
+                Err(error) if error.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error),
+            };
+            body.extend_from_slice(&buf[..len]);
+            if let Some(end) = scanner.as_mut().and_then(|scanner| scanner.end(&body)) {
+                body.truncate(end);
+                return Ok(body);
+            }
+        }
+    }
+}
+
+/// Checks the header upfront, since `ureq` would report invalid headers only when requesting.
+fn check_header(name: &str, value: &str) -> Result<(String, String), anyhow::Error> {
+    let valid_name = !name.is_empty() && name.bytes().all(|byte| byte.is_ascii_graphic() && byte != b':');
+    if !valid_name {
+        anyhow::bail!("invalid header name '{}'", name);
+    }
+    if value.contains(['\r', '\n', '\0']) {
+        anyhow::bail!("invalid value of header '{}'", name);
+    }
+    Ok((name.to_owned(), value.to_owned()))
+}
+
+impl Transport for UreqTransport {
+    fn get<'a>(&'a self, url: &'a str) -> TransportFuture<'a> {
+        Box::pin(async move { self.get_blocking(url, false) })
+    }
+
+    fn get_daily_menu_page<'a>(&'a self, url: &'a str) -> TransportFuture<'a> {
+        Box::pin(async move { self.get_blocking(url, true) })
+    }
+
+    fn get_bytes<'a>(&'a self, url: &'a str, limit: usize) -> BytesFuture<'a> {
+        Box::pin(async move { self.get_bytes_blocking(url, limit) })
+    }
+}
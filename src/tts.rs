@@ -0,0 +1,479 @@
+//! Reading menus aloud using text-to-speech engines.
+//!
+//! [`SpeechTemplate`] turns a menu into text suitable for speaking and [`TtsEngine`]s speak it
+//! using one of the commonly installed programs: [`Festival`], [`Espeak`] or [`Pico2Wave`]. The
+//! programs are not bundled, they have to be installed separately. [`Briefing`] reads the menus
+//! of several restaurants one after another, allowing the listener to skip them.
+//!
+//! ```no_run
+//! use zomato::tts::{Espeak, SpeechTemplate, TtsEngine};
+//!
+//! let menus = zomato::parse_daily_menu(zomato::testing::FIXTURE)?;
+//! let text = SpeechTemplate::default().render_to_string("U Zlatého Bažanta", &menus[0]);
+//! Espeak { language: Some("sk".to_owned()), ..Default::default() }.speak(&text, &Default::default())?;
+//! # Ok::<_, Box<dyn std::error::Error>>(())
+//! ```
+
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use futures_core::Stream;
+use futures_util::future::{select, Either};
+use futures_util::{FutureExt, SinkExt, StreamExt};
+use crate::reservation::{NoReservations, ReservationProvider};
+use crate::{BatchFailure, Client, Error, Menu};
+
+/// Program turning text into speech.
+pub trait TtsEngine {
+    /// Speaks the text, stopping early if `interrupt` gets set.
+    ///
+    /// This blocks until the speech ends.
+    fn speak(&self, text: &str, interrupt: &AtomicBool) -> io::Result<()>;
+
+    /// Writes the speech into a WAV file instead of playing it.
+    fn record_wav(&self, text: &str, path: &Path) -> io::Result<()>;
+}
+
+/// Writes the speech into a file, converting it to OGG if the file has `.ogg` extension.
+///
+/// The conversion requires `oggenc` to be installed.
+pub fn record(engine: &dyn TtsEngine, text: &str, path: &Path) -> io::Result<()> {
+    use mktemp::Temp;
+
+    if path.extension() != Some("ogg".as_ref()) {
+        return engine.record_wav(text, path);
+    }
+
+    let tmp_dir = Temp::new_dir()?;
+    let tmp_file = tmp_dir.join("message.wav");
+    engine.record_wav(text, &tmp_file)?;
+
+    let status = Command::new("oggenc")
+        .arg("-Q")
+        .arg("-o")
+        .arg(path)
+        .arg(&tmp_file)
+        .status()?;
+    if !status.success() {
+        return Err(io::Error::other(format!("oggenc failed: {}", status)));
+    }
+    Ok(())
+}
+
+/// Waits for the child to exit, killing it if `interrupt` gets set.
+fn wait(child: &mut Child, interrupt: &AtomicBool) -> io::Result<()> {
+    while child.try_wait()?.is_none() {
+        if interrupt.load(Ordering::Relaxed) {
+            child.kill()?;
+            child.wait()?;
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    Ok(())
+}
+
+/// Spawns the command and writes the text into its stdin.
+fn pipe_text(mut cmd: Command, text: &str, interrupt: &AtomicBool) -> io::Result<()> {
+    use io::Write;
+
+    cmd.stdin(Stdio::piped());
+    let mut child = cmd.spawn()?;
+    let mut stdin = child.stdin.take().expect("bug in std");
+    stdin.write_all(text.as_bytes())?;
+    // close
+    std::mem::drop(stdin);
+    wait(&mut child, interrupt)
+}
+
+/// The Festival speech synthesis system.
+#[derive(Clone, Debug, Default)]
+pub struct Festival {
+    /// Language passed to `--language`, e.g. `english`.
+    pub language: Option<String>,
+}
+
+impl TtsEngine for Festival {
+    fn speak(&self, text: &str, interrupt: &AtomicBool) -> io::Result<()> {
+        let mut cmd = Command::new("festival");
+        if let Some(language) = &self.language {
+            cmd.arg("--language");
+            cmd.arg(language);
+        }
+        cmd.arg("--tts");
+        pipe_text(cmd, text, interrupt)
+    }
+
+    fn record_wav(&self, text: &str, path: &Path) -> io::Result<()> {
+        // festival itself can't write files, it ships text2wave for that
+        let mut cmd = Command::new("text2wave");
+        if let Some(language) = &self.language {
+            cmd.arg("-eval");
+            cmd.arg(format!("(language_{})", language));
+        }
+        cmd.arg("-o");
+        cmd.arg(path);
+        pipe_text(cmd, text, &AtomicBool::new(false))
+    }
+}
+
+/// The eSpeak speech synthesizer.
+#[derive(Clone, Debug, Default)]
+pub struct Espeak {
+    /// Voice passed to `-v`, e.g. `sk`.
+    pub language: Option<String>,
+    /// Words per minute passed to `-s`.
+    pub speed: Option<String>,
+}
+
+impl Espeak {
+    fn command(&self) -> Command {
+        let mut cmd = Command::new("espeak");
+        if let Some(language) = &self.language {
+            cmd.arg("-v");
+            cmd.arg(language);
+        }
+        if let Some(speed) = &self.speed {
+            cmd.arg("-s");
+            cmd.arg(speed);
+        }
+        cmd
+    }
+}
+
+impl TtsEngine for Espeak {
+    fn speak(&self, text: &str, interrupt: &AtomicBool) -> io::Result<()> {
+        let mut cmd = self.command();
+        cmd.arg("--stdin");
+        pipe_text(cmd, text, interrupt)
+    }
+
+    fn record_wav(&self, text: &str, path: &Path) -> io::Result<()> {
+        let mut cmd = self.command();
+        cmd.arg("-w");
+        cmd.arg(path);
+        cmd.arg("--stdin");
+        pipe_text(cmd, text, &AtomicBool::new(false))
+    }
+}
+
+/// SVOX Pico, the speech is played using `aplay`.
+#[derive(Clone, Debug, Default)]
+pub struct Pico2Wave {
+    /// Language passed to `-l`, e.g. `en-GB`.
+    pub language: Option<String>,
+}
+
+impl TtsEngine for Pico2Wave {
+    fn speak(&self, text: &str, interrupt: &AtomicBool) -> io::Result<()> {
+        use mktemp::Temp;
+
+        let tmp_dir = Temp::new_dir()?;
+        let tmp_file = tmp_dir.join("message.wav");
+
+        self.record_wav(text, &tmp_file)?;
+        let mut child = Command::new("aplay").arg(&tmp_file).spawn()?;
+        wait(&mut child, interrupt)
+    }
+
+    fn record_wav(&self, text: &str, path: &Path) -> io::Result<()> {
+        let mut cmd = Command::new("pico2wave");
+        if let Some(language) = &self.language {
+            cmd.arg("-l");
+            cmd.arg(language);
+        }
+        cmd.arg("-w");
+        cmd.arg(path);
+        cmd.arg(text);
+        let status = cmd.status()?;
+        if !status.success() {
+            return Err(io::Error::other(format!("pico2wave failed: {}", status)));
+        }
+        Ok(())
+    }
+}
+
+/// Controls what is being spoken.
+///
+/// Templates may contain `{restaurant}`, `{date}`, `{dish}` and `{price}` placeholders, which
+/// are replaced with the respective values. Unknown placeholders are left as-is.
+///
+/// ```
+/// use zomato::tts::SpeechTemplate;
+///
+/// let menus = zomato::parse_daily_menu(zomato::testing::FIXTURE)?;
+/// let template = SpeechTemplate {
+///     intro: Some("Today in {restaurant}:".to_owned()),
+///     item: "{dish}. ".to_owned(),
+///     ..Default::default()
+/// };
+/// let text = template.render_to_string("U Zlatého Bažanta", &menus[0]);
+/// assert!(text.starts_with("Today in U Zlatého Bažanta: "));
+/// assert!(text.ends_with("Vyprážaný syr, hranolky, tatárska omáčka (1,3,7). "));
+/// # Ok::<_, zomato::Error>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct SpeechTemplate {
+    /// Spoken once before the dishes.
+    pub intro: Option<String>,
+    /// Spoken for each dish.
+    pub item: String,
+    /// Replaces `{price}` with empty string.
+    pub skip_prices: bool,
+    /// Speaks the date of the menu first.
+    pub announce_date: bool,
+    /// Ends each dish with a sentence so that the engines pause between them.
+    pub pause_between_dishes: bool,
+}
+
+impl Default for SpeechTemplate {
+    fn default() -> Self {
+        SpeechTemplate {
+            intro: None,
+            item: "{dish} {price} ".to_owned(),
+            skip_prices: false,
+            announce_date: false,
+            pause_between_dishes: false,
+        }
+    }
+}
+
+impl SpeechTemplate {
+    /// Writes the text to be spoken about the menu of the restaurant.
+    pub fn render(&self, writer: &mut dyn fmt::Write, restaurant: &str, menu: &Menu) -> fmt::Result {
+        if self.announce_date {
+            write!(writer, "{}. ", menu.date)?;
+        }
+        if let Some(intro) = &self.intro {
+            let intro = fill_placeholders(intro, &[("restaurant", restaurant), ("date", &menu.date)]);
+            write!(writer, "{} ", intro)?;
+        }
+        for (i, food) in menu.items.iter().enumerate() {
+            if i > 0 && self.pause_between_dishes {
+                // Text-to-speech engines pause at the end of a sentence
+                write!(writer, ". ")?;
+            }
+            let price = if self.skip_prices { "" } else { &*food.price };
+            let vars = [
+                ("restaurant", restaurant),
+                ("date", &*menu.date),
+                ("dish", &*food.description),
+                ("price", price),
+            ];
+            write!(writer, "{}", fill_placeholders(&self.item, &vars))?;
+        }
+        Ok(())
+    }
+
+    /// Returns the text to be spoken about the menu of the restaurant.
+    pub fn render_to_string(&self, restaurant: &str, menu: &Menu) -> String {
+        let mut text = String::new();
+        self.render(&mut text, restaurant, menu).expect("writing to String never fails");
+        text
+    }
+}
+
+fn fill_placeholders(template: &str, vars: &[(&str, &str)]) -> String {
+    let mut result = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let value = rest
+            .find('}')
+            .and_then(|end| vars.iter().find(|(name, _)| *name == &rest[1..end]).map(|(_, value)| (end, value)));
+        match value {
+            Some((end, value)) => {
+                result.push_str(value);
+                rest = &rest[(end + 1)..];
+            },
+            None => {
+                result.push('{');
+                rest = &rest[1..];
+            },
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Command controlling the [`Briefing`] while it's being spoken.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Control {
+    /// Stops speaking the current restaurant and continues with the next one.
+    Skip,
+    /// Stops the whole briefing.
+    Abort,
+}
+
+/// Today's menus of several restaurants read one after another.
+///
+/// Each restaurant is introduced by its name if there are more of them and the template doesn't
+/// have its own intro, and followed by the [seating hint](crate::reservation::seating_hint) if
+/// the [reservation provider](Self::reservations) knows it. The menus are fetched while the
+/// previous restaurant is being spoken, at most one restaurant ahead. Restaurants without
+/// today's menu are left out.
+///
+/// ```no_run
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), zomato::Error> {
+/// use std::sync::Arc;
+/// use zomato::tts::{Briefing, Espeak, SpeechTemplate};
+///
+/// let client = zomato::Client::new()?;
+/// let failures = Briefing::new(SpeechTemplate::default())
+///     .restaurant("bratislava", "u-zlateho-bazanta")
+///     .restaurant("bratislava", "bistro-pod-hradom")
+///     .speak(&client, Arc::new(Espeak::default()), futures_util::stream::pending())
+///     .await?;
+/// for failure in failures {
+///     eprintln!("Failed to fetch menu of {}/{}: {}", failure.city, failure.restaurant, failure.error);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct Briefing {
+    template: SpeechTemplate,
+    restaurants: Vec<(String, String)>,
+    reservations: Arc<dyn ReservationProvider>,
+}
+
+impl Briefing {
+    /// Creates briefing speaking the menus using the template.
+    pub fn new(template: SpeechTemplate) -> Self {
+        Briefing {
+            template,
+            restaurants: Vec::new(),
+            reservations: Arc::new(NoReservations),
+        }
+    }
+
+    /// Adds restaurant to the briefing.
+    pub fn restaurant(mut self, city: impl Into<String>, restaurant: impl Into<String>) -> Self {
+        self.restaurants.push((city.into(), restaurant.into()));
+        self
+    }
+
+    /// Sets the provider of the seating hints, [`NoReservations`] is used by default.
+    pub fn reservations(mut self, provider: Arc<dyn ReservationProvider>) -> Self {
+        self.reservations = provider;
+        self
+    }
+
+    /// Speaks the menus, obeying the commands received while speaking.
+    ///
+    /// Returns the restaurants whose menus failed to be fetched, failing only if the engine
+    /// fails. The engine is called from a separate thread since speaking blocks.
+    pub async fn speak(&self, client: &Client, engine: Arc<dyn TtsEngine + Send + Sync>, mut commands: impl Stream<Item = Control> + Unpin) -> Result<Vec<BatchFailure>, Error> {
+        // The buffer has a slot for each sender, so at most one restaurant is fetched ahead
+        let (sender, mut queue) = futures_channel::mpsc::channel(0);
+        let speaking = async move {
+            let mut commands_open = true;
+            while let Some(text) = queue.next().await {
+                let interrupt = Arc::new(AtomicBool::new(false));
+                let mut speaking = speak_in_thread(Arc::clone(&engine), text, Arc::clone(&interrupt));
+                let mut aborted = false;
+                let result = loop {
+                    if !commands_open {
+                        break speaking.await;
+                    }
+                    match select(speaking, commands.next()).await {
+                        Either::Left((result, _)) => break result,
+                        Either::Right((command, unfinished)) => {
+                            speaking = unfinished;
+                            match command {
+                                Some(Control::Skip) => interrupt.store(true, Ordering::Relaxed),
+                                Some(Control::Abort) => {
+                                    aborted = true;
+                                    interrupt.store(true, Ordering::Relaxed);
+                                },
+                                None => commands_open = false,
+                            }
+                        },
+                    }
+                };
+                result.map_err(|error| Error(error.into()))?;
+                if aborted {
+                    break;
+                }
+            }
+            Ok(())
+        };
+        let (failures, result) = futures_util::future::join(self.fetch_utterances(client, sender), speaking).await;
+        result.map(|()| failures)
+    }
+
+    /// Records the whole briefing into a single file using [`record`].
+    ///
+    /// Returns the restaurants whose menus failed to be fetched. Nothing is recorded if there is
+    /// nothing to say.
+    pub async fn record(&self, client: &Client, engine: &dyn TtsEngine, path: &Path) -> Result<Vec<BatchFailure>, Error> {
+        let (sender, queue) = futures_channel::mpsc::channel(0);
+        let (failures, speech) = futures_util::future::join(self.fetch_utterances(client, sender), queue.collect::<String>()).await;
+        if !speech.is_empty() {
+            record(engine, &speech, path).map_err(|error| Error(error.into()))?;
+        }
+        Ok(failures)
+    }
+
+    /// Fetches the menus and sends the text spoken about each restaurant into the queue.
+    async fn fetch_utterances(&self, client: &Client, mut queue: futures_channel::mpsc::Sender<String>) -> Vec<BatchFailure> {
+        // The listener has to know which restaurant is being talked about
+        let template = match &self.template.intro {
+            None if self.restaurants.len() > 1 => SpeechTemplate {
+                intro: Some("{restaurant}.".to_owned()),
+                ..self.template.clone()
+            },
+            _ => self.template.clone(),
+        };
+        let mut failures = Vec::new();
+        for (city, restaurant) in &self.restaurants {
+            let today = match client.get_daily_menu(city, restaurant).await {
+                Ok(menus) => match menus.into_iter().next() {
+                    Some(today) => today,
+                    None => continue,
+                },
+                Err(error) => {
+                    failures.push(BatchFailure {
+                        city: city.clone(),
+                        restaurant: restaurant.clone(),
+                        error,
+                    });
+                    continue;
+                },
+            };
+            // Slugs use dashes instead of spaces which would be spoken weirdly
+            let restaurant_name = restaurant.replace('-', " ");
+            let mut text = template.render_to_string(&restaurant_name, &today);
+            let date = chrono::Local::now().date_naive();
+            match crate::reservation::seating_hint(&*self.reservations, city, restaurant, date).await {
+                Ok(Some(hint)) => text.push_str(&format!("{} is {}. ", restaurant_name, hint)),
+                Ok(None) => (),
+                // The menu is more important than the hint
+                #[cfg(feature = "debug-log")]
+                Err(error) => eprintln!("zomato: failed to get reservations of {}/{}: {}", city, restaurant, error),
+                #[cfg(not(feature = "debug-log"))]
+                Err(_) => (),
+            }
+            // The receiver is gone if the briefing was aborted
+            if queue.send(text).await.is_err() {
+                break;
+            }
+        }
+        failures
+    }
+}
+
+/// Speaks the text in a new thread, returning future resolved when the speech ends.
+fn speak_in_thread(engine: Arc<dyn TtsEngine + Send + Sync>, text: String, interrupt: Arc<AtomicBool>) -> impl std::future::Future<Output = io::Result<()>> + Unpin {
+    let (sender, receiver) = futures_channel::oneshot::channel();
+    std::thread::spawn(move || {
+        // The receiver is gone only if the briefing was dropped
+        let _ = sender.send(engine.speak(&text, &interrupt));
+    });
+    receiver.map(|result| result.unwrap_or_else(|_| Err(io::Error::other("speaking panicked"))))
+}
@@ -0,0 +1,87 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::time::Duration;
+use futures_core::Stream;
+use crate::{Client, Error, Menu};
+
+/// Menu which changed since the last time it was observed.
+///
+/// Returned by [`Client::watch`].
+pub struct MenuUpdate {
+    /// The new content of the menu.
+    pub menus: Vec<Menu>,
+}
+
+impl Client {
+    /// Periodically polls daily menu of given restaurant and yields it whenever it changes.
+    ///
+    /// The first successfully fetched menu is always yielded. After that, menus identical to the
+    /// previously yielded one are skipped, so the stream is quiet while nothing changes.
+    ///
+    /// Errors are yielded too, but the polling continues after the next `interval` elapses, so
+    /// the consumer can decide whether to give up or just log them.
+    ///
+    /// ```
+    /// use futures_util::StreamExt;
+    ///
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), zomato::Error> {
+    /// # let client = zomato::testing::fixture_client();
+    /// let updates = client.watch("bratislava", "u-zlateho-bazanta", std::time::Duration::from_secs(600));
+    /// futures_util::pin_mut!(updates);
+    /// let update = updates.next().await.expect("the stream is infinite")?;
+    /// assert_eq!(update.menus[0].date, "Today");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn watch(&self, city: impl Into<String>, restaurant: impl Into<String>, interval: Duration) -> impl Stream<Item = Result<MenuUpdate, Error>> {
+        let state = WatchState {
+            client: self.clone(),
+            city: city.into(),
+            restaurant: restaurant.into(),
+            last_hash: None,
+            started: false,
+        };
+
+        futures_util::stream::unfold(state, move |mut state| async move {
+            loop {
+                if state.started {
+                    crate::runtime::sleep(interval).await;
+                }
+                state.started = true;
+
+                let menus = match state.client.get_daily_menu(&state.city, &state.restaurant).await {
+                    Ok(menus) => menus,
+                    Err(error) => return Some((Err(error), state)),
+                };
+                let hash = menu_hash(&menus);
+                if state.last_hash != Some(hash) {
+                    state.last_hash = Some(hash);
+                    return Some((Ok(MenuUpdate { menus, }), state));
+                }
+            }
+        })
+    }
+}
+
+struct WatchState {
+    client: Client,
+    city: String,
+    restaurant: String,
+    last_hash: Option<u64>,
+    started: bool,
+}
+
+fn menu_hash(menus: &[Menu]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    menus.len().hash(&mut hasher);
+    for menu in menus {
+        menu.date.hash(&mut hasher);
+        menu.items.len().hash(&mut hasher);
+        for item in &menu.items {
+            item.description.hash(&mut hasher);
+            item.price.hash(&mut hasher);
+        }
+    }
+    hasher.finish()
+}
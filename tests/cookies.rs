@@ -0,0 +1,87 @@
+//! Cookies set by the servers, received through a proxy so that the hosts can be arbitrary.
+#![cfg(feature = "cookies")]
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::mpsc::Receiver;
+use std::sync::Arc;
+use zomato::cookies::CookieJar;
+
+/// Starts proxy answering all requests with the fixture and the cookies.
+///
+/// Returns the URL of the proxy and the values of `Cookie` header of the requests.
+fn start_proxy(set_cookies: &'static [&'static str]) -> (String, Receiver<Option<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let (sender, cookies) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(&stream);
+            let mut line = String::new();
+            let mut cookie = None;
+            while reader.read_line(&mut line).unwrap() > 2 {
+                if let Some(value) = line.to_lowercase().strip_prefix("cookie:") {
+                    cookie = Some(value.trim().to_owned());
+                }
+                line.clear();
+            }
+            sender.send(cookie).unwrap();
+            let body = zomato::testing::FIXTURE;
+            let headers = set_cookies.iter().map(|cookie| format!("Set-Cookie: {}\r\n", cookie)).collect::<String>();
+            write!(stream, "HTTP/1.1 200 OK\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}", headers, body.len(), body).unwrap();
+        }
+    });
+    (url, cookies)
+}
+
+#[tokio::test]
+async fn cookies_for_public_suffixes_are_rejected() {
+    let (proxy, cookies) = start_proxy(&["host=1", "parent=2; Domain=example.com", "suffix=3; Domain=com", "dot=4; Domain=.com."]);
+    let jar = Arc::new(CookieJar::in_memory());
+    let client = zomato::Client::builder()
+        .base_url("http://www.example.com")
+        .proxy(proxy)
+        .cookie_jar(Arc::clone(&jar))
+        .build()
+        .unwrap();
+
+    client.get_daily_menu("bratislava", "u-zlateho-bazanta").await.unwrap();
+    client.get_daily_menu("bratislava", "u-zlateho-bazanta").await.unwrap();
+    assert_eq!(cookies.recv().unwrap(), None);
+    assert_eq!(cookies.recv().unwrap().as_deref(), Some("host=1; parent=2"));
+    assert_eq!(jar.len(), 2);
+}
+
+#[tokio::test]
+async fn cookies_for_multi_label_public_suffixes_are_rejected() {
+    let (proxy, cookies) = start_proxy(&["parent=1; Domain=example.co.uk", "suffix=2; Domain=co.uk", "dot=3; Domain=.CO.UK."]);
+    let jar = Arc::new(CookieJar::in_memory());
+    let client = zomato::Client::builder()
+        .base_url("http://www.example.co.uk")
+        .proxy(proxy)
+        .cookie_jar(Arc::clone(&jar))
+        .build()
+        .unwrap();
+
+    client.get_daily_menu("bratislava", "u-zlateho-bazanta").await.unwrap();
+    client.get_daily_menu("bratislava", "u-zlateho-bazanta").await.unwrap();
+    assert_eq!(cookies.recv().unwrap(), None);
+    assert_eq!(cookies.recv().unwrap().as_deref(), Some("parent=1"));
+    assert_eq!(jar.len(), 1);
+}
+
+#[tokio::test]
+async fn cookies_for_other_domains_are_rejected() {
+    let (proxy, _cookies) = start_proxy(&["other=1; Domain=example.org", "sibling=2; Domain=api.example.com"]);
+    let jar = Arc::new(CookieJar::in_memory());
+    let client = zomato::Client::builder()
+        .base_url("http://www.example.com")
+        .proxy(proxy)
+        .cookie_jar(Arc::clone(&jar))
+        .build()
+        .unwrap();
+
+    client.get_daily_menu("bratislava", "u-zlateho-bazanta").await.unwrap();
+    assert!(jar.is_empty());
+}
@@ -0,0 +1,24 @@
+//! Archives opened by multiple processes at once.
+#![cfg(feature = "history")]
+
+use zomato::history::{History, SCHEMA_VERSION};
+
+#[test]
+fn concurrent_opens_migrate_once() {
+    let dir = mktemp::Temp::new_dir().unwrap();
+    let path = dir.join("history.sqlite");
+    let threads = (0..8)
+        .map(|_| {
+            let path = path.clone();
+            std::thread::spawn(move || History::open(path).unwrap().schema_version().unwrap())
+        })
+        .collect::<Vec<_>>();
+    for thread in threads {
+        assert_eq!(thread.join().unwrap(), SCHEMA_VERSION);
+    }
+
+    let mut history = History::open(&path).unwrap();
+    let menus = zomato::parse_daily_menu(zomato::testing::FIXTURE).unwrap();
+    history.record("bratislava", "u-zlateho-bazanta", &menus).unwrap();
+    assert_eq!(history.price_history("rezeň").unwrap().len(), 1);
+}
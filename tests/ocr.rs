@@ -0,0 +1,32 @@
+//! Downloading photos of daily menus for OCR through the configured transport.
+#![cfg(feature = "ocr")]
+
+use std::sync::Arc;
+use zomato::ocr::Tesseract;
+use zomato::testing::{MockTransport, PHOTOS_FIXTURE};
+
+const PHOTOS_URL: &str = "https://www.zomato.com/bratislava/u-zlateho-bazanta/photos";
+const IMAGE_URL: &str = "https://b.zmtcdn.com/data/pictures/2/16508052/d4e5f6.jpg";
+
+#[tokio::test]
+async fn image_is_downloaded_by_transport() {
+    // Not an image, so recognizing fails whether tesseract is installed or not
+    let transport = Arc::new(MockTransport::new().with_page(PHOTOS_URL, PHOTOS_FIXTURE).with_bytes(IMAGE_URL, &b"not an image"[..]));
+    let client = zomato::Client::builder()
+        .transport(Arc::clone(&transport))
+        .build()
+        .unwrap();
+
+    let result = client.get_daily_menu_ocr("bratislava", "u-zlateho-bazanta", &Tesseract::default()).await;
+    assert!(result.is_err());
+    assert_eq!(transport.requests(), [PHOTOS_URL, IMAGE_URL]);
+}
+
+#[tokio::test]
+async fn large_image_is_rejected() {
+    let transport = MockTransport::new().with_page(PHOTOS_URL, PHOTOS_FIXTURE).with_bytes(IMAGE_URL, vec![0; 11 * 1024 * 1024]);
+    let client = zomato::Client::with_transport(transport);
+
+    let error = client.get_daily_menu_ocr("bratislava", "u-zlateho-bazanta", &Tesseract::default()).await.err().unwrap();
+    assert_eq!(error.to_string(), format!("{} is larger than {} bytes", IMAGE_URL, 10 * 1024 * 1024));
+}
@@ -0,0 +1,31 @@
+//! Exercises the path of `examples/print-daily-menu.rs` against the fixture.
+
+use zomato::format::{TableFormatter, Truncation};
+
+#[tokio::test]
+async fn prints_truncated_table() {
+    let client = zomato::testing::fixture_client();
+    let menus = client.get_daily_menu("bratislava", "u-zlateho-bazanta").await.unwrap();
+
+    let formatter = TableFormatter {
+        max_width: Some(20),
+        truncation: "word".parse().unwrap(),
+        ..Default::default()
+    };
+    let mut table = String::new();
+    formatter.format(&menus, &mut table).unwrap();
+
+    assert_eq!(table, "\
+Today
+Hovädzí vývar s… | €1.20
+150g Bravčový…   | €7.20
+Vyprážaný syr…   | €6.00
+");
+}
+
+#[test]
+fn rejects_unknown_truncation() {
+    assert_eq!("middle".parse::<Truncation>().unwrap(), Truncation::Middle);
+    let error = "sideways".parse::<Truncation>().unwrap_err();
+    assert!(error.to_string().contains("sideways"));
+}
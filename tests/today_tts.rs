@@ -0,0 +1,127 @@
+//! Exercises the briefing of `examples/today-tts.rs` against the fixture, using a fake engine.
+#![cfg(feature = "tts")]
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use futures_channel::mpsc::UnboundedSender;
+use zomato::testing::{MockTransport, FIXTURE};
+use zomato::tts::{Briefing, Control, SpeechTemplate, TtsEngine};
+
+/// Remembers what it was asked to say instead of saying it.
+#[derive(Default)]
+struct FakeEngine {
+    spoken: Mutex<Vec<String>>,
+    recorded: Mutex<Vec<(String, PathBuf)>>,
+    /// Commands of a listener sent whenever the engine starts speaking, one per utterance.
+    ///
+    /// The engine then speaks until interrupted, like a long menu.
+    listener: Option<(Mutex<Vec<Control>>, UnboundedSender<Control>)>,
+}
+
+impl TtsEngine for FakeEngine {
+    fn speak(&self, text: &str, interrupt: &AtomicBool) -> io::Result<()> {
+        self.spoken.lock().unwrap().push(text.to_owned());
+        if let Some((reactions, commands)) = &self.listener {
+            let reaction = reactions.lock().unwrap().remove(0);
+            commands.unbounded_send(reaction).unwrap();
+            while !interrupt.load(Ordering::Relaxed) {
+                std::thread::sleep(Duration::from_millis(10));
+            }
+        }
+        Ok(())
+    }
+
+    fn record_wav(&self, text: &str, path: &Path) -> io::Result<()> {
+        self.recorded.lock().unwrap().push((text.to_owned(), path.to_owned()));
+        Ok(())
+    }
+}
+
+async fn fixture_menu() -> zomato::Menu {
+    let client = zomato::testing::fixture_client();
+    client.get_daily_menu("bratislava", "u-zlateho-bazanta").await.unwrap().into_iter().next().unwrap()
+}
+
+#[tokio::test]
+async fn speaks_menu_using_template() {
+    let template = SpeechTemplate {
+        skip_prices: true,
+        announce_date: true,
+        pause_between_dishes: true,
+        ..Default::default()
+    };
+    let engine = Arc::new(FakeEngine::default());
+    let failures = Briefing::new(template)
+        .restaurant("bratislava", "u-zlateho-bazanta")
+        .restaurant("bratislava", "bistro-pod-hradom")
+        .speak(&zomato::testing::fixture_client(), Arc::clone(&engine) as _, futures_util::stream::pending())
+        .await
+        .unwrap();
+
+    assert!(failures.is_empty());
+    let spoken = engine.spoken.lock().unwrap();
+    assert_eq!(spoken.len(), 2);
+    // the restaurants are introduced since there are more of them
+    assert!(spoken[0].starts_with("Today. u zlateho bazanta. "));
+    assert!(spoken[1].starts_with("Today. bistro pod hradom. "));
+    assert!(!spoken[0].contains('€'));
+    // Every dish but the last one ends a sentence
+    assert_eq!(spoken[0].matches("  . ").count(), 2);
+}
+
+#[tokio::test]
+async fn skips_and_aborts() {
+    let (sender, commands) = futures_channel::mpsc::unbounded();
+    let engine = Arc::new(FakeEngine {
+        listener: Some((Mutex::new(vec![Control::Skip, Control::Abort]), sender)),
+        ..Default::default()
+    });
+    let failures = Briefing::new(SpeechTemplate::default())
+        .restaurant("bratislava", "u-zlateho-bazanta")
+        .restaurant("bratislava", "bistro-pod-hradom")
+        .restaurant("bratislava", "flagship")
+        .speak(&zomato::testing::fixture_client(), Arc::clone(&engine) as _, commands)
+        .await
+        .unwrap();
+
+    assert!(failures.is_empty());
+    let spoken = engine.spoken.lock().unwrap();
+    assert_eq!(spoken.len(), 2);
+    assert!(spoken[1].starts_with("bistro pod hradom. "));
+}
+
+#[tokio::test]
+async fn reports_failed_restaurants() {
+    let client = zomato::Client::with_transport(MockTransport::new().with_page("https://www.zomato.com/bratislava/u-zlateho-bazanta/daily-menu", FIXTURE));
+    let engine = FakeEngine::default();
+    let dir = mktemp::Temp::new_dir().unwrap();
+    let path = dir.join("briefing.wav");
+    let failures = Briefing::new(SpeechTemplate::default())
+        .restaurant("bratislava", "u-zlateho-bazanta")
+        .restaurant("bratislava", "bistro-pod-hradom")
+        .record(&client, &engine, &path)
+        .await
+        .unwrap();
+
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].restaurant, "bistro-pod-hradom");
+    let recorded = engine.recorded.lock().unwrap();
+    assert_eq!(recorded.len(), 1);
+    assert!(recorded[0].0.starts_with("u zlateho bazanta. "));
+    assert_eq!(recorded[0].1, path);
+}
+
+#[tokio::test]
+async fn records_wav_directly() {
+    let menu = fixture_menu().await;
+    let text = SpeechTemplate::default().render_to_string("u zlateho bazanta", &menu);
+    let engine = FakeEngine::default();
+    zomato::tts::record(&engine, &text, Path::new("menu.wav")).unwrap();
+
+    let recorded = engine.recorded.lock().unwrap();
+    assert_eq!(*recorded, [(text, PathBuf::from("menu.wav"))]);
+    assert!(recorded[0].0.contains("Vyprážaný syr, hranolky, tatárska omáčka (1,3,7) €6.00 "));
+}
@@ -0,0 +1,63 @@
+//! Daily menu pages downloaded only up to the end of the embedded data.
+#![cfg(feature = "reqwest")]
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::mpsc::Receiver;
+
+/// Restaurant described using schema.org, which is found after the preloaded state.
+const JSON_LD: &str = r#"<script type="application/ld+json">{
+    "@type": "Restaurant",
+    "name": "U Zlatého Bažanta",
+    "hasMenu": {"hasMenuSection": [{
+        "name": "Today",
+        "hasMenuItem": [{"name": "Vyprážaný syr", "offers": {"price": "6.00", "priceCurrency": "EUR"}}]
+    }]}
+}</script>"#;
+
+/// Starts server answering all requests with the page.
+///
+/// Returns the base URL and the paths of the requests.
+fn start_server(page: String) -> (String, Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let (sender, paths) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(&stream);
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let path = line.split(' ').nth(1).unwrap_or_default().to_owned();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                line.clear();
+            }
+            sender.send(path).unwrap();
+            let _ = write!(stream, "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", page.len(), page);
+        }
+    });
+    (url, paths)
+}
+
+#[tokio::test]
+async fn markers_outside_scripts_are_skipped() {
+    let page = format!("<p>window.__PRELOADED_STATE__ = JSON.parse(\"{{}}\")\n</p>{}", zomato::testing::FIXTURE);
+    let (base_url, paths) = start_server(page);
+    let client = zomato::Client::builder().base_url(base_url).build().unwrap();
+
+    let menus = client.get_daily_menu("bratislava", "u-zlateho-bazanta").await.unwrap();
+    assert_eq!(menus[0].date, "Today");
+    assert_eq!(paths.try_iter().count(), 1);
+}
+
+#[tokio::test]
+async fn whole_page_is_downloaded_if_embedded_data_are_broken() {
+    let page = format!("<script>window.__PRELOADED_STATE__ = JSON.parse(\"{{}}\")\n</script>{}", JSON_LD);
+    let (base_url, paths) = start_server(page);
+    let client = zomato::Client::builder().base_url(base_url).build().unwrap();
+
+    let menus = client.get_daily_menu("bratislava", "u-zlateho-bazanta").await.unwrap();
+    assert_eq!(menus[0].items[0].price, "€6.00");
+    let paths = paths.try_iter().collect::<Vec<_>>();
+    assert_eq!(paths, ["/bratislava/u-zlateho-bazanta/daily-menu", "/bratislava/u-zlateho-bazanta/daily-menu"]);
+}
@@ -0,0 +1,75 @@
+//! Bodies POSTed by the webhook sink, received by a local server.
+#![cfg(feature = "webhook")]
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc::Receiver;
+use zomato::sink::webhook::{PayloadFormat, Webhook, SIGNATURE_HEADER};
+use zomato::sink::{MenuEvent, Sink};
+
+/// Starts server answering all requests with `204 No Content`.
+///
+/// Returns the URL and the signatures and bodies of the requests.
+fn start_server() -> (String, Receiver<(Option<String>, serde_json::Value)>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+    let (sender, requests) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(&stream);
+            let mut line = String::new();
+            let mut length = 0;
+            let mut signature = None;
+            while reader.read_line(&mut line).unwrap() > 2 {
+                let (name, value) = line.split_once(':').unwrap_or_default();
+                if name.eq_ignore_ascii_case("content-length") {
+                    length = value.trim().parse().unwrap();
+                } else if name.eq_ignore_ascii_case(SIGNATURE_HEADER) {
+                    signature = Some(value.trim().to_owned());
+                }
+                line.clear();
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            sender.send((signature, serde_json::from_slice(&body).unwrap())).unwrap();
+            write!(stream, "HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n").unwrap();
+        }
+    });
+    (url, requests)
+}
+
+#[tokio::test]
+async fn events_are_posted_as_json() {
+    let (url, requests) = start_server();
+    let menus = zomato::parse_daily_menu(zomato::testing::FIXTURE).unwrap();
+    let event = MenuEvent::new("bratislava", "u-zlateho-bazanta", menus);
+    Webhook::new(url).secret("secret").deliver(&event).await.unwrap();
+
+    let (signature, body) = requests.recv().unwrap();
+    assert_eq!(body["restaurant"], "u-zlateho-bazanta");
+    assert_eq!(body["menus"][0]["date"], "Today");
+    assert_eq!(signature, Some(zomato::sink::webhook::sign(b"secret", &serde_json::to_vec(&event).unwrap())));
+}
+
+#[tokio::test]
+async fn chats_receive_text() {
+    let (url, requests) = start_server();
+    let mut menus = zomato::parse_daily_menu(zomato::testing::FIXTURE).unwrap();
+    menus[0].items[0].description = "Fish & Chips".to_owned();
+    let event = MenuEvent::new("bratislava", "u-zlateho-bazanta", menus);
+
+    Webhook::new(&url).payload_format(PayloadFormat::Slack).deliver(&event).await.unwrap();
+    let (_, body) = requests.recv().unwrap();
+    let text = body["text"].as_str().unwrap();
+    assert!(text.starts_with("Lunch at bratislava/u-zlateho-bazanta\n```\nToday\nFish &amp; Chips "));
+
+    Webhook::new(&url).payload_format(PayloadFormat::Mattermost).post(&event.menus).await.unwrap();
+    let (_, body) = requests.recv().unwrap();
+    assert!(body["text"].as_str().unwrap().starts_with("Lunch\n```\nToday\nFish & Chips "));
+
+    Webhook::new(&url).payload_format(PayloadFormat::Discord).deliver(&event).await.unwrap();
+    let (_, body) = requests.recv().unwrap();
+    assert!(body.get("text").is_none());
+    assert!(body["content"].as_str().unwrap().contains("| €6.00\n```"));
+}
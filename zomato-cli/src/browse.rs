@@ -0,0 +1,256 @@
+//! The `browse` subcommand.
+
+use std::borrow::Cow;
+use std::path::Path;
+use crossterm::clipboard::CopyToClipboard;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::Style;
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Tabs};
+use ratatui::{DefaultTerminal, Frame};
+use zomato::format::Truncation;
+use zomato::{Menu, MenuItem};
+use crate::config::Config;
+
+const HELP: &str = "←/→ day  ↑/↓ dish  / filter  c copy  s sort by price  r refresh  q quit";
+
+/// Browses the daily menus of a restaurant interactively.
+///
+/// Days are shown as tabs and dishes as a list. Copying uses the OSC 52 escape sequence so it
+/// works over SSH too, as long as the terminal supports it.
+#[derive(clap::Args)]
+pub struct Args {
+    /// Alias defined in the config, URL of the restaurant or city in which it's located.
+    name: String,
+    /// Name of the restaurant as used in the URL, omitted when using an alias.
+    restaurant: Option<String>,
+}
+
+pub async fn run(args: Args, config_path: Option<&Path>) -> Result<(), anyhow::Error> {
+    let config = Config::load(config_path)?;
+    let target = config.resolve(&args.name, args.restaurant.as_deref())?;
+    let (city, restaurant) = (&*target.city, &*target.slug);
+    let client = config.client()?;
+    let menus = client.get_daily_menu(city, restaurant).await?;
+
+    let mut browser = Browser {
+        pane: MenuPane::new(format!("{}/{}", city, restaurant), menus),
+        status: None,
+    };
+    let mut terminal = ratatui::try_init()?;
+    let result = browser.run(&mut terminal, &client, city, restaurant).await;
+    ratatui::restore();
+    result
+}
+
+struct Browser {
+    pane: MenuPane,
+    status: Option<String>,
+}
+
+impl Browser {
+    async fn run(&mut self, terminal: &mut DefaultTerminal, client: &zomato::Client, city: &str, restaurant: &str) -> Result<(), anyhow::Error> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            let key = match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => key,
+                _ => continue,
+            };
+            self.status = None;
+            if self.pane.handle_filter_key(key) {
+                continue;
+            }
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Left | KeyCode::Char('h') | KeyCode::BackTab => self.pane.previous_tab(),
+                KeyCode::Right | KeyCode::Char('l') | KeyCode::Tab => self.pane.next_tab(),
+                KeyCode::Up | KeyCode::Char('k') => self.pane.list.select_previous(),
+                KeyCode::Down | KeyCode::Char('j') => self.pane.list.select_next(),
+                KeyCode::Char('/') => self.pane.start_filter(),
+                KeyCode::Char('c') => self.status = self.pane.copy_selected()?,
+                KeyCode::Char('s') => self.pane.toggle_sort(),
+                KeyCode::Char('r') => {
+                    self.status = Some("Refreshing…".to_owned());
+                    terminal.draw(|frame| self.draw(frame))?;
+                    self.status = match client.get_daily_menu(city, restaurant).await {
+                        Ok(menus) => {
+                            self.pane.set_menus(menus);
+                            Some(format!("Refreshed at {}", chrono::Local::now().format("%H:%M")))
+                        },
+                        Err(error) => Some(format!("Refresh failed: {}", error)),
+                    };
+                },
+                _ => (),
+            }
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [menu_area, status_area] = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+        self.pane.draw(frame, menu_area, true);
+        frame.render_widget(Paragraph::new(self.pane.status_line(self.status.as_deref(), HELP)), status_area);
+    }
+}
+
+/// Day tabs and the list of dishes of a single restaurant.
+///
+/// Shared by the `browse` and `tui` subcommands.
+pub(crate) struct MenuPane {
+    pub(crate) title: String,
+    menus: Vec<Menu>,
+    tab: usize,
+    pub(crate) list: ListState,
+    sort_by_price: bool,
+    filter: String,
+    editing_filter: bool,
+}
+
+impl MenuPane {
+    pub(crate) fn new(title: String, menus: Vec<Menu>) -> Self {
+        let mut pane = MenuPane {
+            title,
+            menus,
+            tab: 0,
+            list: ListState::default(),
+            sort_by_price: false,
+            filter: String::new(),
+            editing_filter: false,
+        };
+        pane.select_tab(0);
+        pane
+    }
+
+    /// Replaces the menus, staying at the same day if possible.
+    pub(crate) fn set_menus(&mut self, menus: Vec<Menu>) {
+        self.menus = menus;
+        self.select_tab(self.tab);
+    }
+
+    /// Removes the menus from the pane.
+    pub(crate) fn take_menus(&mut self) -> Vec<Menu> {
+        std::mem::take(&mut self.menus)
+    }
+
+    pub(crate) fn previous_tab(&mut self) {
+        self.select_tab(self.tab.saturating_sub(1));
+    }
+
+    pub(crate) fn next_tab(&mut self) {
+        self.select_tab(self.tab + 1);
+    }
+
+    pub(crate) fn toggle_sort(&mut self) {
+        self.sort_by_price = !self.sort_by_price;
+        self.list.select_first();
+    }
+
+    /// Starts typing the text the dishes must contain.
+    pub(crate) fn start_filter(&mut self) {
+        self.editing_filter = true;
+    }
+
+    /// Edits the filter if it's being typed, returns `false` if the key wasn't used.
+    ///
+    /// Enter finishes typing and Esc clears the filter.
+    pub(crate) fn handle_filter_key(&mut self, key: KeyEvent) -> bool {
+        if !self.editing_filter {
+            return false;
+        }
+        match key.code {
+            KeyCode::Enter => self.editing_filter = false,
+            KeyCode::Esc => {
+                self.editing_filter = false;
+                self.filter.clear();
+            },
+            KeyCode::Backspace => {
+                self.filter.pop();
+            },
+            KeyCode::Char(c) => self.filter.push(c),
+            _ => return true,
+        }
+        self.select_tab(self.tab);
+        true
+    }
+
+    /// Returns the text shown in the status line.
+    pub(crate) fn status_line<'a>(&'a self, status: Option<&'a str>, help: &'a str) -> Cow<'a, str> {
+        if self.editing_filter {
+            return format!("Filter: {}▏ (Enter done, Esc clear)", self.filter).into();
+        }
+        status.unwrap_or(help).into()
+    }
+
+    /// Switches to the day, clamping it to the available days.
+    fn select_tab(&mut self, tab: usize) {
+        self.tab = tab.min(self.menus.len().saturating_sub(1));
+        let has_items = !self.items().is_empty();
+        self.list.select(if has_items { Some(0) } else { None });
+    }
+
+    /// Returns the dishes of the current day in the displayed order.
+    fn items(&self) -> Vec<&MenuItem> {
+        let filter = self.filter.to_lowercase();
+        let mut items = self
+            .menus
+            .get(self.tab)
+            .map(|menu| menu.items.iter().filter(|item| item.description.to_lowercase().contains(&filter)).collect::<Vec<_>>())
+            .unwrap_or_default();
+        if self.sort_by_price {
+            // dishes without a price go last
+            items.sort_by(|a, b| match (a.parsed_price(), b.parsed_price()) {
+                (Some(a), Some(b)) => a.amount.total_cmp(&b.amount),
+                (a, b) => b.is_some().cmp(&a.is_some()),
+            });
+        }
+        items
+    }
+
+    /// Copies the description of the selected dish, returning the status to display.
+    pub(crate) fn copy_selected(&self) -> Result<Option<String>, anyhow::Error> {
+        let description = match self.list.selected().and_then(|index| self.items().get(index).map(|item| item.description.clone())) {
+            Some(description) => description,
+            None => return Ok(None),
+        };
+        crossterm::execute!(std::io::stdout(), CopyToClipboard::to_clipboard_from(&description))?;
+        Ok(Some(format!("Copied: {}", description)))
+    }
+
+    /// Draws the tabs and the dishes, highlighting the selected dish only if `focused`.
+    pub(crate) fn draw(&mut self, frame: &mut Frame, area: Rect, focused: bool) {
+        let [tabs_area, list_area] = Layout::vertical([Constraint::Length(3), Constraint::Min(0)]).areas(area);
+
+        let tabs = Tabs::new(self.menus.iter().map(|menu| menu.date.clone()))
+            .select(self.tab)
+            .highlight_style(Style::new().reversed())
+            .block(Block::bordered().title(self.title.clone()));
+        frame.render_widget(tabs, tabs_area);
+
+        let items = self.items();
+        let mut list_title = if self.sort_by_price { "Dishes by price".to_owned() } else { "Dishes".to_owned() };
+        if !self.filter.is_empty() {
+            list_title.push_str(&format!(" containing \"{}\"", self.filter));
+        }
+        if items.is_empty() {
+            let message = if self.filter.is_empty() { "No daily menu available" } else { "No matching dishes" };
+            let empty = Paragraph::new(message).block(Block::bordered().title(list_title));
+            frame.render_widget(empty, list_area);
+        } else {
+            let price_width = items.iter().map(|item| item.price.chars().count()).max().unwrap_or(0);
+            // borders, highlight symbol and the space before the price
+            let max_width = usize::from(list_area.width).saturating_sub(price_width + 6);
+            let descriptions = items.iter().map(|item| Truncation::WordBoundary.apply(&item.description, max_width)).collect::<Vec<_>>();
+            let width = descriptions.iter().map(|description| description.chars().count()).max().unwrap_or(0);
+            let items = descriptions
+                .iter()
+                .zip(&items)
+                .map(|(description, item)| ListItem::new(format!("{:width$}  {}", description, item.price, width = width)))
+                .collect::<Vec<_>>();
+            let highlight = if focused { Style::new().reversed() } else { Style::new() };
+            let list = List::new(items)
+                .highlight_style(highlight)
+                .highlight_symbol("> ")
+                .block(Block::bordered().title(list_title));
+            frame.render_stateful_widget(list, list_area, &mut self.list);
+        }
+    }
+}
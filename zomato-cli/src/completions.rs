@@ -0,0 +1,20 @@
+//! The `completions` subcommand.
+
+use clap::CommandFactory;
+use clap_complete::Shell;
+
+/// Prints the shell completion script.
+///
+/// For example, add `source <(zomato completions bash)` to `~/.bashrc`.
+#[derive(clap::Args)]
+pub struct Args {
+    /// Shell to generate the completions for.
+    shell: Shell,
+}
+
+pub fn run(args: Args) -> Result<(), anyhow::Error> {
+    let mut command = crate::Args::command();
+    let name = command.get_name().to_owned();
+    clap_complete::generate(args.shell, &mut command, name, &mut std::io::stdout());
+    Ok(())
+}
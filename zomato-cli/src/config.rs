@@ -0,0 +1,232 @@
+//! User configuration.
+//!
+//! Read from `$XDG_CONFIG_HOME/zomato/config.toml` (`~/.config/zomato/config.toml` by default)
+//! unless `--config` or `ZOMATO_CONFIG` points elsewhere. A missing file is the same as an empty
+//! one.
+//!
+//! ```toml
+//! # Default output format: table, markdown or html
+//! format = "markdown"
+//! # Text-to-speech engine used by `daily-menu --speak`: festival, espeak or pico2wave
+//! tts = "espeak"
+//! # Language of the speech
+//! language = "sk"
+//! # Source of the daily menus: html (default) or web-api
+//! backend = "web-api"
+//!
+//! [aliases]
+//! work = { city = "bratislava", restaurant = "u-zlateho-bazanta" }
+//!
+//! # Days without lunch, Saturday and Sunday by default
+//! [calendar]
+//! weekend = ["sat", "sun"]
+//! holidays = ["2026-12-24", "2026-12-25"]
+//!
+//! # Dishes never shown, spoken or sent by any command, matched case-insensitively
+//! [blacklist]
+//! dishes = ["minutky dle nabídky"]
+//!
+//! [blacklist.restaurants.u-zlateho-bazanta]
+//! dishes = ["polievka dňa"]
+//! # Keeps dishes removed by the global patterns
+//! allow = ["minutky"]
+//!
+//! # Maximum plausible prices of a dish, other prices are reported and left out of statistics
+//! [prices]
+//! max = { "€" = 30, "Kč" = 600 }
+//! # Maximum in other currencies
+//! default_max = 1000
+//!
+//! # Menus older than this many days are ignored, e.g. Friday's menu still served on Monday
+//! [menus]
+//! max_age_days = 0
+//! # Timezone of the restaurants, the local one by default
+//! utc_offset = "+01:00"
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use anyhow::{anyhow, Context};
+use chrono::{FixedOffset, NaiveDate, Weekday};
+use serde_derive::Deserialize;
+use zomato::{Backend, BusinessCalendar, DishBlacklist, MaxMenuAge, PriceBounds, RestaurantRef};
+
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub format: Option<String>,
+    pub backend: Option<String>,
+    // Accepted even without text-to-speech support so that the config is portable
+    #[cfg_attr(not(feature = "tts"), allow(dead_code))]
+    pub language: Option<String>,
+    #[cfg_attr(not(feature = "tts"), allow(dead_code))]
+    pub tts: Option<String>,
+    #[serde(default)]
+    pub aliases: BTreeMap<String, Alias>,
+    #[serde(default)]
+    pub calendar: Calendar,
+    #[serde(default)]
+    pub blacklist: Blacklist,
+    #[serde(default)]
+    pub prices: Prices,
+    #[serde(default)]
+    pub menus: Menus,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Alias {
+    pub city: String,
+    pub restaurant: String,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Calendar {
+    pub weekend: Option<Vec<String>>,
+    #[serde(default)]
+    pub holidays: Vec<String>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Blacklist {
+    #[serde(default)]
+    pub dishes: Vec<String>,
+    #[serde(default)]
+    pub restaurants: BTreeMap<String, RestaurantBlacklist>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RestaurantBlacklist {
+    #[serde(default)]
+    pub dishes: Vec<String>,
+    #[serde(default)]
+    pub allow: Vec<String>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Prices {
+    #[serde(default)]
+    pub max: BTreeMap<String, f64>,
+    pub default_max: Option<f64>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Menus {
+    pub max_age_days: Option<u32>,
+    pub utc_offset: Option<String>,
+}
+
+impl Config {
+    /// Loads the config from the path or the default location.
+    pub fn load(path: Option<&Path>) -> Result<Self, anyhow::Error> {
+        let path = match path {
+            Some(path) => path.to_owned(),
+            None => match default_path() {
+                Some(path) => path,
+                None => return Ok(Config::default()),
+            },
+        };
+        let config = match std::fs::read_to_string(&path) {
+            Ok(config) => config,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Config::default()),
+            Err(error) => return Err(anyhow::Error::from(error).context(format!("failed to read {}", path.display()))),
+        };
+        toml::from_str(&config).with_context(|| format!("invalid config {}", path.display()))
+    }
+
+    /// Returns the bounds of plausible prices, the defaults extended by the configured maximums.
+    pub fn price_bounds(&self) -> PriceBounds {
+        let mut bounds = self.prices.max.iter().fold(PriceBounds::default(), |bounds, (currency, max)| bounds.max(currency, *max));
+        if let Some(max) = self.prices.default_max {
+            bounds = bounds.default_max(max);
+        }
+        bounds
+    }
+
+    /// Returns the calendar of business days.
+    pub fn business_calendar(&self) -> Result<BusinessCalendar, anyhow::Error> {
+        let mut calendar = BusinessCalendar::new();
+        if let Some(weekend) = &self.calendar.weekend {
+            let weekend = weekend
+                .iter()
+                .map(|day| day.parse::<Weekday>().map_err(|_| anyhow!("invalid day of week '{}' in calendar.weekend", day)))
+                .collect::<Result<Vec<_>, _>>()?;
+            calendar = calendar.weekend(weekend);
+        }
+        for holiday in &self.calendar.holidays {
+            let date = holiday.parse::<NaiveDate>().with_context(|| format!("invalid holiday '{}', use YYYY-MM-DD", holiday))?;
+            calendar = calendar.holiday(date);
+        }
+        Ok(calendar)
+    }
+
+    /// Returns the configured maximum age of menus.
+    pub fn max_menu_age(&self) -> Result<Option<MaxMenuAge>, anyhow::Error> {
+        let max_age = match self.menus.max_age_days {
+            Some(days) => MaxMenuAge::days(days),
+            None => return Ok(None),
+        };
+        match &self.menus.utc_offset {
+            Some(offset) => {
+                let offset = offset.parse::<FixedOffset>().map_err(|_| anyhow!("invalid menus.utc_offset '{}', use e.g. +01:00", offset))?;
+                Ok(Some(max_age.utc_offset(offset)))
+            },
+            None => Ok(Some(max_age)),
+        }
+    }
+
+    /// Returns builder of the client removing the blacklisted dishes and stale menus.
+    pub fn client_builder(&self) -> Result<zomato::ClientBuilder, anyhow::Error> {
+        let mut blacklist = self.blacklist.dishes.iter().fold(DishBlacklist::new(), DishBlacklist::dish);
+        for (restaurant, overrides) in &self.blacklist.restaurants {
+            for pattern in &overrides.dishes {
+                blacklist = blacklist.restaurant_dish(restaurant, pattern);
+            }
+            for pattern in &overrides.allow {
+                blacklist = blacklist.restaurant_allow(restaurant, pattern);
+            }
+        }
+        let mut builder = zomato::Client::builder().blacklist(blacklist);
+        if let Some(backend) = &self.backend {
+            builder = builder.backend(backend.parse::<Backend>()?);
+        }
+        if let Some(max_age) = self.max_menu_age()? {
+            builder = builder.max_menu_age(max_age);
+        }
+        Ok(builder)
+    }
+
+    /// Creates the client removing the blacklisted dishes and stale menus.
+    pub fn client(&self) -> Result<zomato::Client, anyhow::Error> {
+        Ok(self.client_builder()?.build()?)
+    }
+
+    /// Returns the city and restaurant, looking up `name` in aliases if `restaurant` is missing.
+    ///
+    /// `name` which isn't an alias may also be `city/restaurant` or the URL of the restaurant.
+    pub fn resolve(&self, name: &str, restaurant: Option<&str>) -> Result<RestaurantRef, anyhow::Error> {
+        if let Some(restaurant) = restaurant {
+            return Ok(RestaurantRef::new(name, restaurant)?);
+        }
+        if let Some(alias) = self.aliases.get(name) {
+            return Ok(RestaurantRef::new(&*alias.city, &*alias.restaurant)?);
+        }
+        if name.contains('/') {
+            return Ok(name.parse()?);
+        }
+        Err(anyhow!("unknown alias '{}', define it in the config or pass both city and restaurant", name))
+    }
+}
+
+fn default_path() -> Option<PathBuf> {
+    let config_dir = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(std::env::var_os("HOME")?).join(".config"),
+    };
+    Some(config_dir.join("zomato").join("config.toml"))
+}
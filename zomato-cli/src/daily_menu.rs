@@ -0,0 +1,70 @@
+//! The `daily-menu` subcommand.
+
+use std::path::Path;
+use crate::config::Config;
+use crate::sinks::OutputFormat;
+
+/// Prints the daily menu of a restaurant.
+#[derive(clap::Args)]
+pub struct Args {
+    /// Alias defined in the config, URL of the restaurant or city in which it's located.
+    name: String,
+    /// Name of the restaurant as used in the URL, omitted when using an alias.
+    restaurant: Option<String>,
+    /// Output format: table, markdown or html. Overrides the config.
+    #[arg(long)]
+    format: Option<OutputFormat>,
+    /// Reads today's menu aloud using the engine and language from the config.
+    #[cfg(feature = "tts")]
+    #[arg(long)]
+    speak: bool,
+}
+
+pub async fn run(args: Args, config_path: Option<&Path>) -> Result<(), anyhow::Error> {
+    let config = Config::load(config_path)?;
+    let target = config.resolve(&args.name, args.restaurant.as_deref())?;
+    let (city, restaurant) = (&*target.city, &*target.slug);
+    let format = match (args.format, &config.format) {
+        (Some(format), _) => format,
+        (None, Some(format)) => format.parse()?,
+        (None, None) => OutputFormat::default(),
+    };
+
+    let menus = config.client()?.get_daily_menu(city, restaurant).await?;
+    let price_bounds = config.price_bounds();
+    for item in menus.iter().flat_map(|menu| &menu.items) {
+        if let Some(warning) = item.price_warning(&price_bounds) {
+            eprintln!("warning: price '{}' of '{}' looks wrong: {}", item.price, item.description, warning);
+        }
+    }
+
+    #[cfg(feature = "tts")]
+    if args.speak {
+        return speak(&config, restaurant, menus.first());
+    }
+
+    print!("{}", format.render(&menus)?);
+    Ok(())
+}
+
+#[cfg(feature = "tts")]
+fn speak(config: &Config, restaurant: &str, menu: Option<&zomato::Menu>) -> Result<(), anyhow::Error> {
+    use anyhow::anyhow;
+    use zomato::tts::{Espeak, Festival, Pico2Wave, SpeechTemplate, TtsEngine};
+
+    let menu = match menu {
+        Some(menu) => menu,
+        None => return Ok(()),
+    };
+    let language = config.language.clone();
+    let engine: Box<dyn TtsEngine> = match config.tts.as_deref().unwrap_or("espeak") {
+        "festival" => Box::new(Festival { language }),
+        "espeak" => Box::new(Espeak { language, ..Default::default() }),
+        "pico2wave" => Box::new(Pico2Wave { language }),
+        other => return Err(anyhow!("unknown text-to-speech engine '{}', use festival, espeak or pico2wave", other)),
+    };
+    // Slugs use dashes instead of spaces which would be spoken weirdly
+    let text = SpeechTemplate::default().render_to_string(&restaurant.replace('-', " "), menu);
+    engine.speak(&text, &Default::default())?;
+    Ok(())
+}
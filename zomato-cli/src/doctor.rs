@@ -0,0 +1,47 @@
+//! The `doctor` subcommand.
+
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use anyhow::Context;
+use zomato::layout::read_records;
+
+/// Lists changes of the layout of Zomato pages recorded by `serve --layout-log`.
+///
+/// Each record shows the markers which appeared (+) or disappeared (-) since the previous one.
+#[derive(clap::Args)]
+pub struct Args {
+    /// Path to the layout log.
+    #[arg(long)]
+    layout_log: PathBuf,
+}
+
+pub fn run(args: Args) -> Result<(), anyhow::Error> {
+    let records = read_records(&args.layout_log).with_context(|| format!("failed to read {}", args.layout_log.display()))?;
+    if records.is_empty() {
+        println!("No layouts recorded yet");
+        return Ok(());
+    }
+
+    let mut previous = None::<BTreeSet<&str>>;
+    for record in &records {
+        let markers = record.markers.iter().map(String::as_str).collect::<BTreeSet<_>>();
+        println!("{} layout {} at {}", chrono::DateTime::<chrono::Utc>::from(record.timestamp).format("%Y-%m-%dT%H:%M:%SZ"), record.generation, record.url);
+        println!("  sample SHA-256 {}", record.sample_hash);
+        match &previous {
+            Some(previous) => {
+                for marker in markers.difference(previous) {
+                    println!("  + {}", marker);
+                }
+                for marker in previous.difference(&markers) {
+                    println!("  - {}", marker);
+                }
+            },
+            None => println!("  first recorded layout with {} markers", markers.len()),
+        }
+        previous = Some(markers);
+    }
+    if records.len() > 1 {
+        println!("Recorded {} layout changes, check that the menus are still parsed correctly", records.len() - 1);
+    }
+    Ok(())
+}
@@ -0,0 +1,73 @@
+//! The `export` subcommand.
+
+use std::path::{Path, PathBuf};
+use anyhow::{bail, Context};
+use zomato::export::{feed, ical};
+use zomato::history::History;
+use zomato::RestaurantRef;
+use crate::config::Config;
+
+/// Generates a calendar or feed of a restaurant from the history.
+///
+/// The output only changes when the stored menus change, so it's safe to run this after every
+/// update of the history. Days which are not business days according to the calendar in the
+/// config are skipped.
+#[derive(clap::Args)]
+pub struct Args {
+    /// Restaurant in the form `city/restaurant` or its URL.
+    restaurant: String,
+    /// Output format: `ics` or `atom`.
+    #[arg(long, default_value = "ics")]
+    format: String,
+    /// Human-readable name of the restaurant, defaults to the name used in the URL.
+    #[arg(long)]
+    name: Option<String>,
+    /// File to write, printed to stdout if missing. The file is left untouched if the content didn't change.
+    #[arg(long)]
+    output: Option<PathBuf>,
+    /// Path to the history database.
+    #[arg(long, env = "ZOMATO_DB")]
+    db: PathBuf,
+}
+
+pub fn run(args: Args, config_path: Option<&Path>) -> Result<(), anyhow::Error> {
+    let calendar = Config::load(config_path)?.business_calendar()?;
+    let target = args.restaurant.parse::<RestaurantRef>()?;
+    let (city, restaurant) = (&*target.city, &*target.slug);
+    let name = args.name.as_deref().unwrap_or(restaurant);
+    let history = History::open(&args.db).with_context(|| format!("failed to open {}", args.db.display()))?;
+    let mut menus = history.dated_menus(city, restaurant)?;
+    menus.retain(|menu| calendar.is_business_day(menu.date));
+
+    let content = match &*args.format {
+        "ics" => ical::dated_to_ics(name, &menus),
+        "atom" => feed::dated_to_atom(&menus, name),
+        _ => bail!("unknown format '{}', supported formats: ics, atom", args.format),
+    };
+
+    match &args.output {
+        Some(path) => write_if_changed(path, &content).with_context(|| format!("failed to write {}", path.display())),
+        None => {
+            print!("{}", content);
+            Ok(())
+        },
+    }
+}
+
+/// Atomically replaces the file unless it already has the content.
+///
+/// Keeping the file intact avoids needless downloads by clients checking its modification time.
+fn write_if_changed(path: &Path, content: &str) -> Result<(), anyhow::Error> {
+    match std::fs::read(path) {
+        Ok(old) if old == content.as_bytes() => return Ok(()),
+        Ok(_) => (),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => (),
+        Err(error) => return Err(error.into()),
+    }
+
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    std::fs::write(&tmp, content)?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
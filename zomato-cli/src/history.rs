@@ -0,0 +1,119 @@
+//! The `history` subcommand.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use anyhow::{anyhow, Context};
+use chrono::{Duration, Local, NaiveDate};
+use zomato::history::{History, PriceRecord};
+use zomato::PriceBounds;
+use crate::config::Config;
+
+/// Prints dishes a restaurant offered in the past.
+#[derive(clap::Args)]
+pub struct Args {
+    /// Name of the restaurant as used in the URL, optionally prefixed with the city (`city/restaurant`).
+    restaurant: String,
+    /// Only show dishes offered since given date (`YYYY-MM-DD`) or duration ago (e.g. `30d`, `8w`).
+    #[arg(long, value_parser = parse_since)]
+    since: Option<NaiveDate>,
+    /// Only show dishes containing this text in their descriptions.
+    #[arg(long)]
+    dish: Option<String>,
+    /// Print how often each dish was offered instead of listing the occurrences.
+    #[arg(long)]
+    stats: bool,
+    /// Path to the history database.
+    #[arg(long, env = "ZOMATO_DB")]
+    db: PathBuf,
+}
+
+pub fn run(args: Args, config_path: Option<&Path>) -> Result<(), anyhow::Error> {
+    let price_bounds = Config::load(config_path)?.price_bounds();
+    let (city, restaurant) = match args.restaurant.split_once('/') {
+        Some((city, restaurant)) => (Some(city), restaurant),
+        None => (None, &*args.restaurant),
+    };
+    let history = History::open(&args.db).with_context(|| format!("failed to open {}", args.db.display()))?;
+    let records = history
+        .price_history(args.dish.as_deref().unwrap_or(""))?
+        .into_iter()
+        .filter(|record| record.restaurant == restaurant && city.is_none_or(|city| record.city == city))
+        .filter(|record| args.since.is_none_or(|since| record.date >= since))
+        .collect::<Vec<_>>();
+
+    if records.is_empty() {
+        eprintln!("No dishes found");
+    } else if args.stats {
+        print_stats(&records, &price_bounds);
+    } else {
+        print_occurrences(&records);
+    }
+    Ok(())
+}
+
+fn print_occurrences(records: &[PriceRecord]) {
+    let width = records.iter().map(|record| record.description.chars().count()).max().unwrap_or(0);
+    for record in records {
+        println!("{}  {:width$}  {}", record.date, record.description, record.price, width = width);
+    }
+}
+
+struct Frequency {
+    count: usize,
+    first_seen: NaiveDate,
+    last_seen: NaiveDate,
+    price_sum: f64,
+    prices: usize,
+}
+
+fn print_stats(records: &[PriceRecord], price_bounds: &PriceBounds) {
+    let mut frequencies = BTreeMap::<&str, Frequency>::new();
+    for record in records {
+        let frequency = frequencies.entry(&record.description).or_insert(Frequency {
+            count: 0,
+            first_seen: record.date,
+            last_seen: record.date,
+            price_sum: 0.0,
+            prices: 0,
+        });
+        frequency.count += 1;
+        frequency.first_seen = frequency.first_seen.min(record.date);
+        frequency.last_seen = frequency.last_seen.max(record.date);
+        if let Some(price) = record.price.parse::<zomato::Price>().ok().filter(|price| price_bounds.is_plausible(price)) {
+            frequency.price_sum += price.amount;
+            frequency.prices += 1;
+        }
+    }
+
+    let mut frequencies = frequencies.into_iter().collect::<Vec<_>>();
+    // stable sort keeps the dishes with the same count ordered by description
+    frequencies.sort_by_key(|(_, frequency)| std::cmp::Reverse(frequency.count));
+
+    let width = frequencies.iter().map(|(description, _)| description.chars().count()).max().unwrap_or(0);
+    println!("{:>5}  {:10}  {:10}  {:width$}  average price", "count", "first seen", "last seen", "dish", width = width);
+    for (description, frequency) in frequencies {
+        print!("{:>5}  {}  {}  {:width$}", frequency.count, frequency.first_seen, frequency.last_seen, description, width = width);
+        if frequency.prices > 0 {
+            print!("  {:.2}", frequency.price_sum / frequency.prices as f64);
+        }
+        println!();
+    }
+}
+
+fn parse_since(value: &str) -> Result<NaiveDate, anyhow::Error> {
+    if let Ok(date) = value.parse() {
+        return Ok(date);
+    }
+    let split = value.len() - value.chars().last().map_or(0, char::len_utf8);
+    let (count, unit) = value.split_at(split);
+    let count = count.parse::<i64>().map_err(|_| anyhow!("expected date or duration such as 30d"))?;
+    let days = match unit {
+        "d" => Some(count),
+        "w" => count.checked_mul(7),
+        _ => return Err(anyhow!("unknown unit '{}', expected d or w", unit)),
+    };
+    days
+        .and_then(Duration::try_days)
+        .and_then(|duration| Local::now().date_naive().checked_sub_signed(duration))
+        .ok_or_else(|| anyhow!("the duration {} is too long", value))
+}
@@ -0,0 +1,94 @@
+//! Command line interface to the zomato crate.
+
+#[cfg(feature = "tui")]
+mod browse;
+mod completions;
+mod config;
+mod daily_menu;
+#[cfg(feature = "email")]
+mod digest;
+mod doctor;
+mod export;
+mod history;
+mod man;
+#[cfg(feature = "notify")]
+mod notify;
+mod pipeline;
+mod replay;
+#[cfg(any(feature = "telegram", feature = "matrix"))]
+mod send;
+#[cfg(feature = "serve")]
+mod serve;
+mod sinks;
+#[cfg(feature = "tui")]
+mod tui;
+mod url;
+mod weekly;
+
+use clap::{Parser, Subcommand};
+
+/// Retrieves daily menus from zomato.com and queries their history.
+#[derive(Parser)]
+#[command(name = "zomato", version)]
+struct Args {
+    /// Path to the config file, ~/.config/zomato/config.toml by default.
+    #[arg(long, global = true, env = "ZOMATO_CONFIG")]
+    config: Option<std::path::PathBuf>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    #[cfg(feature = "tui")]
+    Browse(browse::Args),
+    Completions(completions::Args),
+    DailyMenu(daily_menu::Args),
+    #[cfg(feature = "email")]
+    Digest(digest::Args),
+    Doctor(doctor::Args),
+    Export(export::Args),
+    History(history::Args),
+    Man(man::Args),
+    #[cfg(feature = "notify")]
+    Notify(notify::Args),
+    Pipeline(pipeline::Args),
+    Replay(replay::Args),
+    #[cfg(any(feature = "telegram", feature = "matrix"))]
+    Send(send::Args),
+    #[cfg(feature = "serve")]
+    Serve(serve::Args),
+    #[cfg(feature = "tui")]
+    Tui(tui::Args),
+    Url(url::Args),
+    Weekly(weekly::Args),
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<(), anyhow::Error> {
+    let args = Args::parse();
+    match args.command {
+        #[cfg(feature = "tui")]
+        Command::Browse(command_args) => browse::run(command_args, args.config.as_deref()).await,
+        Command::Completions(args) => completions::run(args),
+        Command::DailyMenu(command_args) => daily_menu::run(command_args, args.config.as_deref()).await,
+        #[cfg(feature = "email")]
+        Command::Digest(command_args) => digest::run(command_args, args.config.as_deref()).await,
+        Command::Doctor(args) => doctor::run(args),
+        Command::Export(command_args) => export::run(command_args, args.config.as_deref()),
+        Command::History(command_args) => history::run(command_args, args.config.as_deref()),
+        Command::Man(args) => man::run(args),
+        #[cfg(feature = "notify")]
+        Command::Notify(command_args) => notify::run(command_args, args.config.as_deref()).await,
+        Command::Pipeline(command_args) => pipeline::run(command_args, args.config.as_deref()).await,
+        Command::Replay(args) => replay::run(args).await,
+        #[cfg(any(feature = "telegram", feature = "matrix"))]
+        Command::Send(command_args) => send::run(command_args, args.config.as_deref()).await,
+        #[cfg(feature = "serve")]
+        Command::Serve(command_args) => serve::run(command_args, args.config.as_deref()).await,
+        #[cfg(feature = "tui")]
+        Command::Tui(command_args) => tui::run(command_args, args.config.as_deref()).await,
+        Command::Url(command_args) => url::run(command_args, args.config.as_deref()),
+        Command::Weekly(command_args) => weekly::run(command_args, args.config.as_deref()).await,
+    }
+}
@@ -0,0 +1,27 @@
+//! The `man` subcommand.
+
+use std::path::PathBuf;
+use anyhow::Context;
+use clap::CommandFactory;
+
+/// Prints the manual page in roff format.
+///
+/// For example, `zomato man | man -l -` shows it.
+#[derive(clap::Args)]
+pub struct Args {
+    /// Write the pages of the command and all subcommands into this directory instead.
+    #[arg(long)]
+    dir: Option<PathBuf>,
+}
+
+pub fn run(args: Args) -> Result<(), anyhow::Error> {
+    let command = crate::Args::command();
+    match args.dir {
+        Some(dir) => {
+            std::fs::create_dir_all(&dir).with_context(|| format!("failed to create {}", dir.display()))?;
+            clap_mangen::generate_to(command, &dir).with_context(|| format!("failed to write manual pages into {}", dir.display()))?;
+        },
+        None => clap_mangen::Man::new(command).render(&mut std::io::stdout()).context("failed to write the manual page")?,
+    }
+    Ok(())
+}
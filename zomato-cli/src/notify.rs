@@ -0,0 +1,31 @@
+//! The `notify` subcommand.
+
+use std::path::Path;
+use zomato::sink::notify::DesktopNotifier;
+use crate::config::Config;
+
+/// Shows today's menu as a desktop notification.
+///
+/// Suitable for running from a timer in the morning.
+#[derive(clap::Args)]
+pub struct Args {
+    /// City in which the restaurant is located, as used in the URL.
+    city: String,
+    /// Name of the restaurant as used in the URL.
+    restaurant: String,
+    /// Human-readable name of the restaurant shown in the notification.
+    #[arg(long)]
+    name: Option<String>,
+}
+
+pub async fn run(args: Args, config_path: Option<&Path>) -> Result<(), anyhow::Error> {
+    let client = Config::load(config_path)?.client()?;
+    let menus = client.get_daily_menu(&args.city, &args.restaurant).await?;
+    // Slugs use dashes instead of spaces
+    let name = match args.name {
+        Some(name) => name,
+        None => args.restaurant.replace('-', " "),
+    };
+    DesktopNotifier::new().notify(&name, menus.first())?;
+    Ok(())
+}
@@ -0,0 +1,180 @@
+//! The `pipeline` subcommand.
+//!
+//! Pipelines are defined in a JSON file:
+//!
+//! ```json
+//! {
+//!     "pipelines": {
+//!         "vegetarian-lunch": {
+//!             "restaurants": ["bratislava/u-zlateho-bazanta"],
+//!             "filters": [{ "exclude": ["rezeň"], "max_price": 7.0 }],
+//!             "transforms": ["vegetarian", "drop-empty"],
+//!             "format": "markdown",
+//!             "sinks": ["stdout", "event-log:lunch.jsonl"]
+//!         }
+//!     }
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use anyhow::{anyhow, bail, Context};
+use serde_derive::Deserialize;
+use zomato::pipeline::{Pipeline, Transform, TransformFuture};
+use zomato::{DietaryHint, Menu, MenuQuery, RestaurantRef};
+use crate::sinks::{OutputFormat, SinkSpec};
+
+/// Runs named pipelines fetching, filtering and delivering menus.
+#[derive(clap::Args)]
+pub struct Args {
+    /// Path to the JSON file defining the pipelines.
+    #[arg(long, env = "ZOMATO_PIPELINES")]
+    config: PathBuf,
+    /// Pipelines to run, all of them if none are given.
+    names: Vec<String>,
+    /// Keep running the pipelines every given number of seconds.
+    #[arg(long)]
+    interval: Option<u64>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Config {
+    pipelines: BTreeMap<String, PipelineConfig>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PipelineConfig {
+    /// `city/restaurant` or the URL of the restaurant
+    restaurants: Vec<String>,
+    #[serde(default)]
+    filters: Vec<FilterConfig>,
+    #[serde(default)]
+    transforms: Vec<String>,
+    format: Option<String>,
+    sinks: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FilterConfig {
+    #[serde(default)]
+    include: Vec<String>,
+    #[serde(default)]
+    exclude: Vec<String>,
+    max_price: Option<f64>,
+}
+
+impl FilterConfig {
+    fn to_query(&self) -> MenuQuery {
+        let mut query = MenuQuery::new();
+        for keyword in &self.include {
+            query = query.include(keyword);
+        }
+        for keyword in &self.exclude {
+            query = query.exclude(keyword);
+        }
+        if let Some(max_price) = self.max_price {
+            query = query.max_price(max_price);
+        }
+        query
+    }
+}
+
+impl PipelineConfig {
+    fn build(&self, name: &str) -> Result<Pipeline, anyhow::Error> {
+        let mut pipeline = Pipeline::new(name);
+        for restaurant in &self.restaurants {
+            let restaurant = restaurant.parse::<RestaurantRef>()?;
+            pipeline = pipeline.restaurant(&restaurant.city, &restaurant.slug);
+        }
+        for filter in &self.filters {
+            pipeline = pipeline.transform(filter.to_query());
+        }
+        for transform in &self.transforms {
+            pipeline = match &**transform {
+                "vegetarian" => pipeline.transform(Vegetarian),
+                "drop-empty" => pipeline.transform(DropEmpty),
+                _ => bail!("unknown transform '{}', supported transforms: vegetarian, drop-empty", transform),
+            };
+        }
+        let format = self.format.as_deref().map(str::parse).transpose()?.unwrap_or(OutputFormat::Table);
+        for sink in &self.sinks {
+            pipeline = pipeline.sink(sink.parse::<SinkSpec>()?.open(format)?);
+        }
+        Ok(pipeline)
+    }
+}
+
+/// Keeps only the items which are likely vegetarian.
+struct Vegetarian;
+
+impl Transform for Vegetarian {
+    fn apply(&self, mut menus: Vec<Menu>) -> TransformFuture<'_> {
+        for menu in &mut menus {
+            menu.items.retain(|item| item.dietary_hints().contains(&DietaryHint::Vegetarian));
+        }
+        Box::pin(async move { Ok(menus) })
+    }
+}
+
+/// Removes menus without any items.
+struct DropEmpty;
+
+impl Transform for DropEmpty {
+    fn apply(&self, mut menus: Vec<Menu>) -> TransformFuture<'_> {
+        menus.retain(|menu| !menu.items.is_empty());
+        Box::pin(async move { Ok(menus) })
+    }
+}
+
+pub async fn run(args: Args, config_path: Option<&Path>) -> Result<(), anyhow::Error> {
+    let config = std::fs::read_to_string(&args.config).with_context(|| format!("failed to read {}", args.config.display()))?;
+    let mut config = serde_json::from_str::<Config>(&config).with_context(|| format!("invalid config {}", args.config.display()))?;
+
+    let selected = if args.names.is_empty() {
+        std::mem::take(&mut config.pipelines).into_iter().collect::<Vec<_>>()
+    } else {
+        args.names
+            .iter()
+            .map(|name| config.pipelines.remove(name).map(|pipeline| (name.clone(), pipeline)).ok_or_else(|| anyhow!("unknown pipeline '{}'", name)))
+            .collect::<Result<Vec<_>, _>>()?
+    };
+    let pipelines = selected
+        .iter()
+        .map(|(name, pipeline)| pipeline.build(name).with_context(|| format!("invalid pipeline '{}'", name)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let client = crate::config::Config::load(config_path)?.client()?;
+    loop {
+        let mut failed = false;
+        for pipeline in &pipelines {
+            let report = pipeline.run(&client).await;
+            for failure in &report.failed {
+                failed = true;
+                eprintln!("{}: failed to process {}/{}: {}", pipeline.name(), failure.city, failure.restaurant, failure.error);
+            }
+            for undelivered in &report.undelivered {
+                failed = true;
+                eprintln!("{}: failed to deliver {}/{} to {}: {}", pipeline.name(), undelivered.city, undelivered.restaurant, selected_sink(&selected, pipeline.name(), undelivered.sink), undelivered.error);
+            }
+        }
+
+        match args.interval {
+            Some(interval) => tokio::time::sleep(Duration::from_secs(interval)).await,
+            None if failed => bail!("some pipelines failed"),
+            None => return Ok(()),
+        }
+    }
+}
+
+/// Returns the specification of the sink for error messages.
+fn selected_sink<'a>(selected: &'a [(String, PipelineConfig)], pipeline: &str, sink: usize) -> &'a str {
+    selected
+        .iter()
+        .find(|(name, _)| name == pipeline)
+        .and_then(|(_, config)| config.sinks.get(sink))
+        .map_or("unknown sink", String::as_str)
+}
@@ -0,0 +1,48 @@
+//! The `replay` subcommand.
+
+use std::path::PathBuf;
+use anyhow::{bail, Context};
+use zomato::sink::event_log::read_events;
+use zomato::sink::{replay_events, Sink};
+use crate::sinks::{self, OutputFormat, SinkSpec};
+
+/// Delivers events recorded in an event log to sinks again.
+#[derive(clap::Args)]
+pub struct Args {
+    /// Path to the event log.
+    #[arg(long)]
+    from: PathBuf,
+    /// Sink to deliver the events to (`stdout` or `event-log:PATH`), may be repeated.
+    #[arg(long, required = true)]
+    sink: Vec<SinkSpec>,
+    /// Only print the events that would be delivered.
+    #[arg(long)]
+    dry_run: bool,
+}
+
+pub async fn run(args: Args) -> Result<(), anyhow::Error> {
+    let events = read_events(&args.from).with_context(|| format!("failed to open {}", args.from.display()))?;
+
+    if args.dry_run {
+        for event in events {
+            let event = event?;
+            for sink in &args.sink {
+                println!("{} {}/{} -> {}", sinks::timestamp(&event), event.city, event.restaurant, sink);
+            }
+        }
+        return Ok(());
+    }
+
+    let sinks = args.sink.iter().map(|sink| sink.open(OutputFormat::Table)).collect::<Result<Vec<_>, _>>()?;
+    let sink_refs = sinks.iter().map(|sink| &**sink as &dyn Sink).collect::<Vec<_>>();
+    let report = replay_events(events, &sink_refs).await?;
+    for failure in &report.failed {
+        let kind = if failure.error.is_retriable() { "temporarily" } else { "permanently" };
+        eprintln!("Event {} {} failed to be delivered to {}: {}", failure.event + 1, kind, args.sink[failure.sink], failure.error);
+    }
+    eprintln!("Replayed {} events, {} deliveries failed", report.replayed, report.failed.len());
+    if !report.failed.is_empty() {
+        bail!("some deliveries failed");
+    }
+    Ok(())
+}
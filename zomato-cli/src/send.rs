@@ -0,0 +1,96 @@
+//! The `send` subcommand.
+
+use std::path::Path;
+use clap::Subcommand;
+use crate::config::Config;
+
+/// Sends today's menu to a chat.
+#[derive(clap::Args)]
+pub struct Args {
+    #[command(subcommand)]
+    target: Target,
+}
+
+#[derive(Subcommand)]
+enum Target {
+    #[cfg(feature = "matrix")]
+    Matrix(MatrixArgs),
+    #[cfg(feature = "telegram")]
+    Telegram(TelegramArgs),
+}
+
+/// Restaurant whose menu is sent.
+#[derive(clap::Args)]
+struct Restaurant {
+    /// City in which the restaurant is located, as used in the URL.
+    city: String,
+    /// Name of the restaurant as used in the URL.
+    restaurant: String,
+    /// Human-readable name of the restaurant shown in the message.
+    #[arg(long)]
+    name: Option<String>,
+}
+
+impl Restaurant {
+    async fn fetch(&self, client: &zomato::Client) -> Result<(String, Vec<zomato::Menu>), anyhow::Error> {
+        let menus = client.get_daily_menu(&self.city, &self.restaurant).await?;
+        // Slugs use dashes instead of spaces
+        let name = match &self.name {
+            Some(name) => name.clone(),
+            None => self.restaurant.replace('-', " "),
+        };
+        Ok((name, menus))
+    }
+}
+
+/// Posts the menu to a Matrix room.
+#[cfg(feature = "matrix")]
+#[derive(clap::Args)]
+struct MatrixArgs {
+    /// Base URL of the homeserver, e.g. https://matrix.org.
+    #[arg(long)]
+    homeserver: String,
+    /// Room to post the menu to, e.g. !abcdef:matrix.org.
+    #[arg(long)]
+    room_id: String,
+    /// Access token of a user who joined the room.
+    #[arg(long, env = "MATRIX_ACCESS_TOKEN", hide_env_values = true)]
+    token: String,
+    #[command(flatten)]
+    restaurant: Restaurant,
+}
+
+/// Sends the menu using a Telegram bot.
+#[cfg(feature = "telegram")]
+#[derive(clap::Args)]
+struct TelegramArgs {
+    /// Chat to send the menu to, numeric id or @channelusername.
+    #[arg(long)]
+    chat_id: String,
+    /// Token of the bot obtained from @BotFather.
+    #[arg(long, env = "TELEGRAM_BOT_TOKEN", hide_env_values = true)]
+    token: String,
+    #[command(flatten)]
+    restaurant: Restaurant,
+}
+
+pub async fn run(args: Args, config_path: Option<&Path>) -> Result<(), anyhow::Error> {
+    let client = Config::load(config_path)?.client()?;
+    match args.target {
+        #[cfg(feature = "matrix")]
+        Target::Matrix(args) => {
+            let (name, menus) = args.restaurant.fetch(&client).await?;
+            zomato::sink::matrix::MatrixRoom::new(args.homeserver, args.token, args.room_id)
+                .send(&name, menus.first())
+                .await?;
+        },
+        #[cfg(feature = "telegram")]
+        Target::Telegram(args) => {
+            let (name, menus) = args.restaurant.fetch(&client).await?;
+            zomato::sink::telegram::TelegramBot::new(args.token, args.chat_id)
+                .send(&name, menus.first())
+                .await?;
+        },
+    }
+    Ok(())
+}
@@ -0,0 +1,72 @@
+//! The `serve` subcommand.
+
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use zomato::layout::LayoutLog;
+use crate::config::Config;
+use zomato::server::{ImageProxy, Role, Server};
+use zomato::RestaurantRef;
+
+/// Serves the menus over HTTP at `/menu/{city}/{restaurant}`.
+#[derive(clap::Args)]
+pub struct Args {
+    /// Address to listen on.
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    bind: SocketAddr,
+    /// How many seconds the fetched menus are cached.
+    #[arg(long, default_value_t = zomato::server::DEFAULT_CACHE_TTL.as_secs())]
+    cache_ttl: u64,
+    /// Directory in which the fetched menus are persisted across restarts.
+    #[cfg(feature = "binary-cache")]
+    #[arg(long)]
+    cache_dir: Option<std::path::PathBuf>,
+    /// Tokens allowed to manage restaurants and force refreshes, separated by commas.
+    #[arg(long = "admin-token", env = "ZOMATO_ADMIN_TOKENS", value_delimiter = ',', hide_env_values = true)]
+    admin_tokens: Vec<String>,
+    /// Tokens required to read menus, separated by commas. Reading is public if none are set.
+    #[arg(long = "viewer-token", env = "ZOMATO_VIEWER_TOKENS", value_delimiter = ',', hide_env_values = true)]
+    viewer_tokens: Vec<String>,
+    /// Proxy images hosted by Zomato at /img/{hash}, register them using POST /img.
+    #[arg(long)]
+    image_proxy: bool,
+    /// How many seconds the proxied images are cached.
+    #[arg(long, default_value_t = zomato::server::DEFAULT_IMAGE_TTL.as_secs(), requires = "image_proxy")]
+    image_ttl: u64,
+    /// Restaurant to register at startup in the form city/restaurant or its URL, may be repeated.
+    #[arg(long = "restaurant")]
+    restaurants: Vec<String>,
+    /// Record previously unseen layouts of Zomato pages into this file, see `zomato doctor`.
+    #[arg(long)]
+    layout_log: Option<std::path::PathBuf>,
+}
+
+pub async fn run(args: Args, config_path: Option<&Path>) -> Result<(), anyhow::Error> {
+    let mut client = Config::load(config_path)?.client_builder()?;
+    if let Some(path) = &args.layout_log {
+        client = client.layout_log(Arc::new(LayoutLog::open(path)?));
+    }
+    let mut server = Server::new(client.build()?)
+        .cache_ttl(Duration::from_secs(args.cache_ttl));
+    #[cfg(feature = "binary-cache")]
+    if let Some(dir) = &args.cache_dir {
+        server = server.disk_cache(zomato::cache::DiskCache::open(dir)?);
+    }
+    if args.image_proxy {
+        server = server.image_proxy(ImageProxy::new()?.ttl(Duration::from_secs(args.image_ttl)));
+    }
+    for token in args.viewer_tokens {
+        server = server.token(token, Role::Viewer);
+    }
+    for token in args.admin_tokens {
+        server = server.token(token, Role::Admin);
+    }
+    for restaurant in &args.restaurants {
+        let restaurant = restaurant.parse::<RestaurantRef>()?;
+        server.add_restaurant(&restaurant.city, &restaurant.slug)?;
+    }
+    eprintln!("Listening on http://{}", args.bind);
+    server.run(args.bind).await?;
+    Ok(())
+}
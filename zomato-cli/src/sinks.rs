@@ -0,0 +1,169 @@
+//! Sinks configured on the command line.
+
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+use anyhow::{anyhow, Context};
+use zomato::format::TableFormatter;
+use zomato::sink::event_log::EventLog;
+use zomato::sink::webhook::{PayloadFormat, Webhook};
+use zomato::sink::{DeliveryError, MenuEvent, Receipt, Sink, SinkFuture};
+
+#[cfg(not(feature = "mqtt"))]
+const SUPPORTED_SINKS: &str = "stdout, event-log:PATH, webhook:URL";
+#[cfg(feature = "mqtt")]
+const SUPPORTED_SINKS: &str = "stdout, event-log:PATH, webhook:URL, mqtt:HOST[:PORT][/TOPIC]";
+
+/// Sink specification in the form `kind[:argument]`.
+#[derive(Clone)]
+pub enum SinkSpec {
+    Stdout,
+    EventLog(PathBuf),
+    Webhook(String),
+    #[cfg(feature = "mqtt")]
+    Mqtt { host: String, port: u16, topic: Option<String> },
+}
+
+impl SinkSpec {
+    /// Creates the sink, text sinks render the menus in given format.
+    pub fn open(&self, format: OutputFormat) -> Result<Box<dyn Sink>, anyhow::Error> {
+        match self {
+            SinkSpec::Stdout => Ok(Box::new(Stdout { format })),
+            SinkSpec::EventLog(path) => {
+                let log = EventLog::open(path).with_context(|| format!("failed to open event log {}", path.display()))?;
+                Ok(Box::new(log))
+            },
+            SinkSpec::Webhook(url) => {
+                let mut webhook = Webhook::new(url.clone());
+                if let Ok(format) = std::env::var("ZOMATO_WEBHOOK_FORMAT") {
+                    webhook = webhook.payload_format(format.parse::<PayloadFormat>().context("invalid ZOMATO_WEBHOOK_FORMAT")?);
+                }
+                match std::env::var_os("ZOMATO_WEBHOOK_SECRET") {
+                    Some(secret) => {
+                        let secret = secret.into_string().map_err(|_| anyhow!("ZOMATO_WEBHOOK_SECRET is not valid UTF-8"))?;
+                        Ok(Box::new(webhook.secret(secret)))
+                    },
+                    None => Ok(Box::new(webhook)),
+                }
+            },
+            #[cfg(feature = "mqtt")]
+            SinkSpec::Mqtt { host, port, topic } => {
+                let mut publisher = zomato::sink::mqtt::MqttPublisher::new(host.clone(), *port);
+                if let Some(topic) = topic {
+                    publisher = publisher.topic(topic.clone());
+                }
+                if let Ok(user) = std::env::var("ZOMATO_MQTT_USER") {
+                    publisher = publisher.credentials(user, std::env::var("ZOMATO_MQTT_PASSWORD").unwrap_or_default());
+                }
+                Ok(Box::new(publisher))
+            },
+        }
+    }
+}
+
+impl FromStr for SinkSpec {
+    type Err = anyhow::Error;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let (kind, argument) = match spec.split_once(':') {
+            Some((kind, argument)) => (kind, Some(argument)),
+            None => (spec, None),
+        };
+        match (kind, argument) {
+            ("stdout", None) => Ok(SinkSpec::Stdout),
+            ("event-log", Some(path)) => Ok(SinkSpec::EventLog(path.into())),
+            ("event-log", None) => Err(anyhow!("missing path, use event-log:PATH")),
+            ("webhook", Some(url)) => Ok(SinkSpec::Webhook(url.to_owned())),
+            ("webhook", None) => Err(anyhow!("missing URL, use webhook:URL")),
+            #[cfg(feature = "mqtt")]
+            ("mqtt", Some(address)) => {
+                let (address, topic) = match address.split_once('/') {
+                    Some((address, topic)) => (address, Some(topic.to_owned())),
+                    None => (address, None),
+                };
+                let (host, port) = match address.rsplit_once(':') {
+                    Some((host, port)) => (host, port.parse().with_context(|| format!("invalid port '{}'", port))?),
+                    None => (address, 1883),
+                };
+                Ok(SinkSpec::Mqtt { host: host.to_owned(), port, topic })
+            },
+            #[cfg(feature = "mqtt")]
+            ("mqtt", None) => Err(anyhow!("missing broker, use mqtt:HOST[:PORT][/TOPIC]")),
+            _ => Err(anyhow!("unknown sink '{}', supported sinks: {}", spec, SUPPORTED_SINKS)),
+        }
+    }
+}
+
+impl fmt::Display for SinkSpec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SinkSpec::Stdout => write!(f, "stdout"),
+            SinkSpec::EventLog(path) => write!(f, "event-log:{}", path.display()),
+            SinkSpec::Webhook(url) => write!(f, "webhook:{}", url),
+            #[cfg(feature = "mqtt")]
+            SinkSpec::Mqtt { host, port, topic } => {
+                write!(f, "mqtt:{}:{}", host, port)?;
+                match topic {
+                    Some(topic) => write!(f, "/{}", topic),
+                    None => Ok(()),
+                }
+            },
+        }
+    }
+}
+
+/// Format in which text sinks render the menus.
+#[derive(Copy, Clone, Default)]
+pub enum OutputFormat {
+    #[default]
+    Table,
+    Markdown,
+    Html,
+}
+
+impl OutputFormat {
+    pub fn render(self, menus: &[zomato::Menu]) -> Result<String, fmt::Error> {
+        match self {
+            OutputFormat::Table => {
+                let mut table = String::new();
+                TableFormatter::default().format(menus, &mut table)?;
+                Ok(table)
+            },
+            OutputFormat::Markdown => Ok(zomato::export::markdown::to_markdown(menus)),
+            OutputFormat::Html => Ok(zomato::export::html::to_html(menus)),
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "table" => Ok(OutputFormat::Table),
+            "markdown" => Ok(OutputFormat::Markdown),
+            "html" => Ok(OutputFormat::Html),
+            _ => Err(anyhow!("unknown format '{}', supported formats: table, markdown, html", format)),
+        }
+    }
+}
+
+/// Prints the events in the configured format.
+struct Stdout {
+    format: OutputFormat,
+}
+
+impl Sink for Stdout {
+    fn deliver<'a>(&'a self, event: &'a MenuEvent) -> SinkFuture<'a> {
+        Box::pin(async move {
+            let text = self.format.render(&event.menus).map_err(DeliveryError::permanent)?;
+            println!("{}/{} ({})", event.city, event.restaurant, timestamp(event));
+            print!("{}", text);
+            Ok(Receipt::new())
+        })
+    }
+}
+
+pub fn timestamp(event: &MenuEvent) -> impl fmt::Display {
+    chrono::DateTime::<chrono::Utc>::from(event.timestamp).format("%Y-%m-%dT%H:%M:%SZ")
+}
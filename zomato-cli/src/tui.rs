@@ -0,0 +1,174 @@
+//! The `tui` subcommand.
+
+use std::collections::HashMap;
+use std::path::Path;
+use anyhow::bail;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::Style;
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+use zomato::Menu;
+use crate::browse::MenuPane;
+use crate::config::Config;
+
+const SIDEBAR_TITLE: &str = "Restaurants";
+const HELP: &str = "Tab switch pane  ←/→ day  ↑/↓ move  / filter  c copy  s sort  r refresh  q quit";
+
+/// Browses the menus of all restaurants in the config interactively.
+///
+/// The aliases from the config are listed in a sidebar, the menus of the selected restaurant are
+/// shown as in `browse`. Menus are fetched when the restaurant is first selected and kept until
+/// refreshed.
+#[derive(clap::Args)]
+pub struct Args {
+    /// Aliases to show, all aliases in the config by default.
+    aliases: Vec<String>,
+}
+
+struct Restaurant {
+    alias: String,
+    city: String,
+    restaurant: String,
+}
+
+pub async fn run(args: Args, config_path: Option<&Path>) -> Result<(), anyhow::Error> {
+    let config = Config::load(config_path)?;
+    let restaurants = if args.aliases.is_empty() {
+        config
+            .aliases
+            .iter()
+            .map(|(alias, target)| Restaurant { alias: alias.clone(), city: target.city.clone(), restaurant: target.restaurant.clone() })
+            .collect::<Vec<_>>()
+    } else {
+        args.aliases
+            .iter()
+            .map(|alias| {
+                let target = config.resolve(alias, None)?;
+                Ok(Restaurant { alias: alias.clone(), city: target.city, restaurant: target.slug })
+            })
+            .collect::<Result<Vec<_>, anyhow::Error>>()?
+    };
+    if restaurants.is_empty() {
+        bail!("no restaurants to show, define aliases in the config");
+    }
+    let client = config.client()?;
+
+    let mut app = App {
+        restaurants,
+        sidebar: ListState::default().with_selected(Some(0)),
+        sidebar_focused: false,
+        fetched: HashMap::new(),
+        shown: None,
+        pane: MenuPane::new(String::new(), Vec::new()),
+        status: None,
+    };
+    let mut terminal = ratatui::try_init()?;
+    let result = app.run(&mut terminal, &client).await;
+    ratatui::restore();
+    result
+}
+
+struct App {
+    restaurants: Vec<Restaurant>,
+    sidebar: ListState,
+    sidebar_focused: bool,
+    /// Menus of the restaurants fetched so far, by the index in `restaurants`.
+    ///
+    /// The menus of the restaurant being shown are moved to the pane.
+    fetched: HashMap<usize, Vec<Menu>>,
+    shown: Option<usize>,
+    pane: MenuPane,
+    status: Option<String>,
+}
+
+impl App {
+    async fn run(&mut self, terminal: &mut DefaultTerminal, client: &zomato::Client) -> Result<(), anyhow::Error> {
+        self.show_selected(terminal, client, false).await?;
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            let key = match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => key,
+                _ => continue,
+            };
+            self.status = None;
+            if self.pane.handle_filter_key(key) {
+                continue;
+            }
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Tab | KeyCode::BackTab => self.sidebar_focused = !self.sidebar_focused,
+                KeyCode::Left | KeyCode::Char('h') => self.pane.previous_tab(),
+                KeyCode::Right | KeyCode::Char('l') => self.pane.next_tab(),
+                KeyCode::Up | KeyCode::Char('k') if self.sidebar_focused => {
+                    self.sidebar.select_previous();
+                    self.show_selected(terminal, client, false).await?;
+                },
+                KeyCode::Down | KeyCode::Char('j') if self.sidebar_focused => {
+                    // `select_next` doesn't know the number of items before rendering
+                    let next = self.selected() + 1;
+                    if next < self.restaurants.len() {
+                        self.sidebar.select(Some(next));
+                        self.show_selected(terminal, client, false).await?;
+                    }
+                },
+                KeyCode::Up | KeyCode::Char('k') => self.pane.list.select_previous(),
+                KeyCode::Down | KeyCode::Char('j') => self.pane.list.select_next(),
+                KeyCode::Char('/') => self.pane.start_filter(),
+                KeyCode::Char('c') => self.status = self.pane.copy_selected()?,
+                KeyCode::Char('s') => self.pane.toggle_sort(),
+                KeyCode::Char('r') => self.show_selected(terminal, client, true).await?,
+                _ => (),
+            }
+        }
+    }
+
+    fn selected(&self) -> usize {
+        self.sidebar.selected().unwrap_or(0).min(self.restaurants.len() - 1)
+    }
+
+    /// Shows the menus of the selected restaurant, fetching them if needed or requested.
+    async fn show_selected(&mut self, terminal: &mut DefaultTerminal, client: &zomato::Client, refresh: bool) -> Result<(), anyhow::Error> {
+        if let Some(shown) = self.shown.take() {
+            self.fetched.insert(shown, self.pane.take_menus());
+        }
+        let index = self.selected();
+        let restaurant = &self.restaurants[index];
+        self.pane.title = format!("{} ({}/{})", restaurant.alias, restaurant.city, restaurant.restaurant);
+        if refresh || !self.fetched.contains_key(&index) {
+            self.status = Some(format!("Fetching {}…", restaurant.alias));
+            terminal.draw(|frame| self.draw(frame))?;
+            let restaurant = &self.restaurants[index];
+            match client.get_daily_menu(&restaurant.city, &restaurant.restaurant).await {
+                Ok(menus) => {
+                    self.fetched.insert(index, menus);
+                    self.status = refresh.then(|| format!("Refreshed at {}", chrono::Local::now().format("%H:%M")));
+                },
+                Err(error) => self.status = Some(format!("Fetching failed: {}", error)),
+            }
+        }
+        match self.fetched.remove(&index) {
+            Some(menus) => {
+                self.pane.set_menus(menus);
+                self.shown = Some(index);
+            },
+            None => self.pane.set_menus(Vec::new()),
+        }
+        Ok(())
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main_area, status_area] = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+        let sidebar_width = self.restaurants.iter().map(|restaurant| restaurant.alias.chars().count()).chain([SIDEBAR_TITLE.len()]).max().unwrap_or(0) + 2;
+        let [sidebar_area, menu_area] = Layout::horizontal([Constraint::Length(sidebar_width as u16), Constraint::Min(0)]).areas(main_area);
+
+        let highlight = if self.sidebar_focused { Style::new().reversed() } else { Style::new().bold() };
+        let sidebar = List::new(self.restaurants.iter().map(|restaurant| ListItem::new(restaurant.alias.clone())))
+            .highlight_style(highlight)
+            .block(Block::bordered().title(SIDEBAR_TITLE));
+        frame.render_stateful_widget(sidebar, sidebar_area, &mut self.sidebar);
+
+        self.pane.draw(frame, menu_area, !self.sidebar_focused);
+        frame.render_widget(Paragraph::new(self.pane.status_line(self.status.as_deref(), HELP)), status_area);
+    }
+}
@@ -0,0 +1,48 @@
+//! The `url` subcommand.
+
+use std::path::Path;
+use crate::config::Config;
+
+/// Prints the Zomato URLs of a restaurant or a search.
+///
+/// Handy for sharing links and for checking what is fetched when the menu can't be retrieved.
+#[derive(clap::Args)]
+pub struct Args {
+    /// Alias defined in the config, URL of the restaurant or city in which it's located.
+    name: String,
+    /// Name of the restaurant as used in the URL, omitted when using an alias or searching.
+    restaurant: Option<String>,
+    /// Print the URL of the search results for this query in the city instead.
+    #[arg(long, conflicts_with = "restaurant")]
+    search: Option<String>,
+    /// Print only the URL of this page, without the label.
+    #[arg(long, value_enum, conflicts_with = "search")]
+    only: Option<Page>,
+}
+
+#[derive(Copy, Clone, clap::ValueEnum)]
+enum Page {
+    DailyMenu,
+    Info,
+}
+
+pub fn run(args: Args, config_path: Option<&Path>) -> Result<(), anyhow::Error> {
+    let client = zomato::Client::new()?;
+    if let Some(query) = &args.search {
+        println!("{}", client.search_page_url(&args.name, query, 1)?);
+        return Ok(());
+    }
+
+    let config = Config::load(config_path)?;
+    let target = config.resolve(&args.name, args.restaurant.as_deref())?;
+    let (city, restaurant) = (&*target.city, &*target.slug);
+    match args.only {
+        Some(Page::DailyMenu) => println!("{}", client.daily_menu_url(city, restaurant)?),
+        Some(Page::Info) => println!("{}", client.info_url(city, restaurant)?),
+        None => {
+            println!("daily menu: {}", client.daily_menu_url(city, restaurant)?);
+            println!("info:       {}", client.info_url(city, restaurant)?);
+        },
+    }
+    Ok(())
+}
@@ -0,0 +1,71 @@
+//! The `weekly` subcommand.
+
+use std::path::{Path, PathBuf};
+use anyhow::{bail, Context};
+use zomato::history::digest::WeeklyDigest;
+use zomato::history::History;
+use zomato::RestaurantRef;
+use crate::config::Config;
+use crate::sinks::{OutputFormat, SinkSpec};
+
+/// Summarizes the menus of the past week from the history and delivers them to sinks.
+///
+/// Meant to be run from cron, e.g. `0 14 * * 5` on Friday afternoon.
+#[derive(clap::Args)]
+pub struct Args {
+    /// Restaurant to summarize as `city/restaurant` or its URL, may be repeated. All restaurants in the
+    /// history are summarized if none is given.
+    #[arg(long)]
+    restaurant: Vec<String>,
+    /// Number of summarized days ending today.
+    #[arg(long, default_value_t = 7)]
+    days: u32,
+    /// Count dishes with descriptions at least this similar (0 to 1, e.g. 0.95) as the same dish.
+    #[arg(long, value_name = "MIN_SIMILARITY")]
+    merge_similar: Option<f64>,
+    /// Format of the summary (`table`, `markdown` or `html`).
+    #[arg(long, default_value = "table")]
+    format: OutputFormat,
+    /// Sink to deliver the summaries to, may be repeated.
+    #[arg(long, default_value = "stdout")]
+    sink: Vec<SinkSpec>,
+    /// Path to the history database.
+    #[arg(long, env = "ZOMATO_DB")]
+    db: PathBuf,
+}
+
+pub async fn run(args: Args, config_path: Option<&Path>) -> Result<(), anyhow::Error> {
+    let price_bounds = Config::load(config_path)?.price_bounds();
+    let mut digest = WeeklyDigest::new().days(args.days);
+    if let Some(min_similarity) = args.merge_similar {
+        digest = digest.merge_similar(min_similarity);
+    }
+    for restaurant in &args.restaurant {
+        let restaurant = restaurant.parse::<RestaurantRef>()?;
+        digest = digest.restaurant(&restaurant.city, &restaurant.slug);
+    }
+    let history = History::open(&args.db)
+        .with_context(|| format!("failed to open {}", args.db.display()))?
+        .with_price_bounds(price_bounds);
+    let weeks = digest.build(&history)?;
+    if weeks.is_empty() {
+        eprintln!("No menus recorded in the past {} days", args.days);
+        return Ok(());
+    }
+
+    let sinks = args.sink.iter().map(|sink| sink.open(args.format)).collect::<Result<Vec<_>, _>>()?;
+    let mut failed = false;
+    for week in &weeks {
+        let event = week.to_event();
+        for (sink, spec) in sinks.iter().zip(&args.sink) {
+            if let Err(error) = sink.deliver(&event).await {
+                eprintln!("Failed to deliver summary of {}/{} to {}: {}", week.city, week.restaurant, spec, error);
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        bail!("some deliveries failed");
+    }
+    Ok(())
+}
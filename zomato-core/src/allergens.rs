@@ -0,0 +1,82 @@
+//! Extraction of allergen codes from descriptions of dishes.
+//!
+//! Restaurants in the EU must declare the 14 allergen groups and most Central-European
+//! restaurants do so by appending their numbers to the name of the dish, e.g.
+//! "Vyprážaný syr, hranolky (1,3,7)". The notation varies, we understand codes enclosed in
+//! parentheses, brackets or slashes, optionally prefixed by "A" or a word like "alergény".
+
+use std::ops::Range;
+
+/// Highest allergen number defined by EU regulation 1169/2011.
+const MAX_ALLERGEN: u8 = 14;
+
+const PREFIXES: &[&str] = &["alergény", "alergeny", "allergens", "allergene", "a"];
+
+/// Allergens found in a description.
+pub(crate) struct Allergens {
+    /// Sorted and deduplicated allergen numbers.
+    pub(crate) codes: Vec<u8>,
+    /// Position of the group including the delimiters.
+    pub(crate) range: Range<usize>,
+}
+
+/// Finds the last group of allergen codes in the description.
+pub(crate) fn find(description: &str) -> Option<Allergens> {
+    let mut end = description.len();
+    while let Some(close) = description[..end].rfind([')', ']', '/']) {
+        let open_char = match description.as_bytes()[close] {
+            b')' => '(',
+            b']' => '[',
+            _ => '/',
+        };
+        let open = match description[..close].rfind(open_char) {
+            Some(open) => open,
+            None => {
+                end = close;
+                continue;
+            },
+        };
+        if let Some(codes) = parse_codes(&description[(open + 1)..close]) {
+            return Some(Allergens {
+                codes,
+                range: open..(close + 1),
+            });
+        }
+        end = close;
+    }
+    None
+}
+
+/// Removes the allergen codes from the description.
+pub(crate) fn strip(description: &str, range: Range<usize>) -> String {
+    let before = description[..range.start].trim_end();
+    let after = description[range.end..].trim_start();
+    let mut result = String::with_capacity(description.len());
+    result.push_str(before);
+    if !before.is_empty() && after.starts_with(char::is_alphanumeric) {
+        result.push(' ');
+    }
+    result.push_str(after);
+    result
+}
+
+fn parse_codes(group: &str) -> Option<Vec<u8>> {
+    let mut group = group.trim();
+    let lowercase = group.to_lowercase();
+    if let Some(prefix) = PREFIXES.iter().find(|prefix| lowercase.starts_with(*prefix)) {
+        // The lowercase version of the prefixes has the same length
+        group = group[prefix.len()..].trim_start_matches(|c: char| c == ':' || c.is_whitespace());
+    }
+
+    let mut codes = group
+        .split(|c: char| c == ',' || c == ';' || c == '.' || c.is_whitespace())
+        .filter(|code| !code.is_empty())
+        .map(|code| code.parse::<u8>().ok().filter(|code| (1..=MAX_ALLERGEN).contains(code)))
+        .collect::<Option<Vec<_>>>()?;
+    if codes.is_empty() {
+        return None;
+    }
+    codes.sort_unstable();
+    codes.dedup();
+    Some(codes)
+}
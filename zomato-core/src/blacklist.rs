@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+use crate::{Menu, MenuItem};
+
+/// Dishes which should never be shown.
+///
+/// Some restaurants list filler lines, e.g. "minutky dle nabídky" (meals to order), every day.
+/// The blacklist removes them from the menus before they reach any output, patterns are matched
+/// case-insensitively against the description of the dish and its translations. Restaurants can
+/// have their own patterns and can also keep dishes which would be removed by the global ones.
+///
+/// The `zomato` crate applies the blacklist configured using `ClientBuilder::blacklist` to all
+/// fetched menus.
+///
+/// ```
+/// use zomato_core::DishBlacklist;
+///
+/// let blacklist = DishBlacklist::new()
+///     .dish("hranolky")
+///     .restaurant_dish("u-zlateho-bazanta", "vývar")
+///     .restaurant_allow("u-zlateho-bazanta", "syr");
+///
+/// let mut menus = zomato_core::parse_daily_menu(zomato_core::testing::FIXTURE)?;
+/// blacklist.apply("u-zlateho-bazanta", &mut menus);
+/// assert_eq!(menus[0].items.len(), 2);
+/// assert!(menus[0].items[0].description.contains("rezeň"));
+/// assert!(menus[0].items[1].description.contains("syr, hranolky"));
+///
+/// let mut menus = zomato_core::parse_daily_menu(zomato_core::testing::FIXTURE)?;
+/// blacklist.apply("u-kohuta", &mut menus);
+/// assert_eq!(menus[0].items.len(), 2);
+/// assert!(menus[0].items[0].description.contains("vývar"));
+/// # Ok::<_, zomato_core::Error>(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct DishBlacklist {
+    patterns: Vec<String>,
+    restaurants: HashMap<String, Overrides>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct Overrides {
+    patterns: Vec<String>,
+    allowed: Vec<String>,
+}
+
+impl DishBlacklist {
+    /// Creates blacklist which doesn't remove anything.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Removes dishes containing the pattern from menus of all restaurants.
+    pub fn dish(mut self, pattern: impl AsRef<str>) -> Self {
+        self.patterns.push(pattern.as_ref().to_lowercase());
+        self
+    }
+
+    /// Removes dishes containing the pattern from menus of given restaurant.
+    ///
+    /// The restaurant is identified by its name as used in the URL.
+    pub fn restaurant_dish(mut self, restaurant: impl Into<String>, pattern: impl AsRef<str>) -> Self {
+        self.restaurants.entry(restaurant.into()).or_default().patterns.push(pattern.as_ref().to_lowercase());
+        self
+    }
+
+    /// Keeps dishes of given restaurant containing the pattern even if they are blacklisted
+    /// globally.
+    pub fn restaurant_allow(mut self, restaurant: impl Into<String>, pattern: impl AsRef<str>) -> Self {
+        self.restaurants.entry(restaurant.into()).or_default().allowed.push(pattern.as_ref().to_lowercase());
+        self
+    }
+
+    /// Returns `true` if the blacklist doesn't remove anything.
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty() && self.restaurants.values().all(|overrides| overrides.patterns.is_empty())
+    }
+
+    /// Checks if the dish served by given restaurant should be removed.
+    pub fn is_blacklisted(&self, restaurant: &str, item: &MenuItem) -> bool {
+        let descriptions = std::iter::once(&item.description)
+            .chain(item.description_translations.values())
+            .map(|description| description.to_lowercase())
+            .collect::<Vec<_>>();
+        let contains = |pattern: &String| descriptions.iter().any(|description| description.contains(&**pattern));
+
+        let overrides = self.restaurants.get(restaurant);
+        if overrides.is_some_and(|overrides| overrides.patterns.iter().any(contains)) {
+            return true;
+        }
+        let allowed = overrides.is_some_and(|overrides| overrides.allowed.iter().any(contains));
+        !allowed && self.patterns.iter().any(contains)
+    }
+
+    /// Removes blacklisted dishes from the menus of given restaurant.
+    pub fn apply(&self, restaurant: &str, menus: &mut [Menu]) {
+        if self.is_empty() {
+            return;
+        }
+        for menu in menus {
+            menu.items.retain(|item| !self.is_blacklisted(restaurant, item));
+        }
+    }
+}
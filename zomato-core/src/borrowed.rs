@@ -0,0 +1,229 @@
+//! Menus borrowing the texts from the parsed page.
+//!
+//! Parsing large archives of pages using [`parse_daily_menu`](crate::parse_daily_menu) allocates
+//! several strings per dish. [`parse_daily_menu_borrowed`](crate::parse_daily_menu_borrowed)
+//! returns the texts as slices of the page instead, they are copied only if they contain escape
+//! sequences or need to be [normalized](crate::normalize). Only the description, the date, the
+//! price and the times of the menu are available, convert the menu using [`Menu::into_owned`] to
+//! get the other information extracted from the description. Only the pages containing the preloaded state are supported,
+//! not the other [extraction strategies](crate::Extraction).
+//!
+//! ```
+//! use std::borrow::Cow;
+//!
+//! let html = zomato_core::testing::FIXTURE;
+//! let menus = zomato_core::parse_daily_menu_borrowed(html)?;
+//! assert_eq!(menus[0].date, "Today");
+//! assert_eq!(menus[0].items[1].description, "150g Bravčový rezeň, zemiakový šalát (1,3,7,10)");
+//! assert!(matches!(menus[0].items[1].price, Cow::Borrowed("€7.20")));
+//! assert!(matches!(menus[0].items[1].description, Cow::Borrowed(_)));
+//!
+//! let owned = menus.into_iter().map(|menu| menu.into_owned()).collect::<Vec<_>>();
+//! assert_eq!(owned[0].items[1].allergens, [1, 3, 7, 10]);
+//! # Ok::<_, zomato_core::Error>(())
+//! ```
+
+use std::borrow::Cow;
+use serde_derive::Deserialize;
+use crate::normalize::normalize_cow;
+use crate::extraction::find_literal_state;
+use crate::parser::{find_preloaded_state, timestamp, unescape, Ordered, Tolerant};
+use crate::{Error, Parser};
+
+/// Daily menu borrowing the texts from the page.
+///
+/// See [`crate::Menu`] for the owned version.
+#[derive(Clone, Debug)]
+pub struct Menu<'a> {
+    /// Date of the menu.
+    pub date: Cow<'a, str>,
+    /// Food items offered at this day.
+    pub items: Vec<MenuItem<'a>>,
+    /// Time since which the menu is served as Unix timestamp, if Zomato sent it.
+    pub valid_from: Option<i64>,
+    /// Time until which the menu is served as Unix timestamp, if Zomato sent it.
+    pub valid_to: Option<i64>,
+    /// Zomato marked this menu as the one served now, see [`crate::Menu::is_current`].
+    pub is_current: bool,
+}
+
+impl Menu<'_> {
+    /// Converts the menu into the owned version, extracting allergens and portions.
+    pub fn into_owned(self) -> crate::Menu {
+        let parser = Parser::new();
+        crate::Menu {
+            date: self.date.into_owned(),
+            items: self.items.into_iter().map(|item| parser.menu_item(item.raw_description.into_owned(), item.price.into_owned())).collect(),
+            ocr: false,
+            valid_from: self.valid_from,
+            valid_to: self.valid_to,
+            is_current: self.is_current,
+        }
+    }
+}
+
+/// Food within daily menu borrowing the texts from the page.
+///
+/// See [`crate::MenuItem`] for the owned version.
+#[derive(Clone, Debug)]
+pub struct MenuItem<'a> {
+    /// Description of the food, HTML entities are decoded and whitespace is collapsed.
+    pub description: Cow<'a, str>,
+    /// Description of the food exactly as received from Zomato.
+    pub raw_description: Cow<'a, str>,
+    /// Food price, may be empty.
+    pub price: Cow<'a, str>,
+}
+
+impl MenuItem<'_> {
+    /// Parses the price of the food.
+    ///
+    /// Returns `None` if the price is empty or can't be parsed.
+    pub fn parsed_price(&self) -> Option<crate::Price> {
+        self.price.parse().ok()
+    }
+}
+
+pub(crate) fn parse_daily_menu(html: &str) -> Result<Vec<Menu<'_>>, anyhow::Error> {
+    use anyhow::Context;
+
+    // Positions of the quotes which were escaped, so that the strings borrowing from the unescaped
+    // JSON can be found in the page.
+    let mut quotes = Vec::new();
+    let (json_escaped, json) = match find_preloaded_state(html) {
+        Some(json_escaped) => (json_escaped, unescape(json_escaped, |position| quotes.push(position))),
+        None => {
+            let json = find_literal_state(html).ok_or_else(|| anyhow::anyhow!("data not found"))?;
+            (json, json.to_owned())
+        },
+    };
+    let source = Source {
+        escaped: json_escaped,
+        unescaped: &json,
+        quotes: &quotes,
+    };
+
+    let data = serde_json::from_str::<Data<'_>>(&json).context("failed to parse json")?;
+    let sections = data
+        .pages
+        .restaurant
+        .0
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("missing restaurant"))?
+        .1
+        .sections;
+
+    let mut menus = sections
+        .daily_menu
+        .into_iter()
+        .filter_map(Tolerant::known)
+        .map(|menu| Menu {
+            date: source.rebase(menu.date),
+            items: menu
+                .dishes
+                .into_iter()
+                .filter_map(Tolerant::known)
+                .map(|item| {
+                    let raw_description = source.rebase(item.name);
+                    let description = match &raw_description {
+                        Cow::Borrowed(raw_description) => normalize_cow(raw_description),
+                        Cow::Owned(raw_description) => Cow::Owned(normalize_cow(raw_description).into_owned()),
+                    };
+                    MenuItem {
+                        description,
+                        raw_description,
+                        price: source.rebase(item.price),
+                    }
+                })
+                .collect(),
+            valid_from: timestamp(&menu.valid_from),
+            valid_to: timestamp(&menu.valid_to),
+            is_current: menu.is_current.as_bool().unwrap_or(false),
+        })
+        .collect::<Vec<_>>();
+    // Same order as the owned parser
+    if menus.iter().all(|menu| menu.valid_from.is_some()) {
+        menus.sort_by_key(|menu| menu.valid_from);
+    }
+    Ok(menus)
+}
+
+/// Parses daily menu from the page without copying the texts if possible.
+///
+/// The menus are ordered like the ones returned by [`parse_daily_menu`](crate::parse_daily_menu).
+/// See the [`borrowed`](crate::borrowed) module for more information.
+pub fn parse_daily_menu_borrowed(html: &str) -> Result<Vec<Menu<'_>>, Error> {
+    parse_daily_menu(html).map_err(Error)
+}
+
+struct Source<'a, 'b> {
+    escaped: &'a str,
+    unescaped: &'b str,
+    quotes: &'b [usize],
+}
+
+impl<'a> Source<'a, '_> {
+    /// Converts string borrowing from the unescaped JSON into the same string in the page.
+    fn rebase(&self, text: Cow<'_, str>) -> Cow<'a, str> {
+        let text = match text {
+            Cow::Borrowed(text) => text,
+            Cow::Owned(text) => return Cow::Owned(text),
+        };
+        let offset = text.as_ptr() as usize - self.unescaped.as_ptr() as usize;
+        // Each escaped quote before the text is one byte longer in the page
+        let start = offset + self.quotes.partition_point(|&quote| quote < offset);
+        match self.escaped.get(start..(start + text.len())) {
+            Some(escaped) if escaped == text => Cow::Borrowed(escaped),
+            _ => Cow::Owned(text.to_owned()),
+        }
+    }
+}
+
+// Tolerates the same changes of the page as the owned parser.
+
+#[derive(Deserialize)]
+struct InternalMenuItem<'a> {
+    #[serde(borrow)]
+    name: Cow<'a, str>,
+    #[serde(borrow, rename = "displayPrice", alias = "price", default)]
+    price: Cow<'a, str>,
+}
+
+#[derive(Deserialize)]
+struct DailyMenu<'a> {
+    #[serde(borrow, default)]
+    dishes: Vec<Tolerant<InternalMenuItem<'a>>>,
+    #[serde(borrow, rename = "timeHeading", alias = "heading", default)]
+    date: Cow<'a, str>,
+    #[serde(rename = "startTime", default)]
+    valid_from: serde_json::Value,
+    #[serde(rename = "endTime", default)]
+    valid_to: serde_json::Value,
+    #[serde(rename = "isCurrent", default)]
+    is_current: serde_json::Value,
+}
+
+#[derive(Deserialize)]
+struct Sections<'a> {
+    #[serde(borrow, rename = "SECTION_DAILY_MENU", default)]
+    daily_menu: Vec<Tolerant<DailyMenu<'a>>>,
+}
+
+#[derive(Deserialize)]
+struct UnknownObject<'a> {
+    #[serde(borrow)]
+    sections: Sections<'a>,
+}
+
+#[derive(Deserialize)]
+struct Pages<'a> {
+    #[serde(borrow)]
+    restaurant: Ordered<Cow<'a, str>, UnknownObject<'a>>,
+}
+
+#[derive(Deserialize)]
+struct Data<'a> {
+    #[serde(borrow)]
+    pages: Pages<'a>,
+}
@@ -0,0 +1,116 @@
+use std::fmt;
+use crate::{Menu, MenuItem, PriceBounds, Unit};
+
+/// Course of a meal.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Course {
+    /// Soup, usually served first.
+    Soup,
+    /// Main course.
+    Main,
+    /// Dessert.
+    Dessert,
+    /// Drink.
+    Drink,
+}
+
+impl fmt::Display for Course {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Course::Soup => "Soup",
+            Course::Main => "Main course",
+            Course::Dessert => "Dessert",
+            Course::Drink => "Drink",
+        };
+        f.write_str(name)
+    }
+}
+
+const SOUP: &[&str] = &[
+    "soup", "polievk", "polévk", "vývar", "kulajda", "cesnačk", "česnečk", "fazuľovic", "kapustnic", "zelňačk", "držkov", "boršč", "leves", "suppe", "brühe",
+];
+
+const DESSERT: &[&str] = &[
+    "dessert", "dezert", "zákusok", "zákusek", "koláč", "palacink", "palačink", "buchty", "štrúdl", "torta", "dort", "tiramisu", "puding", "pudink", "cake", "muffin", "kuchen", "palatschink", "lievanc", "lívanc",
+];
+
+// Wine and beer are omitted since they commonly appear in sauces
+const DRINK: &[&str] = &[
+    "drink", "nápoj", "limonád", "limonad", "kofola", "juice", "džús", "čaj", "tea", "coffee", "káva", "water", "minerálk", "getränk", "saft",
+];
+
+/// Soups tend to cost less than half of the most expensive dish.
+const SOUP_PRICE_RATIO: f64 = 0.5;
+
+impl Menu {
+    /// Splits the items into courses.
+    ///
+    /// The courses are guessed from keywords in the descriptions, volume portions (drinks) and
+    /// prices - the first item which is a lot cheaper than the others is likely a soup. Prices
+    /// outside the [default bounds](PriceBounds::default) are ignored. Items that don't match
+    /// anything are considered main courses.
+    ///
+    /// The courses are returned in the order they are usually served, courses without any items
+    /// are omitted. The items keep their order.
+    ///
+    /// ```
+    /// use zomato_core::Course;
+    ///
+    /// let menu = zomato_core::parse_daily_menu(zomato_core::testing::FIXTURE)?.remove(0);
+    /// let courses = menu.by_course();
+    /// assert_eq!(courses[0].0, Course::Soup);
+    /// assert_eq!(courses[0].1.len(), 1);
+    /// assert_eq!(courses[1].0, Course::Main);
+    /// assert_eq!(courses[1].1.len(), 2);
+    /// # Ok::<_, zomato_core::Error>(())
+    /// ```
+    pub fn by_course(&self) -> Vec<(Course, Vec<&MenuItem>)> {
+        let bounds = PriceBounds::default();
+        let max_price = self
+            .items
+            .iter()
+            .filter_map(|item| item.plausible_price(&bounds))
+            .map(|price| price.amount)
+            .fold(None, |max: Option<f64>, price| Some(max.map_or(price, |max| max.max(price))));
+
+        let mut courses = Vec::<(Course, Vec<&MenuItem>)>::new();
+        for (position, item) in self.items.iter().enumerate() {
+            let course = categorize(item, position, max_price, &bounds);
+            match courses.iter_mut().find(|(existing, _)| *existing == course) {
+                Some((_, items)) => items.push(item),
+                None => courses.push((course, vec![item])),
+            }
+        }
+        courses.sort_by_key(|(course, _)| *course);
+        courses
+    }
+}
+
+fn categorize(item: &MenuItem, position: usize, max_price: Option<f64>, bounds: &PriceBounds) -> Course {
+    let description = item.description.to_lowercase();
+    let words = description
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>();
+    let contains = |keywords: &[&str]| keywords.iter().any(|keyword| words.iter().any(|word| word.starts_with(keyword)));
+
+    if contains(SOUP) {
+        return Course::Soup;
+    }
+    if contains(DESSERT) {
+        return Course::Dessert;
+    }
+    if contains(DRINK) {
+        return Course::Drink;
+    }
+    if let (0, Some(price), Some(max_price)) = (position, item.plausible_price(bounds), max_price) {
+        if price.amount < max_price * SOUP_PRICE_RATIO {
+            return Course::Soup;
+        }
+    }
+    let is_volume = |unit| matches!(unit, Unit::Milliliter | Unit::Centiliter | Unit::Deciliter | Unit::Liter);
+    if item.portions.iter().any(|portion| is_volume(portion.unit)) {
+        return Course::Drink;
+    }
+    Course::Main
+}
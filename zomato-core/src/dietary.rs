@@ -0,0 +1,180 @@
+use std::sync::OnceLock;
+use crate::MenuItem;
+
+/// Dietary category a dish likely belongs to.
+///
+/// The hints are guessed from the description of the dish, so they are only as good as the
+/// description. Don't rely on them if the restriction is serious (e.g. allergy).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum DietaryHint {
+    /// The dish likely contains meat or fish.
+    Meat,
+    /// The dish likely doesn't contain meat or fish.
+    Vegetarian,
+    /// The dish likely doesn't contain any animal products.
+    ///
+    /// Vegan dishes are tagged as vegetarian too.
+    Vegan,
+}
+
+/// Guesses dietary hints from keywords found in descriptions of dishes.
+///
+/// Keywords are matched case-insensitively against the beginnings of words, so that they cover
+/// inflected forms - e.g. "kurac" matches both "kurací" and "kuracie". The default classifier
+/// knows common keywords in English, Slovak, Czech, German and Hungarian, custom classifiers can
+/// be built starting from [`empty`](Self::empty) or by extending the default one.
+///
+/// The dish is classified as:
+///
+/// * meat if any meat keyword matches,
+/// * vegetarian if any animal product (e.g. cheese) or vegetarian keyword matches,
+/// * vegan if any vegan keyword matches,
+/// * nothing otherwise.
+///
+/// ```
+/// use zomato_core::{DietaryClassifier, DietaryHint};
+///
+/// let menu = zomato_core::parse_daily_menu(zomato_core::testing::FIXTURE)?.remove(0);
+/// assert_eq!(menu.items[1].dietary_hints(), [DietaryHint::Meat]);
+/// assert_eq!(menu.items[2].dietary_hints(), [DietaryHint::Vegetarian]);
+///
+/// let classifier = DietaryClassifier::default().vegan_keyword("cícer");
+/// let item = zomato_core::MenuItem {
+///     description: "Cícerové karí, ryža".to_owned(),
+///     raw_description: "Cícerové karí, ryža".to_owned(),
+///     price: "€5.90".to_owned(),
+///     allergens: Vec::new(),
+///     raw_allergens: None,
+///     portions: Vec::new(),
+///     raw_portions: Vec::new(),
+///     description_translations: Default::default(),
+///     details: None,
+///     tags: Vec::new(),
+///     id: None,
+/// };
+/// assert_eq!(classifier.classify(&item), [DietaryHint::Vegan, DietaryHint::Vegetarian]);
+/// # Ok::<_, zomato_core::Error>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct DietaryClassifier {
+    meat: Vec<String>,
+    animal_products: Vec<String>,
+    vegetarian: Vec<String>,
+    vegan: Vec<String>,
+}
+
+impl DietaryClassifier {
+    /// Creates classifier without any keywords.
+    pub fn empty() -> Self {
+        DietaryClassifier {
+            meat: Vec::new(),
+            animal_products: Vec::new(),
+            vegetarian: Vec::new(),
+            vegan: Vec::new(),
+        }
+    }
+
+    /// Adds keyword indicating meat or fish.
+    pub fn meat_keyword(mut self, keyword: impl AsRef<str>) -> Self {
+        self.meat.push(keyword.as_ref().to_lowercase());
+        self
+    }
+
+    /// Adds keyword indicating animal product which isn't meat, e.g. cheese or eggs.
+    pub fn animal_product_keyword(mut self, keyword: impl AsRef<str>) -> Self {
+        self.animal_products.push(keyword.as_ref().to_lowercase());
+        self
+    }
+
+    /// Adds keyword marking the dish as vegetarian.
+    pub fn vegetarian_keyword(mut self, keyword: impl AsRef<str>) -> Self {
+        self.vegetarian.push(keyword.as_ref().to_lowercase());
+        self
+    }
+
+    /// Adds keyword marking the dish as vegan, e.g. "tofu".
+    pub fn vegan_keyword(mut self, keyword: impl AsRef<str>) -> Self {
+        self.vegan.push(keyword.as_ref().to_lowercase());
+        self
+    }
+
+    /// Guesses the hints for the dish.
+    ///
+    /// Returns empty `Vec` if nothing is known about the dish.
+    pub fn classify(&self, item: &MenuItem) -> Vec<DietaryHint> {
+        let description = item.description.to_lowercase();
+        let words = description
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<_>>();
+        let contains = |keywords: &[String]| keywords.iter().any(|keyword| words.iter().any(|word| word.starts_with(&**keyword)));
+
+        if contains(&self.meat) {
+            vec![DietaryHint::Meat]
+        } else if contains(&self.animal_products) || contains(&self.vegetarian) {
+            vec![DietaryHint::Vegetarian]
+        } else if contains(&self.vegan) {
+            vec![DietaryHint::Vegan, DietaryHint::Vegetarian]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+impl Default for DietaryClassifier {
+    fn default() -> Self {
+        DietaryClassifier {
+            meat: to_owned(DEFAULT_MEAT),
+            animal_products: to_owned(DEFAULT_ANIMAL_PRODUCTS),
+            vegetarian: to_owned(DEFAULT_VEGETARIAN),
+            vegan: to_owned(DEFAULT_VEGAN),
+        }
+    }
+}
+
+fn to_owned(keywords: &[&str]) -> Vec<String> {
+    keywords.iter().map(|keyword| (*keyword).to_owned()).collect()
+}
+
+const DEFAULT_MEAT: &[&str] = &[
+    // English
+    "meat", "chicken", "beef", "pork", "ham", "bacon", "sausage", "salami", "turkey", "duck", "lamb", "veal", "fish", "salmon", "tuna", "shrimp", "prawn", "steak",
+    // Slovak
+    "mäso", "mäsov", "kurac", "kurča", "hovädz", "bravč", "šunk", "slanin", "klobás", "saláma", "morč", "kačac", "kačic", "jahňa", "teľac", "ryb", "losos", "tuniak", "pečeň", "pečienk",
+    // Czech
+    "maso", "masov", "kuřec", "kuře", "hověz", "vepř", "krůt", "kachn", "jehněč", "teleč", "tuňák", "játr",
+    // German
+    "fleisch", "huhn", "hähnchen", "rind", "schwein", "schinken", "speck", "wurst", "pute", "ente", "lamm", "kalb", "fisch", "lachs",
+    // Hungarian
+    "hús", "csirke", "marha", "sertés", "sonka", "kolbász", "pulyka", "kacsa",
+];
+
+const DEFAULT_ANIMAL_PRODUCTS: &[&str] = &[
+    // English
+    "cheese", "egg", "milk", "cream", "butter", "yogurt", "yoghurt", "honey", "mozzarella", "parmesan",
+    // Slovak
+    "syr", "vajc", "vajíč", "mliek", "mlieč", "smotan", "masl", "tvaroh", "bryndz", "jogurt", "oštiep", "parenic",
+    // Czech
+    "sýr", "vejc", "vajíč", "mlék", "mléč", "smetan", "másl",
+    // German
+    "käse", "milch", "sahne", "quark",
+    // Hungarian
+    "sajt", "tojás", "tejföl", "túró",
+];
+
+const DEFAULT_VEGETARIAN: &[&str] = &[
+    "vegetarian", "veggie", "vegetarián", "vegetarisch", "vegetárián", "bezmäs", "bezmas",
+];
+
+const DEFAULT_VEGAN: &[&str] = &[
+    "vegan", "vegán", "tofu", "tempeh", "seitan",
+];
+
+impl MenuItem {
+    /// Guesses dietary hints of the dish using the default [`DietaryClassifier`].
+    pub fn dietary_hints(&self) -> Vec<DietaryHint> {
+        static CLASSIFIER: OnceLock<DietaryClassifier> = OnceLock::new();
+
+        CLASSIFIER.get_or_init(Default::default).classify(self)
+    }
+}
@@ -0,0 +1,206 @@
+//! Strategies of finding the state of the page.
+
+use std::fmt;
+use serde_json::{json, Value};
+use crate::parser::{find_preloaded_state, in_script, unescape};
+
+/// Text preceding the state assigned as a JavaScript object literal.
+const LITERAL_STATE_START: &str = "window.__PRELOADED_STATE__ = {";
+
+/// Opening tag of the scripts containing JSON-LD.
+const JSON_LD_START: &str = "<script type=\"application/ld+json\">";
+
+/// Way the state of the page was found.
+///
+/// Zomato A/B tests the format of its pages, so the state is searched for using several strategies
+/// tried in the order of the variants. The next strategy is tried also if the state found by the
+/// previous one can't be parsed. The strategy used for parsing a page is reported in
+/// [`Page::extraction`](crate::Page::extraction).
+///
+/// ```
+/// use zomato_core::Extraction;
+///
+/// let page = zomato_core::Parser::new().parse_page(zomato_core::testing::FIXTURE)?;
+/// assert_eq!(page.extraction, Extraction::PreloadedState);
+///
+/// let html = r#"<script type="application/ld+json">{
+///     "@type": "Restaurant",
+///     "name": "U Zlatého Bažanta",
+///     "hasMenu": {"hasMenuSection": [{
+///         "name": "Today",
+///         "hasMenuItem": [{"name": "Vyprážaný syr", "offers": {"price": "6.00", "priceCurrency": "EUR"}}]
+///     }]}
+/// }</script>"#;
+/// let page = zomato_core::Parser::new().parse_page(html)?;
+/// assert_eq!(page.extraction, Extraction::JsonLd);
+/// assert_eq!(page.menus[0].items[0].price, "€6.00");
+///
+/// let broken = format!("<script>window.__PRELOADED_STATE__ = JSON.parse(\"{{}}\")\n</script>{}", html);
+/// let page = zomato_core::Parser::new().parse_page(&broken)?;
+/// assert_eq!(page.extraction, Extraction::JsonLd);
+/// # Ok::<_, zomato_core::Error>(())
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum Extraction {
+    /// Escaped JSON passed to `JSON.parse` in the script assigning `window.__PRELOADED_STATE__`.
+    PreloadedState,
+    /// Object literal assigned to `window.__PRELOADED_STATE__`.
+    LiteralObject,
+    /// Restaurant described using schema.org in a JSON-LD script.
+    ///
+    /// Only the name, rating, cuisines and the menu are available.
+    JsonLd,
+    /// Response of the web API used by the Zomato app, parsed using
+    /// [`Parser::parse_web_api`](crate::Parser::parse_web_api).
+    WebApi,
+}
+
+impl Extraction {
+    /// Returns the name of the strategy, e.g. "preloaded-state".
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Extraction::PreloadedState => "preloaded-state",
+            Extraction::LiteralObject => "literal-object",
+            Extraction::JsonLd => "json-ld",
+            Extraction::WebApi => "web-api",
+        }
+    }
+}
+
+impl fmt::Display for Extraction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Finds the state of the page trying the strategies available in the page in order.
+pub(crate) fn extract_state(html: &str) -> Result<(String, Extraction), anyhow::Error> {
+    extract_states(html).next().ok_or_else(|| anyhow::anyhow!("data not found"))
+}
+
+/// Finds the states of the page found by all strategies in order.
+///
+/// The states are searched lazily, so the later strategies are tried only if the former states
+/// can't be parsed.
+pub(crate) fn extract_states(html: &str) -> impl Iterator<Item = (String, Extraction)> + '_ {
+    let preloaded_state = move || find_preloaded_state(html).map(|json_escaped| (unescape(json_escaped, |_| ()), Extraction::PreloadedState));
+    let literal_state = move || find_literal_state(html).map(|json| (json.to_owned(), Extraction::LiteralObject));
+    let json_ld_state = move || json_ld_state(html).map(|json| (json, Extraction::JsonLd));
+    std::iter::once_with(preloaded_state)
+        .chain(std::iter::once_with(literal_state))
+        .chain(std::iter::once_with(json_ld_state))
+        .flatten()
+}
+
+/// Finds the JSON of the state assigned as an object literal.
+///
+/// Zomato generates the literal from JSON so it can be parsed as such.
+pub(crate) fn find_literal_state(html: &str) -> Option<&str> {
+    let mut searched = 0;
+    loop {
+        // The brace is a part of the JSON
+        let start = searched + html[searched..].find(LITERAL_STATE_START)? + LITERAL_STATE_START.len() - 1;
+        searched = start;
+        if !in_script(&html[..start]) {
+            continue;
+        }
+        let json = &html[start..];
+        let mut values = serde_json::Deserializer::from_str(json).into_iter::<serde::de::IgnoredAny>();
+        values.next()?.ok()?;
+        return Some(&json[..values.byte_offset()]);
+    }
+}
+
+/// Converts the schema.org description of the restaurant into the state of the page.
+fn json_ld_state(html: &str) -> Option<String> {
+    let restaurant = html
+        .split(JSON_LD_START)
+        .skip(1)
+        .filter_map(|script| serde_json::from_str::<Value>(script.split("</script>").next()?.trim()).ok())
+        .flat_map(|value| match value {
+            Value::Array(values) => values,
+            Value::Object(mut object) => match object.remove("@graph") {
+                Some(Value::Array(values)) => values,
+                _ => vec![Value::Object(object)],
+            },
+            _ => Vec::new(),
+        })
+        .find(|value| value["@type"] == "Restaurant" && value.get("hasMenu").is_some())?;
+
+    let cuisines = match &restaurant["servesCuisine"] {
+        Value::Array(cuisines) => cuisines.iter().filter_map(Value::as_str).collect::<Vec<_>>().join(", "),
+        Value::String(cuisines) => cuisines.clone(),
+        _ => String::new(),
+    };
+    let sections = match &restaurant["hasMenu"]["hasMenuSection"] {
+        Value::Array(sections) => sections.iter().collect(),
+        Value::Object(_) => vec![&restaurant["hasMenu"]["hasMenuSection"]],
+        _ => Vec::new(),
+    };
+    let daily_menu = sections
+        .into_iter()
+        .map(|section| {
+            let dishes = section["hasMenuItem"]
+                .as_array()
+                .map(|items| items.iter().map(|item| json!({ "name": item["name"], "displayPrice": json_ld_price(&item["offers"]) })).collect())
+                .unwrap_or_else(Vec::new);
+            json!({ "timeHeading": section["name"], "dishes": dishes })
+        })
+        .collect::<Vec<_>>();
+
+    let state = json!({
+        "pages": {
+            "restaurant": {
+                "json-ld": {
+                    "sections": {
+                        "SECTION_BASIC_INFO": {
+                            "name": restaurant["name"],
+                            "cuisine_string": cuisines,
+                            "rating": {
+                                "aggregate_rating": restaurant["aggregateRating"]["ratingValue"],
+                                "votes": restaurant["aggregateRating"]["ratingCount"],
+                            },
+                        },
+                        "SECTION_DAILY_MENU": daily_menu,
+                    },
+                },
+            },
+        },
+    });
+    Some(state.to_string())
+}
+
+/// Formats the price the same way Zomato displays it.
+fn json_ld_price(offers: &Value) -> String {
+    let offer = match offers {
+        Value::Array(offers) => offers.first().unwrap_or(&Value::Null),
+        offer => offer,
+    };
+    let amount = match &offer["price"] {
+        Value::String(amount) => amount.clone(),
+        Value::Number(amount) => amount.to_string(),
+        _ => return String::new(),
+    };
+    match offer["priceCurrency"].as_str().unwrap_or("") {
+        "EUR" => format!("€{}", amount),
+        "USD" => format!("${}", amount),
+        "GBP" => format!("£{}", amount),
+        "INR" => format!("₹{}", amount),
+        "CZK" => format!("{} Kč", amount),
+        "PLN" => format!("{} zł", amount),
+        "HUF" => format!("{} Ft", amount),
+        currency => format!("{} {}", amount, currency).trim_end().to_owned(),
+    }
+}
+
+/// Converts the response of the web API into the state of the page.
+pub(crate) fn web_api_state(json: &str) -> Result<String, anyhow::Error> {
+    let mut response = serde_json::from_str::<Value>(json)?;
+    let sections = response
+        .get_mut("page_data")
+        .and_then(|page_data| page_data.get_mut("sections"))
+        .map(Value::take)
+        .ok_or_else(|| anyhow::anyhow!("the response doesn't contain page_data.sections"))?;
+    Ok(json!({ "pages": { "restaurant": { "web-api": { "sections": sections } } } }).to_string())
+}
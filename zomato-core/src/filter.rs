@@ -0,0 +1,136 @@
+use crate::{Menu, MenuItem};
+
+/// Criteria for selecting dishes from menus.
+///
+/// All criteria must be satisfied for the dish to match. Keywords are matched case-insensitively
+/// against the description of the dish.
+///
+/// ```
+/// use zomato_core::MenuQuery;
+///
+/// let mut menu = zomato_core::parse_daily_menu(zomato_core::testing::FIXTURE)?.remove(0);
+/// let query = MenuQuery::new()
+///     .exclude("syr")
+///     .max_price(7.0);
+/// menu.filter(&query);
+/// assert_eq!(menu.items.len(), 1);
+/// assert!(menu.items[0].description.starts_with("Hovädzí vývar"));
+/// # Ok::<_, zomato_core::Error>(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MenuQuery {
+    include: Vec<String>,
+    /// Keywords with the minimum similarity.
+    fuzzy: Vec<(String, f64)>,
+    exclude: Vec<String>,
+    max_price: Option<f64>,
+    #[cfg(feature = "regex")]
+    patterns: Vec<regex::Regex>,
+}
+
+impl MenuQuery {
+    /// Creates query matching all dishes.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Requires the description to contain the keyword.
+    ///
+    /// If multiple keywords are included the description must contain at least one of them.
+    pub fn include(mut self, keyword: impl AsRef<str>) -> Self {
+        self.include.push(keyword.as_ref().to_lowercase());
+        self
+    }
+
+    /// Requires the description to contain a phrase similar to the keyword.
+    ///
+    /// The keyword is compared to each run of the same number of words in the description using
+    /// the [global similarity backend](crate::similarity::global), so it matches despite typos
+    /// or missing diacritics. `min_similarity` is between 0 and 1, 0.85 is a good start. Fuzzy
+    /// keywords are alternatives to the [included](Self::include) ones, so the dish matches if
+    /// it contains any of them.
+    ///
+    /// ```
+    /// use zomato_core::MenuQuery;
+    ///
+    /// let mut menu = zomato_core::parse_daily_menu(zomato_core::testing::FIXTURE)?.remove(0);
+    /// menu.filter(&MenuQuery::new().fuzzy("bravcovy rezen", 0.85));
+    /// assert_eq!(menu.items.len(), 1);
+    /// assert!(menu.items[0].description.contains("Bravčový rezeň"));
+    /// # Ok::<_, zomato_core::Error>(())
+    /// ```
+    pub fn fuzzy(mut self, keyword: impl AsRef<str>, min_similarity: f64) -> Self {
+        self.fuzzy.push((keyword.as_ref().to_lowercase(), min_similarity));
+        self
+    }
+
+    /// Rejects dishes with descriptions containing the keyword.
+    pub fn exclude(mut self, keyword: impl AsRef<str>) -> Self {
+        self.exclude.push(keyword.as_ref().to_lowercase());
+        self
+    }
+
+    /// Rejects dishes more expensive than `max_price`.
+    ///
+    /// Dishes with unknown price are kept since they might be cheap.
+    pub fn max_price(mut self, max_price: f64) -> Self {
+        self.max_price = Some(max_price);
+        self
+    }
+
+    /// Requires the description to match the regular expression.
+    ///
+    /// If multiple expressions are added the description must match all of them.
+    #[cfg(feature = "regex")]
+    pub fn matching(mut self, pattern: &str) -> Result<Self, regex::Error> {
+        self.patterns.push(regex::Regex::new(pattern)?);
+        Ok(self)
+    }
+
+    /// Checks if the dish satisfies the criteria.
+    pub fn matches(&self, item: &MenuItem) -> bool {
+        let description = item.description.to_lowercase();
+        let has_includes = !self.include.is_empty() || !self.fuzzy.is_empty();
+        if has_includes && !self.includes(&description) {
+            return false;
+        }
+        if self.exclude.iter().any(|keyword| description.contains(&**keyword)) {
+            return false;
+        }
+        if let (Some(max_price), Some(price)) = (self.max_price, item.parsed_price()) {
+            if price.amount > max_price {
+                return false;
+            }
+        }
+        #[cfg(feature = "regex")]
+        {
+            if !self.patterns.iter().all(|pattern| pattern.is_match(&item.description)) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl MenuQuery {
+    /// Checks if the lowercase description contains any of the included keywords.
+    fn includes(&self, description: &str) -> bool {
+        if self.include.iter().any(|keyword| description.contains(&**keyword)) {
+            return true;
+        }
+        if self.fuzzy.is_empty() {
+            return false;
+        }
+        let similarity = crate::similarity::global();
+        self.fuzzy
+            .iter()
+            .any(|(keyword, min_similarity)| crate::similarity::best_phrase_match(&*similarity, keyword, description) >= *min_similarity)
+    }
+}
+
+impl Menu {
+    /// Removes all dishes not matching the query.
+    pub fn filter(&mut self, query: &MenuQuery) {
+        self.items.retain(|item| query.matches(item));
+    }
+}
@@ -0,0 +1,139 @@
+use std::fmt;
+use std::str::FromStr;
+use serde_derive::{Deserialize, Serialize};
+use crate::MenuItem;
+
+/// Language of a dish description.
+///
+/// Only the languages which can be told apart by [`Parser::merge_translations`] are supported.
+/// They are identified by ISO 639-1 codes when parsed, displayed or serialized.
+///
+/// [`Parser::merge_translations`]: crate::Parser::merge_translations
+///
+/// ```
+/// use zomato_core::Lang;
+///
+/// assert_eq!("cs".parse::<Lang>().unwrap(), Lang::Czech);
+/// assert_eq!(Lang::English.to_string(), "en");
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum Lang {
+    /// Czech (`cs`).
+    #[serde(rename = "cs")]
+    Czech,
+    /// Slovak (`sk`).
+    #[serde(rename = "sk")]
+    Slovak,
+    /// English (`en`).
+    #[serde(rename = "en")]
+    English,
+}
+
+impl Lang {
+    /// Returns the ISO 639-1 code of the language.
+    pub fn code(self) -> &'static str {
+        match self {
+            Lang::Czech => "cs",
+            Lang::Slovak => "sk",
+            Lang::English => "en",
+        }
+    }
+}
+
+impl fmt::Display for Lang {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// Error returned when parsing unsupported language code.
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("unsupported language '{code}', supported languages: cs, sk, en")]
+pub struct ParseLangError {
+    code: String,
+}
+
+impl FromStr for Lang {
+    type Err = ParseLangError;
+
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        match code {
+            "cs" => Ok(Lang::Czech),
+            "sk" => Ok(Lang::Slovak),
+            "en" => Ok(Lang::English),
+            _ => Err(ParseLangError { code: code.to_owned() }),
+        }
+    }
+}
+
+const ENGLISH_WORDS: &[&str] = &[
+    "and", "with", "soup", "chicken", "pork", "beef", "fish", "cheese", "potatoes", "potato", "rice",
+    "salad", "sauce", "fried", "roasted", "baked", "grilled", "mashed", "dumplings", "vegetables",
+    "vegetable", "cream", "noodles", "pasta", "steak", "turkey", "duck", "mushroom", "mushrooms",
+    "dessert", "bread", "fries", "of",
+];
+const CZECH_WORDS: &[&str] = &["polévka", "brambory", "rýže", "kuřecí", "vepřový", "hovězí", "omáčka", "knedlík", "se", "na"];
+const SLOVAK_WORDS: &[&str] = &["polievka", "zemiaky", "ryža", "kuracie", "bravčový", "hovädzí", "omáčka", "knedľa", "so", "na"];
+
+/// Guesses the language of the description, `None` if unsure.
+pub(crate) fn detect(text: &str) -> Option<Lang> {
+    let text = text.to_lowercase();
+    if text.contains(['ř', 'ů', 'ě']) {
+        return Some(Lang::Czech);
+    }
+    if text.contains(['ä', 'ô', 'ľ', 'ĺ', 'ŕ']) {
+        return Some(Lang::Slovak);
+    }
+
+    let words = text.split(|c: char| !c.is_alphabetic()).filter(|word| !word.is_empty()).collect::<Vec<_>>();
+    let count = |list: &[&str]| words.iter().filter(|word| list.contains(word)).count();
+    if text.is_ascii() && count(ENGLISH_WORDS) > 0 {
+        return Some(Lang::English);
+    }
+    let czech = count(CZECH_WORDS);
+    let slovak = count(SLOVAK_WORDS);
+    if czech > slovak {
+        Some(Lang::Czech)
+    } else if slovak > czech {
+        Some(Lang::Slovak)
+    } else {
+        None
+    }
+}
+
+/// Merges adjacent items describing the same dish in different languages.
+///
+/// The items are considered the same dish if exactly one of them is English and they have the
+/// same price and allergens, or the second one has none of them. The first item is kept with
+/// both descriptions in [`MenuItem::description_translations`].
+pub(crate) fn merge_translations(items: Vec<MenuItem>) -> Vec<MenuItem> {
+    let mut merged = Vec::<MenuItem>::with_capacity(items.len());
+    for item in items {
+        if let Some(previous) = merged.last_mut() {
+            if let Some((previous_lang, lang)) = translation_languages(previous, &item) {
+                previous.description_translations.insert(previous_lang, previous.description.clone());
+                previous.description_translations.insert(lang, item.description);
+                continue;
+            }
+        }
+        merged.push(item);
+    }
+    merged
+}
+
+/// Returns the languages of the items if the second one is a translation of the first one.
+fn translation_languages(first: &MenuItem, second: &MenuItem) -> Option<(Lang, Lang)> {
+    if !first.description_translations.is_empty() {
+        return None;
+    }
+    if !(second.price.is_empty() || second.price == first.price) || !(second.allergens.is_empty() || second.allergens == first.allergens) {
+        return None;
+    }
+    let languages = (detect(&first.description)?, detect(&second.description)?);
+    match languages {
+        (Lang::English, Lang::English) => None,
+        (Lang::English, _) | (_, Lang::English) => Some(languages),
+        _ => None,
+    }
+}
@@ -0,0 +1,115 @@
+//! Detection of changes of the structure of Zomato pages.
+//!
+//! Zomato occasionally changes how the data are embedded in its pages. Such changes often start
+//! with new or renamed fields before the parser breaks completely, so it's worth noticing them
+//! early. [`Layout::detect`] describes the structure of a page as a set of markers, which don't
+//! depend on the restaurant or its menu, so all pages of the same generation have the same
+//! [`generation`](Layout::generation).
+//!
+//! ```
+//! use zomato_core::layout::Layout;
+//!
+//! let layout = Layout::detect(zomato_core::testing::FIXTURE);
+//! assert!(layout.markers().any(|marker| marker == "dish:displayPrice"));
+//!
+//! let other_restaurant = zomato_core::testing::FIXTURE.replace("Bažanta", "Kohúta");
+//! assert_eq!(Layout::detect(&other_restaurant).generation(), layout.generation());
+//!
+//! let renamed = zomato_core::testing::FIXTURE.replace("displayPrice", "price");
+//! assert_ne!(Layout::detect(&renamed).generation(), layout.generation());
+//! ```
+
+use std::collections::BTreeSet;
+use crate::extraction::{extract_state, Extraction};
+
+/// Sections whose presence is recorded, the other ones differ between restaurants.
+const KNOWN_SECTIONS: &[&str] = &["SECTION_BASIC_INFO", "SECTION_DAILY_MENU"];
+
+/// Structure of a page.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Layout {
+    markers: BTreeSet<String>,
+}
+
+impl Layout {
+    /// Detects the structure of the HTML page downloaded from Zomato.
+    ///
+    /// This never fails, pages which can't be parsed at all have markers describing why.
+    pub fn detect(html: &str) -> Self {
+        let mut markers = BTreeSet::new();
+        collect_markers(html, &mut markers);
+        Layout {
+            markers,
+        }
+    }
+
+    /// Returns the markers in alphabetical order.
+    ///
+    /// The markers are short strings like `state:pages` (the embedded state has the `pages`
+    /// field) or `dish:displayPrice` (dishes of the daily menu have the `displayPrice` field).
+    /// Pages found using a fallback [extraction strategy](crate::Extraction) have a marker like
+    /// `extraction:json-ld`.
+    pub fn markers(&self) -> impl Iterator<Item = &str> {
+        self.markers.iter().map(String::as_str)
+    }
+
+    /// Returns the identifier of the layout.
+    ///
+    /// This is a hash of the markers which is stable across versions of this crate, so it can be
+    /// stored and compared with layouts detected later.
+    pub fn generation(&self) -> String {
+        // 64-bit FNV-1a
+        let mut hash = 0xcbf2_9ce4_8422_2325_u64;
+        for marker in &self.markers {
+            for byte in marker.bytes().chain(std::iter::once(b'\n')) {
+                hash ^= u64::from(byte);
+                hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+            }
+        }
+        format!("{:016x}", hash)
+    }
+}
+
+fn collect_markers(html: &str, markers: &mut BTreeSet<String>) {
+    fn keys(value: &serde_json::Value, prefix: &str, markers: &mut BTreeSet<String>) {
+        if let Some(object) = value.as_object() {
+            markers.extend(object.keys().map(|key| format!("{}:{}", prefix, key)));
+        }
+    }
+
+    let (state, extraction) = match extract_state(html) {
+        Ok(found) => found,
+        Err(_) => {
+            markers.insert("state:missing".to_owned());
+            return;
+        },
+    };
+    // Pages with the default format keep their generation from before the fallbacks existed.
+    if extraction != Extraction::PreloadedState {
+        markers.insert(format!("extraction:{}", extraction));
+    }
+    let state = match serde_json::from_str::<serde_json::Value>(&state) {
+        Ok(state) => state,
+        Err(_) => {
+            markers.insert("state:invalid".to_owned());
+            return;
+        },
+    };
+    keys(&state, "state", markers);
+    keys(&state["pages"], "pages", markers);
+
+    let restaurant = match state["pages"]["restaurant"].as_object().and_then(|restaurants| restaurants.values().next()) {
+        Some(restaurant) => restaurant,
+        None => return,
+    };
+    keys(restaurant, "restaurant", markers);
+    let sections = &restaurant["sections"];
+    for section in KNOWN_SECTIONS {
+        if !sections[section].is_null() {
+            markers.insert(format!("section:{}", section));
+        }
+    }
+    let daily_menu = &sections["SECTION_DAILY_MENU"][0];
+    keys(daily_menu, "daily-menu", markers);
+    keys(&daily_menu["dishes"][0], "dish", markers);
+}
@@ -0,0 +1,308 @@
+//! Data model and parsers of Zomato pages.
+//!
+//! This crate contains only the pure parts of the `zomato` crate: the types describing menus and
+//! restaurants and the parser of the pages. It doesn't make any requests, so it has a small
+//! dependency tree without any async runtime or HTTP client and it compiles for embedded and WASM
+//! targets. Download the pages yourself or use the `zomato` crate, which re-exports everything
+//! from this crate.
+//!
+//! ```
+//! let menus = zomato_core::parse_daily_menu(zomato_core::testing::FIXTURE)?;
+//! assert_eq!(menus[0].items.len(), 3);
+//! # Ok::<_, zomato_core::Error>(())
+//! ```
+
+mod allergens;
+mod blacklist;
+pub mod borrowed;
+mod course;
+mod dietary;
+mod extraction;
+mod filter;
+mod lang;
+pub mod layout;
+mod normalize;
+mod parser;
+mod portions;
+mod price;
+pub mod similarity;
+pub mod testing;
+
+pub use blacklist::DishBlacklist;
+pub use borrowed::parse_daily_menu_borrowed;
+pub use course::Course;
+pub use dietary::{DietaryClassifier, DietaryHint};
+pub use extraction::Extraction;
+pub use filter::MenuQuery;
+pub use lang::{Lang, ParseLangError};
+pub use normalize::normalize;
+pub use parser::{extract_preloaded_state, Page, Parser, SectionHandler, PRELOADED_STATE_END, PRELOADED_STATE_START, StateScanner};
+pub use portions::{Quantity, Unit};
+pub use price::{ParsePriceError, Price, PriceBounds, PriceWarning};
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt;
+use serde_derive::{Deserialize, Serialize};
+
+/// Error returned when parsing fails.
+///
+/// This currently implements only `Debug` and `Display`. New traits/methods may be implemented in
+/// the future.
+#[derive(thiserror::Error, Debug)]
+#[error(transparent)]
+pub struct Error (anyhow::Error);
+
+/// Food within daily menu.
+///
+/// ```
+/// let html = zomato_core::testing::FIXTURE.replace("Vyprážaný syr, hranolky", "Vyprážaný&nbsp;syr &amp;\\\\n  hranolky");
+/// let item = &zomato_core::parse_daily_menu(&html)?[0].items[2];
+/// assert_eq!(item.description, "Vyprážaný syr & hranolky, tatárska omáčka (1,3,7)");
+/// assert_eq!(item.raw_description, "Vyprážaný&nbsp;syr &amp;\\n  hranolky, tatárska omáčka (1,3,7)");
+/// assert_eq!(item.allergens, [1, 3, 7]);
+/// assert_eq!(item.raw_allergens.as_deref(), Some("(1,3,7)"));
+///
+/// let menu = zomato_core::Parser::new().strip_portions(true).parse_daily_menu(&html)?.remove(0);
+/// assert_eq!(menu.items[1].description, "Bravčový rezeň, zemiakový šalát (1,3,7,10)");
+/// assert_eq!(menu.items[1].raw_portions, ["150g"]);
+/// assert_eq!(menu.items[1].details.as_deref(), Some("s citrónom"));
+/// assert_eq!(menu.items[1].tags, ["non-veg"]);
+/// assert_eq!(menu.items[1].id.as_deref(), Some("3719651"));
+/// assert_eq!(menu.items[2].id.as_deref(), Some("3719652"));
+/// # Ok::<_, zomato_core::Error>(())
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct MenuItem {
+    /// Description of the food
+    ///
+    /// HTML entities are decoded and whitespace is collapsed, see also `raw_description`.
+    pub description: String,
+    /// Description of the food exactly as received from Zomato.
+    #[serde(default)]
+    pub raw_description: String,
+    /// Food price exactly as received from Zomato.
+    ///
+    /// Note that sometimes the price may be empty! Use [`MenuItem::parsed_price`] to get the
+    /// amount and currency.
+    pub price: String,
+    /// Numbers of allergen groups declared in the description, sorted.
+    ///
+    /// Empty if the restaurant didn't declare any allergens or used notation this crate doesn't
+    /// understand. The description still contains them unless the parser was configured using
+    /// [`Parser::strip_allergens`].
+    #[serde(default)]
+    pub allergens: Vec<u8>,
+    /// The declaration of allergens as written in the description, e.g. "(1,3,7,10)".
+    ///
+    /// `None` if no declaration was found. It's kept even if the parser was configured to strip
+    /// the allergens from the description.
+    #[serde(default)]
+    pub raw_allergens: Option<String>,
+    /// Portion sizes found in the description, e.g. "300g".
+    ///
+    /// Some restaurants list multiple sizes, e.g. weights of meat and side dish. The description
+    /// still contains them unless the parser was configured using [`Parser::strip_portions`].
+    #[serde(default)]
+    pub portions: Vec<Quantity>,
+    /// The portion sizes as written in the description, in the same order as `portions`.
+    #[serde(default)]
+    pub raw_portions: Vec<String>,
+    /// Descriptions of the dish in multiple languages.
+    ///
+    /// Empty unless the page listed the dish in more languages and the parser was configured
+    /// using [`Parser::merge_translations`]. `description` contains one of them.
+    #[serde(default)]
+    pub description_translations: BTreeMap<Lang, String>,
+    /// Additional text Zomato displays below the name of the dish, e.g. the side dish.
+    ///
+    /// HTML entities are decoded and whitespace is collapsed like in `description`.
+    #[serde(default)]
+    pub details: Option<String>,
+    /// Tags attached to the dish, e.g. "veg" or "non-veg", exactly as received from Zomato.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Identifier of the dish assigned by Zomato, numeric identifiers are converted to strings.
+    #[serde(default)]
+    pub id: Option<String>,
+}
+
+impl MenuItem {
+    /// Parses the price of the food.
+    ///
+    /// Returns `None` if the price is empty or can't be parsed.
+    pub fn parsed_price(&self) -> Option<Price> {
+        self.price.parse().ok()
+    }
+
+    /// Parses the price of the food, returning `None` if it's outside the bounds too.
+    ///
+    /// Statistics should use this so that typos in prices don't distort them.
+    ///
+    /// ```
+    /// use zomato_core::{PriceBounds, PriceWarning};
+    ///
+    /// let html = zomato_core::testing::FIXTURE.replace("€6.00", "€0.00");
+    /// let menu = zomato_core::parse_daily_menu(&html)?.remove(0);
+    /// let bounds = PriceBounds::default();
+    /// assert!(menu.items[2].parsed_price().is_some());
+    /// assert!(menu.items[2].plausible_price(&bounds).is_none());
+    /// assert_eq!(menu.items[2].price_warning(&bounds), Some(PriceWarning::NotPositive));
+    ///
+    /// let bounds = PriceBounds::default().max("€", 5.0);
+    /// assert!(menu.items[1].plausible_price(&bounds).is_none());
+    /// # Ok::<_, zomato_core::Error>(())
+    /// ```
+    pub fn plausible_price(&self, bounds: &PriceBounds) -> Option<Price> {
+        self.parsed_price().filter(|price| bounds.is_plausible(price))
+    }
+
+    /// Returns the reason why the price is outside the bounds, `None` if it's plausible or
+    /// missing.
+    pub fn price_warning(&self, bounds: &PriceBounds) -> Option<PriceWarning> {
+        bounds.check(&self.parsed_price()?).err()
+    }
+
+    /// Returns the description in given language, falling back to [`description`](Self::description).
+    pub fn description_in(&self, lang: Lang) -> &str {
+        self.description_translations.get(&lang).unwrap_or(&self.description)
+    }
+}
+
+/// Daily menu of a restaurant.
+///
+/// This is a menu for specific day.
+///
+/// ```
+/// let menus = zomato_core::parse_daily_menu(zomato_core::testing::FIXTURE)?;
+/// let current = menus.iter().find(|menu| menu.is_current).expect("the fixture marks the menu");
+/// assert_eq!(current.valid_from, Some(1792054800));
+/// assert_eq!(current.valid_to, Some(1792069200));
+/// # Ok::<_, zomato_core::Error>(())
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct Menu {
+    /// Date of the menu.
+    pub date: String,
+    /// Food items offered at this day.
+    pub items: Vec<MenuItem>,
+    /// The menu was recognized from a photo using OCR, so the items may contain mistakes.
+    #[serde(default)]
+    pub ocr: bool,
+    /// Time since which the menu is served as Unix timestamp, if Zomato sent it.
+    #[serde(default)]
+    pub valid_from: Option<i64>,
+    /// Time until which the menu is served as Unix timestamp, if Zomato sent it.
+    #[serde(default)]
+    pub valid_to: Option<i64>,
+    /// Zomato marked this menu as the one served now.
+    ///
+    /// This is more reliable than assuming the first menu is today's, which isn't the case e.g.
+    /// in the evening when some restaurants already publish the menu for the next day. It's
+    /// `false` for all menus if the page doesn't mark any.
+    #[serde(default)]
+    pub is_current: bool,
+}
+
+impl Menu {
+    /// Formats the menu as the date followed by the dishes with aligned prices, one per line.
+    ///
+    /// The descriptions are aligned according to their width in terminal, so wide characters,
+    /// e.g. Chinese ones, and combining characters don't break the alignment.
+    ///
+    /// ```
+    /// let mut menu = zomato_core::parse_daily_menu(zomato_core::testing::FIXTURE)?.remove(0);
+    /// assert_eq!(menu.to_table_string(), "\
+    /// Today
+    /// Hovädzí vývar s rezancami (1,3,9)                | €1.20
+    /// 150g Bravčový rezeň, zemiakový šalát (1,3,7,10)  | €7.20
+    /// Vyprážaný syr, hranolky, tatárska omáčka (1,3,7) | €6.00
+    /// ");
+    ///
+    /// menu.items.truncate(2);
+    /// menu.items[0].description = "拉麺".to_owned();
+    /// menu.items[1].description = "Kava\u{301}".to_owned();
+    /// assert_eq!(menu.to_table_string(), "Today\n拉麺 | €1.20\nKava\u{301} | €7.20\n");
+    /// # Ok::<_, zomato_core::Error>(())
+    /// ```
+    pub fn to_table_string(&self) -> String {
+        let mut table = String::new();
+        write_table(std::slice::from_ref(self), |item| Cow::Borrowed(&item.description), &mut table)
+            .expect("writing into String never fails");
+        table
+    }
+}
+
+/// Writes the menus as a table with prices aligned across all of them.
+///
+/// Each menu starts with its date on a separate line, followed by a line for each dish. The
+/// descriptions are returned by `description`, which allows e.g. shortening or translating them,
+/// and aligned according to their width in terminal like in [`Menu::to_table_string`].
+///
+/// ```
+/// use std::borrow::Cow;
+///
+/// let menus = zomato_core::parse_daily_menu(zomato_core::testing::FIXTURE)?;
+/// let mut table = String::new();
+/// zomato_core::write_table(&menus, |item| Cow::Owned(item.description.to_uppercase()), &mut table).unwrap();
+/// assert_eq!(table.lines().nth(1).unwrap(), "HOVÄDZÍ VÝVAR S REZANCAMI (1,3,9)                | €1.20");
+/// # Ok::<_, zomato_core::Error>(())
+/// ```
+pub fn write_table<'a>(menus: &'a [Menu], description: impl Fn(&'a MenuItem) -> Cow<'a, str>, out: &mut dyn fmt::Write) -> fmt::Result {
+    use unicode_width::UnicodeWidthStr;
+
+    let width = menus
+        .iter()
+        .flat_map(|menu| &menu.items)
+        .map(|item| description(item).width())
+        .max()
+        .unwrap_or(0);
+
+    for menu in menus {
+        writeln!(out, "{}", menu.date)?;
+        for item in &menu.items {
+            let description = description(item);
+            writeln!(out, "{}{} | {}", description, " ".repeat(width - description.width()), item.price)?;
+        }
+    }
+    Ok(())
+}
+
+/// Displayed as a table, see [`Menu::to_table_string`].
+impl fmt::Display for Menu {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.to_table_string())
+    }
+}
+
+/// Basic information about a restaurant.
+///
+/// Zomato doesn't fill all the information for all restaurants so most of the fields are optional.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RestaurantInfo {
+    /// Human-readable name of the restaurant.
+    pub name: String,
+    /// Aggregate rating of the restaurant, usually between 0 and 5.
+    pub rating: Option<f32>,
+    /// Number of votes the rating is computed from.
+    pub votes: Option<u32>,
+    /// Opening hours as displayed by Zomato, e.g. "11am – 10pm (Mon-Sun)".
+    pub opening_hours: Option<String>,
+    /// Cuisines served by the restaurant.
+    pub cuisines: Vec<String>,
+}
+
+/// Parses daily menu from the HTML page previously downloaded from Zomato.
+///
+/// This is useful if you need to download the page yourself or if you want to process archived
+/// pages.
+///
+/// ```
+/// let menus = zomato_core::parse_daily_menu(zomato_core::testing::FIXTURE)?;
+/// assert_eq!(menus[0].date, "Today");
+/// assert_eq!(menus[0].items[0].price, "€1.20");
+/// # Ok::<_, zomato_core::Error>(())
+/// ```
+pub fn parse_daily_menu(html: &str) -> Result<Vec<Menu>, Error> {
+    Parser::new().parse_daily_menu(html)
+}
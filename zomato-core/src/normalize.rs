@@ -0,0 +1,98 @@
+//! Cleanup of texts coming from Zomato.
+
+use std::borrow::Cow;
+
+const ENTITIES: &[(&str, char)] = &[
+    ("amp", '&'),
+    ("lt", '<'),
+    ("gt", '>'),
+    ("quot", '"'),
+    ("apos", '\''),
+    ("nbsp", ' '),
+    ("ndash", '–'),
+    ("mdash", '—'),
+    ("hellip", '…'),
+    ("euro", '€'),
+    ("deg", '°'),
+    ("frac12", '½'),
+    ("times", '×'),
+    ("aacute", 'á'),
+    ("eacute", 'é'),
+    ("iacute", 'í'),
+    ("oacute", 'ó'),
+    ("uacute", 'ú'),
+    ("yacute", 'ý'),
+    ("auml", 'ä'),
+    ("ouml", 'ö'),
+    ("uuml", 'ü'),
+    ("ocirc", 'ô'),
+    ("szlig", 'ß'),
+];
+
+/// Decodes HTML entities and collapses whitespace.
+///
+/// Literal `\n`, `\r` and `\t` sequences are treated as whitespace too since they appear in
+/// descriptions which were escaped twice. The parser applies this to the descriptions of dishes.
+///
+/// ```
+/// assert_eq!(zomato_core::normalize("Syr&nbsp;&amp;\\n  hranolky"), "Syr & hranolky");
+/// ```
+pub fn normalize(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut pending_space = false;
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        let (decoded, len) = match c {
+            '&' => decode_entity(rest).unwrap_or(('&', 1)),
+            '\\' if rest[1..].starts_with(['n', 'r', 't']) => (' ', 2),
+            c => (c, c.len_utf8()),
+        };
+        rest = &rest[len..];
+
+        if decoded.is_whitespace() {
+            pending_space = true;
+        } else {
+            if pending_space && !result.is_empty() {
+                result.push(' ');
+            }
+            pending_space = false;
+            result.push(decoded);
+        }
+    }
+    result
+}
+
+/// Same as [`normalize`] but borrows the text if it doesn't need any changes.
+pub(crate) fn normalize_cow(text: &str) -> Cow<'_, str> {
+    let mut previous_space = true;
+    let normalized = text.chars().all(|c| {
+        let ok = match c {
+            '&' | '\\' => false,
+            ' ' => !previous_space,
+            c => !c.is_whitespace(),
+        };
+        previous_space = c == ' ';
+        ok
+    });
+    if normalized && !previous_space {
+        Cow::Borrowed(text)
+    } else {
+        Cow::Owned(normalize(text))
+    }
+}
+
+/// Decodes the entity at the beginning of the text returning the character and the length of the
+/// entity.
+fn decode_entity(text: &str) -> Option<(char, usize)> {
+    // The longest entity we know is "&#x10FFFF;"
+    let end = text.char_indices().take(12).find(|&(_, c)| c == ';')?.0;
+    let name = &text[1..end];
+    let c = if let Some(number) = name.strip_prefix("#x").or_else(|| name.strip_prefix("#X")) {
+        char::from_u32(u32::from_str_radix(number, 16).ok()?)?
+    } else if let Some(number) = name.strip_prefix('#') {
+        char::from_u32(number.parse().ok()?)?
+    } else {
+        ENTITIES.iter().find(|(entity, _)| *entity == name)?.1
+    };
+    Some((c, end + 1))
+}
@@ -0,0 +1,607 @@
+use serde_derive::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::marker::PhantomData;
+use crate::{allergens, portions, Error, Menu, MenuItem, RestaurantInfo};
+use crate::extraction::{extract_state, extract_states, web_api_state, Extraction};
+use crate::normalize::normalize;
+
+/// Handler of a section which is not modelled by this crate.
+///
+/// This is implemented for all `FnMut(serde_json::Value)` closures so you don't need to implement
+/// it manually most of the time.
+pub trait SectionHandler {
+    /// Called with the raw content of the section.
+    fn handle(&mut self, section: serde_json::Value);
+}
+
+impl<F: FnMut(serde_json::Value)> SectionHandler for F {
+    fn handle(&mut self, section: serde_json::Value) {
+        self(section)
+    }
+}
+
+/// Parser of Zomato pages.
+///
+/// Zomato pages contain many sections (with keys like `SECTION_BASIC_INFO`) this crate doesn't
+/// understand yet. The parser allows you to register handlers for them so that you can consume new
+/// features before this crate adds typed support.
+///
+/// Note that handlers are only called for sections not modelled by this crate. Registering a
+/// handler for `SECTION_DAILY_MENU` has no effect.
+///
+/// ```
+/// let mut info = None;
+/// let mut parser = zomato_core::Parser::new()
+///     .on_section("SECTION_BASIC_INFO", |section| info = Some(section));
+/// parser.parse_daily_menu(zomato_core::testing::FIXTURE).unwrap();
+/// drop(parser);
+/// assert_eq!(info.unwrap()["name"], "U Zlatého Bažanta");
+/// ```
+#[derive(Default)]
+pub struct Parser<'a> {
+    handlers: HashMap<String, Box<dyn SectionHandler + Send + 'a>>,
+    fallback: Option<FallbackHandler<'a>>,
+    strip_allergens: bool,
+    strip_portions: bool,
+    merge_translations: bool,
+}
+
+type FallbackHandler<'a> = Box<dyn FnMut(&str, serde_json::Value) + Send + 'a>;
+
+impl<'a> Parser<'a> {
+    /// Creates a parser without any handlers.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Registers handler for section with given key.
+    ///
+    /// If a handler was already registered for the key it is replaced.
+    pub fn on_section<H: SectionHandler + Send + 'a>(mut self, key: impl Into<String>, handler: H) -> Self {
+        self.handlers.insert(key.into(), Box::new(handler));
+        self
+    }
+
+    /// Registers handler called for all unknown sections that don't have a specific handler.
+    ///
+    /// The handler receives the key of the section along with its content.
+    pub fn on_unknown_section<F: FnMut(&str, serde_json::Value) + Send + 'a>(mut self, handler: F) -> Self {
+        self.fallback = Some(Box::new(handler));
+        self
+    }
+
+    /// Removes allergen codes from descriptions of dishes if set to `true`.
+    ///
+    /// The codes are available in [`MenuItem::allergens`] regardless of this setting. Stripping
+    /// them is useful for cleaner display or text-to-speech.
+    ///
+    /// ```
+    /// let mut parser = zomato_core::Parser::new().strip_allergens(true);
+    /// let menu = parser.parse_daily_menu(zomato_core::testing::FIXTURE).unwrap().remove(0);
+    /// assert_eq!(menu.items[1].description, "150g Bravčový rezeň, zemiakový šalát");
+    /// assert_eq!(menu.items[1].allergens, [1, 3, 7, 10]);
+    /// ```
+    pub fn strip_allergens(mut self, strip: bool) -> Self {
+        self.strip_allergens = strip;
+        self
+    }
+
+    /// Removes portion sizes from descriptions of dishes if set to `true`.
+    ///
+    /// The sizes are available in [`MenuItem::portions`] regardless of this setting, so that they
+    /// can be rendered separately.
+    ///
+    /// ```
+    /// let mut parser = zomato_core::Parser::new().strip_portions(true);
+    /// let menu = parser.parse_daily_menu(zomato_core::testing::FIXTURE).unwrap().remove(0);
+    /// assert_eq!(menu.items[1].description, "Bravčový rezeň, zemiakový šalát (1,3,7,10)");
+    /// assert_eq!(menu.items[1].portions[0].amount(), 150.0);
+    /// ```
+    pub fn strip_portions(mut self, strip: bool) -> Self {
+        self.strip_portions = strip;
+        self
+    }
+
+    /// Merges dishes listed in two languages into single items if set to `true`.
+    ///
+    /// Some restaurants list each dish twice, e.g. in Czech and then in English. With this
+    /// setting such pairs are detected and merged into a single item, the descriptions in both
+    /// languages are available in [`MenuItem::description_translations`]. Formatters can then
+    /// display the preferred one, see `TableFormatter::language` in the `zomato` crate.
+    ///
+    /// ```
+    /// use zomato_core::Lang;
+    ///
+    /// let html = zomato_core::testing::FIXTURE.replace("150g Bravčový rezeň, zemiakový šalát (1,3,7,10)", "Hovädzí guláš, knedľa (1)").replace(
+    ///     "Vyprážaný syr, hranolky, tatárska omáčka (1,3,7)",
+    ///     "Beef goulash with dumplings (1)",
+    /// ).replace("€6.00", "");
+    /// let mut parser = zomato_core::Parser::new().merge_translations(true);
+    /// let menu = parser.parse_daily_menu(&html).unwrap().remove(0);
+    /// assert_eq!(menu.items.len(), 2);
+    /// assert_eq!(menu.items[1].description, "Hovädzí guláš, knedľa (1)");
+    /// assert_eq!(menu.items[1].description_in(Lang::English), "Beef goulash with dumplings (1)");
+    /// ```
+    pub fn merge_translations(mut self, merge: bool) -> Self {
+        self.merge_translations = merge;
+        self
+    }
+
+    /// Parses daily menu from the HTML page previously downloaded from Zomato, calling registered
+    /// handlers.
+    ///
+    /// The menus are returned the earliest first, see [`Page::menus`].
+    pub fn parse_daily_menu(&mut self, html: &str) -> Result<Vec<Menu>, Error> {
+        self.parse_page(html).map(|page| page.menus)
+    }
+
+    /// Parses the daily menu and the basic information about the restaurant from the page.
+    ///
+    /// ```
+    /// let page = zomato_core::Parser::new().parse_page(zomato_core::testing::FIXTURE)?;
+    /// assert_eq!(page.info.unwrap().name, "U Zlatého Bažanta");
+    /// assert_eq!(page.menus[0].date, "Today");
+    /// # Ok::<_, zomato_core::Error>(())
+    /// ```
+    pub fn parse_page(&mut self, html: &str) -> Result<Page, Error> {
+        self.parse_page_inner(html).map_err(Error)
+    }
+
+    fn dispatch(&mut self, sections: Ordered<String, serde_json::Value>) {
+        for (key, section) in sections.0 {
+            if let Some(handler) = self.handlers.get_mut(&key) {
+                handler.handle(section);
+            } else if let Some(fallback) = &mut self.fallback {
+                fallback(&key, section);
+            }
+        }
+    }
+
+    /// Creates menu item extracting the information embedded in the description.
+    ///
+    /// This processes the description and price as received from Zomato the same way as the
+    /// items of parsed pages, so it's useful for descriptions stored elsewhere, e.g. in archives.
+    pub fn menu_item(&self, raw_description: String, price: String) -> MenuItem {
+        let mut description = normalize(&raw_description);
+        let (allergens, raw_allergens) = match allergens::find(&description) {
+            Some(found) => {
+                let raw_allergens = description[found.range.clone()].to_owned();
+                if self.strip_allergens {
+                    description = allergens::strip(&description, found.range);
+                }
+                (found.codes, Some(raw_allergens))
+            },
+            None => (Vec::new(), None),
+        };
+        let portions = portions::find(&description);
+        let raw_portions = portions.iter().map(|(_, range)| description[range.clone()].to_owned()).collect();
+        if self.strip_portions && !portions.is_empty() {
+            description = portions::strip(&description, portions.iter().map(|(_, range)| range.clone()));
+        }
+
+        MenuItem {
+            description,
+            raw_description,
+            price,
+            allergens,
+            raw_allergens,
+            portions: portions.into_iter().map(|(quantity, _)| quantity).collect(),
+            raw_portions,
+            description_translations: BTreeMap::new(),
+            details: None,
+            tags: Vec::new(),
+            id: None,
+        }
+    }
+
+    /// Parses the response of the web API used by the Zomato app.
+    ///
+    /// The response contains the same sections as the state of the page, it's used as the last
+    /// resort if the data can't be found in the page.
+    ///
+    /// ```
+    /// let state = zomato_core::extract_preloaded_state(zomato_core::testing::FIXTURE)?;
+    /// let state = serde_json::from_str::<serde_json::Value>(&state).unwrap();
+    /// let sections = state["pages"]["restaurant"]["16508052"]["sections"].clone();
+    /// let response = serde_json::json!({ "page_data": { "sections": sections } }).to_string();
+    ///
+    /// let page = zomato_core::Parser::new().parse_web_api(&response)?;
+    /// assert_eq!(page.extraction, zomato_core::Extraction::WebApi);
+    /// assert_eq!(page.menus[0].items[1].price, "€7.20");
+    /// # Ok::<_, zomato_core::Error>(())
+    /// ```
+    pub fn parse_web_api(&mut self, json: &str) -> Result<Page, Error> {
+        web_api_state(json)
+            .and_then(|state| self.parse_state(state, Extraction::WebApi))
+            .map_err(Error)
+    }
+
+    fn parse_page_inner(&mut self, html: &str) -> Result<Page, anyhow::Error> {
+        // The error of the first strategy is the most useful if none of them succeeds
+        let mut first_error = None;
+        for (state, extraction) in extract_states(html) {
+            match self.parse_state(state, extraction) {
+                Ok(page) => return Ok(page),
+                Err(error) => { first_error.get_or_insert(error); },
+            }
+        }
+        Err(first_error.unwrap_or_else(|| anyhow::anyhow!("data not found")))
+    }
+
+    fn parse_state(&mut self, state: String, extraction: Extraction) -> Result<Page, anyhow::Error> {
+        use anyhow::Context;
+
+        let data = from_json::<Data>(state).context("failed to parse json")?;
+        let sections = data
+            .pages
+            .restaurant
+            .0
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("missing restaurant"))?
+            .1
+            .sections;
+
+        // The basic info is still passed to the handlers since it's not modelled completely.
+        let info = sections
+            .other
+            .0
+            .iter()
+            .find(|(key, _)| key == "SECTION_BASIC_INFO")
+            .and_then(|(_, section)| parse_restaurant_info(section));
+
+        self.dispatch(sections.other);
+
+        let mut menus = sections
+            .daily_menu
+            .into_iter()
+            .filter_map(Tolerant::known)
+            .map(|menu| {
+                let items = menu
+                    .dishes
+                    .into_iter()
+                    .filter_map(Tolerant::known)
+                    .map(|item| MenuItem {
+                        details: item.details.as_deref().map(normalize).filter(|details| !details.is_empty()),
+                        tags: item.tags.as_array().into_iter().flatten().filter_map(|tag| tag.as_str()).map(Into::into).collect(),
+                        // Identifiers are sent both as numbers and strings
+                        id: match item.id {
+                            serde_json::Value::String(id) => Some(id),
+                            serde_json::Value::Number(id) => Some(id.to_string()),
+                            _ => None,
+                        },
+                        ..self.menu_item(item.name, item.price)
+                    })
+                    .collect::<Vec<_>>();
+                let items = if self.merge_translations { crate::lang::merge_translations(items) } else { items };
+                Menu {
+                    items,
+                    date: menu.date,
+                    ocr: false,
+                    valid_from: timestamp(&menu.valid_from),
+                    valid_to: timestamp(&menu.valid_to),
+                    is_current: menu.is_current.as_bool().unwrap_or(false),
+                }
+            })
+            .collect::<Vec<_>>();
+        // Zomato lists the menus chronologically, the timestamps are only used if all menus have them
+        if menus.iter().all(|menu| menu.valid_from.is_some()) {
+            menus.sort_by_key(|menu| menu.valid_from);
+        }
+
+        Ok(Page { menus, info, extraction, })
+    }
+}
+
+/// Text preceding the JSON containing the state of the page.
+///
+/// The JSON is escaped as a JavaScript string.
+pub const PRELOADED_STATE_START: &str = "window.__PRELOADED_STATE__ = JSON.parse(\"";
+
+/// Text following the JSON containing the state of the page.
+///
+/// Everything this crate parses is in the JSON, so downloading the page can stop here.
+pub const PRELOADED_STATE_END: &str = "\")\n";
+
+/// Extracts JSON containing the state of the page.
+///
+/// Zomato embeds all the data into a script which passes escaped JSON string to `JSON.parse`.
+/// This is useful for parsing sections of pages not modelled by this crate, e.g. search results.
+/// Pages using other formats are handled by the fallback strategies described in [`Extraction`].
+///
+/// ```
+/// let state = zomato_core::extract_preloaded_state(zomato_core::testing::FIXTURE)?;
+/// assert!(state.starts_with(r#"{"pages":{"restaurant":"#));
+///
+/// // only scripts are searched
+/// let html = r#"<p>window.__PRELOADED_STATE__ = JSON.parse("{}")</p>"#;
+/// assert!(zomato_core::extract_preloaded_state(html).is_err());
+/// # Ok::<_, zomato_core::Error>(())
+/// ```
+pub fn extract_preloaded_state(html: &str) -> Result<String, Error> {
+    extract_preloaded_state_internal(html).map_err(Error)
+}
+
+pub(crate) fn extract_preloaded_state_internal(html: &str) -> Result<String, anyhow::Error> {
+    extract_state(html).map(|(state, _)| state)
+}
+
+/// Replaces escaped quotes, calling `on_quote` with the position of each replaced quote in the
+/// result.
+pub(crate) fn unescape(json_escaped: &str, mut on_quote: impl FnMut(usize)) -> String {
+    let mut json_unescaped = String::with_capacity(json_escaped.len());
+    for piece in json_escaped.split("\\\"") {
+        if !json_unescaped.is_empty() {
+            on_quote(json_unescaped.len());
+            json_unescaped.push('"');
+        }
+        json_unescaped.push_str(piece);
+    }
+    json_unescaped
+}
+
+/// Finds the escaped JSON in the script assigning the preloaded state.
+///
+/// Building the DOM of the whole page just to find one script is slow, so the page is searched
+/// for the text preceding the JSON instead and only the occurrences inside `<script>` elements
+/// are accepted.
+pub(crate) fn find_preloaded_state(html: &str) -> Option<&str> {
+    let data_start = find_data_start(html, 0)?;
+    let json_with_tail = &html[data_start..];
+    let script_end = json_with_tail.find("</script").unwrap_or(json_with_tail.len());
+    let script = &json_with_tail[..script_end];
+    Some(script.split(PRELOADED_STATE_END).next().expect("empty split"))
+}
+
+/// Returns the position right after the first start marker inside a script, searching from
+/// given position.
+fn find_data_start(html: &str, mut searched: usize) -> Option<usize> {
+    loop {
+        let start = searched + html[searched..].find(PRELOADED_STATE_START)?;
+        searched = start + PRELOADED_STATE_START.len();
+        if in_script(&html[..start]) {
+            return Some(searched);
+        }
+    }
+}
+
+/// Finds the end of the data embedded in a page while it's being downloaded.
+///
+/// Everything this crate parses is in the script assigning the preloaded state, which is a small
+/// part of the page, so downloading can stop after it. The page is scanned incrementally as the
+/// chunks arrive, finding the same script as [`extract_preloaded_state`] does.
+///
+/// ```
+/// let html = r#"<p>window.__PRELOADED_STATE__ = JSON.parse("{}")</p><script>window.__PRELOADED_STATE__ = JSON.parse("{\"a\":1}")
+/// </script><footer>"#;
+/// let mut scanner = zomato_core::StateScanner::new();
+/// assert_eq!(scanner.end(&html[..60]), None);
+/// let end = scanner.end(html).unwrap();
+/// assert!(html[..end].ends_with(zomato_core::PRELOADED_STATE_END));
+/// assert_eq!(zomato_core::extract_preloaded_state(&html[..end])?, r#"{"a":1}"#);
+/// # Ok::<_, zomato_core::Error>(())
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct StateScanner {
+    /// Position right after the start marker if it was found.
+    data_start: Option<usize>,
+    /// How much of the page was already searched for the current marker.
+    scanned: usize,
+}
+
+impl StateScanner {
+    /// Creates scanner for a new page.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the length of the page up to the end of the embedded data if it was received.
+    ///
+    /// `html` is the part of the page received so far, each call must pass the previous part
+    /// extended by the new data.
+    pub fn end(&mut self, html: &str) -> Option<usize> {
+        let data_start = match self.data_start {
+            Some(data_start) => data_start,
+            None => {
+                // the marker may be split between chunks
+                let from = char_boundary(html, self.scanned.saturating_sub(PRELOADED_STATE_START.len() - 1));
+                self.scanned = html.len();
+                let data_start = find_data_start(html, from)?;
+                self.data_start = Some(data_start);
+                self.scanned = data_start;
+                data_start
+            },
+        };
+        let from = char_boundary(html, self.scanned.saturating_sub("</script".len() - 1)).max(data_start);
+        self.scanned = html.len();
+        let json_with_tail = &html[from..];
+        // the data ends at the end marker or at the end of the script, whichever comes first
+        let data_end = json_with_tail.find(PRELOADED_STATE_END).map(|end| end + PRELOADED_STATE_END.len());
+        let script_end = json_with_tail.find("</script");
+        let end = match (data_end, script_end) {
+            (Some(data_end), Some(script_end)) => data_end.min(script_end),
+            (data_end, script_end) => data_end.or(script_end)?,
+        };
+        Some(from + end)
+    }
+}
+
+/// Moves the position back to the nearest boundary of characters.
+fn char_boundary(text: &str, mut position: usize) -> usize {
+    while !text.is_char_boundary(position) {
+        position -= 1;
+    }
+    position
+}
+
+/// Checks if the end of the text is inside a `<script>` element.
+pub(crate) fn in_script(before: &str) -> bool {
+    match (before.rfind("<script"), before.rfind("</script")) {
+        (Some(open), Some(close)) => open > close,
+        (Some(_), None) => true,
+        (None, _) => false,
+    }
+}
+
+/// Information parsed from a single page.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct Page {
+    /// Daily menus listed on the page, the earliest first.
+    ///
+    /// The menus are sorted by [`Menu::valid_from`] if all of them have it, otherwise they are in
+    /// the order of the page, in which Zomato lists them chronologically.
+    pub menus: Vec<Menu>,
+    /// Basic information about the restaurant, `None` if it's missing or can't be parsed.
+    pub info: Option<RestaurantInfo>,
+    /// The strategy which found the data in the page.
+    pub extraction: Extraction,
+}
+
+// The basic info section changes more often than the daily menu so we're parsing it leniently.
+fn parse_restaurant_info(section: &serde_json::Value) -> Option<RestaurantInfo> {
+    // Numbers are sometimes sent as strings
+    fn number(value: &serde_json::Value) -> Option<f64> {
+        value.as_f64().or_else(|| value.as_str()?.trim().parse().ok())
+    }
+
+    let name = section["name"].as_str()?.to_owned();
+    let rating = number(&section["rating"]["aggregate_rating"]).map(|rating| rating as f32);
+    let votes = number(&section["rating"]["votes"]).map(|votes| votes as u32);
+    let opening_hours = section["timing"]["timing_desc"].as_str().map(ToOwned::to_owned);
+    let cuisines = section["cuisine_string"]
+        .as_str()
+        .map(|cuisines| cuisines.split(',').map(str::trim).filter(|cuisine| !cuisine.is_empty()).map(ToOwned::to_owned).collect())
+        .unwrap_or_default();
+
+    Some(RestaurantInfo {
+        name,
+        rating,
+        votes,
+        opening_hours,
+        cuisines,
+    })
+}
+
+/// Deserializes the unescaped embedded data.
+#[cfg(not(feature = "simd"))]
+fn from_json<T: serde::de::DeserializeOwned>(json: String) -> Result<T, anyhow::Error> {
+    Ok(serde_json::from_str(&json)?)
+}
+
+/// Deserializes the unescaped embedded data.
+///
+/// simd-json modifies the buffer in place, which is fine since the JSON isn't used afterwards.
+#[cfg(feature = "simd")]
+fn from_json<T: serde::de::DeserializeOwned>(json: String) -> Result<T, anyhow::Error> {
+    let mut json = json.into_bytes();
+    Ok(simd_json::serde::from_slice(&mut json)?)
+}
+
+// Zomato changes the state of the page from time to time, so only the description of the dish is
+// required. Entries which can't be understood are skipped instead of failing the whole page.
+
+#[derive(Deserialize, Debug)]
+struct InternalMenuItem {
+    name: String,
+    #[serde(rename = "displayPrice", alias = "price", default)]
+    price: String,
+    #[serde(rename = "subtext", default)]
+    details: Option<String>,
+    // Lenient so that unexpected types don't make the whole dish unknown
+    #[serde(default)]
+    tags: serde_json::Value,
+    #[serde(default)]
+    id: serde_json::Value,
+}
+
+#[derive(Deserialize)]
+struct DailyMenu {
+    #[serde(default)]
+    dishes: Vec<Tolerant<InternalMenuItem>>,
+    #[serde(rename = "timeHeading", alias = "heading", default)]
+    date: String,
+    // Lenient so that unexpected types don't make the whole menu unknown
+    #[serde(rename = "startTime", default)]
+    valid_from: serde_json::Value,
+    #[serde(rename = "endTime", default)]
+    valid_to: serde_json::Value,
+    #[serde(rename = "isCurrent", default)]
+    is_current: serde_json::Value,
+}
+
+/// Parses Unix timestamp of the daily menu.
+pub(crate) fn timestamp(value: &serde_json::Value) -> Option<i64> {
+    // Numbers are sometimes sent as strings
+    value.as_i64().or_else(|| value.as_str()?.trim().parse().ok())
+}
+
+/// Entries of an object in the order in which they appear in the page.
+///
+/// Used instead of `HashMap` so that the results don't depend on the order of iteration.
+pub(crate) struct Ordered<K, V>(pub(crate) Vec<(K, V)>);
+
+impl<'de, K: serde::Deserialize<'de>, V: serde::Deserialize<'de>> serde::Deserialize<'de> for Ordered<K, V> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor<K, V>(PhantomData<(K, V)>);
+
+        impl<'de, K: serde::Deserialize<'de>, V: serde::Deserialize<'de>> serde::de::Visitor<'de> for Visitor<K, V> {
+            type Value = Ordered<K, V>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an object")
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut entries = Vec::with_capacity(map.size_hint().unwrap_or(0));
+                while let Some(entry) = map.next_entry()? {
+                    entries.push(entry);
+                }
+                Ok(Ordered(entries))
+            }
+        }
+
+        deserializer.deserialize_map(Visitor(PhantomData))
+    }
+}
+
+/// Entry which is skipped if it doesn't have the expected structure.
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub(crate) enum Tolerant<T> {
+    Known(T),
+    Unknown(serde::de::IgnoredAny),
+}
+
+impl<T> Tolerant<T> {
+    pub(crate) fn known(self) -> Option<T> {
+        match self {
+            Tolerant::Known(value) => Some(value),
+            Tolerant::Unknown(_) => None,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Sections {
+    #[serde(rename = "SECTION_DAILY_MENU", default)]
+    daily_menu: Vec<Tolerant<DailyMenu>>,
+    #[serde(flatten)]
+    other: Ordered<String, serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct UnknownObject {
+    sections: Sections,
+}
+
+#[derive(Deserialize)]
+struct Pages {
+    restaurant: Ordered<String, UnknownObject>,
+}
+
+#[derive(Deserialize)]
+struct Data {
+    pages: Pages,
+}
@@ -0,0 +1,197 @@
+//! Extraction of portion sizes from descriptions of dishes.
+
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
+use serde_derive::{Deserialize, Serialize};
+
+/// Size of a portion, e.g. "300g" or "0,33l".
+///
+/// The amount is always a finite number, so quantities can be compared and hashed.
+///
+/// ```
+/// use zomato_core::{Quantity, Unit};
+///
+/// let menu = zomato_core::parse_daily_menu(zomato_core::testing::FIXTURE)?.remove(0);
+/// assert_eq!(menu.items[1].portions, [Quantity::new(150.0, Unit::Gram).unwrap()]);
+/// assert_eq!(menu.items[1].portions[0].to_string(), "150 g");
+/// assert!(Quantity::new(f64::NAN, Unit::Gram).is_none());
+/// # Ok::<_, zomato_core::Error>(())
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Serialize)]
+pub struct Quantity {
+    amount: f64,
+    /// Unit of the amount.
+    pub unit: Unit,
+}
+
+impl Quantity {
+    /// Creates the quantity, returns `None` if the amount is NaN or infinite.
+    pub fn new(amount: f64, unit: Unit) -> Option<Self> {
+        // Adding zero turns -0.0 into 0.0, so equal quantities have the same bits
+        amount.is_finite().then_some(Quantity { amount: amount + 0.0, unit })
+    }
+
+    /// The amount in given unit.
+    pub fn amount(&self) -> f64 {
+        self.amount
+    }
+}
+
+// The amount is never NaN, see `Quantity::new`.
+impl Eq for Quantity {}
+
+impl Hash for Quantity {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.amount.to_bits().hash(state);
+        self.unit.hash(state);
+    }
+}
+
+/// Rejects amounts which are not finite, e.g. in a corrupted cache.
+impl<'de> serde::Deserialize<'de> for Quantity {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(rename = "Quantity")]
+        struct Raw {
+            amount: f64,
+            unit: Unit,
+        }
+
+        let raw = <Raw as serde::Deserialize>::deserialize(deserializer)?;
+        Quantity::new(raw.amount, raw.unit).ok_or_else(|| serde::de::Error::custom("the amount of quantity is not a finite number"))
+    }
+}
+
+impl fmt::Display for Quantity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.amount, self.unit.symbol())
+    }
+}
+
+/// Unit of [`Quantity`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum Unit {
+    /// Weight in grams.
+    Gram,
+    /// Weight in kilograms.
+    Kilogram,
+    /// Volume in milliliters.
+    Milliliter,
+    /// Volume in centiliters.
+    Centiliter,
+    /// Volume in deciliters.
+    Deciliter,
+    /// Volume in liters.
+    Liter,
+    /// Number of pieces.
+    Piece,
+}
+
+impl Unit {
+    /// Returns the usual abbreviation of the unit.
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Unit::Gram => "g",
+            Unit::Kilogram => "kg",
+            Unit::Milliliter => "ml",
+            Unit::Centiliter => "cl",
+            Unit::Deciliter => "dl",
+            Unit::Liter => "l",
+            Unit::Piece => "pcs",
+        }
+    }
+}
+
+// Longer symbols first so that "gr" isn't rejected as "g" followed by a letter
+const UNITS: &[(&str, Unit)] = &[
+    ("pcs", Unit::Piece),
+    ("gr", Unit::Gram),
+    ("kg", Unit::Kilogram),
+    ("ml", Unit::Milliliter),
+    ("cl", Unit::Centiliter),
+    ("dl", Unit::Deciliter),
+    ("ks", Unit::Piece),
+    ("pc", Unit::Piece),
+    ("g", Unit::Gram),
+    ("l", Unit::Liter),
+];
+
+/// Finds all quantities in the description along with their positions.
+pub(crate) fn find(description: &str) -> Vec<(Quantity, Range<usize>)> {
+    let mut result = Vec::new();
+    let mut chars = description.char_indices().peekable();
+    let mut previous = None::<char>;
+    while let Some((start, c)) = chars.next() {
+        let at_word_start = previous.is_none_or(|previous| !previous.is_alphanumeric());
+        previous = Some(c);
+        if !c.is_ascii_digit() || !at_word_start {
+            continue;
+        }
+
+        let mut end = start + 1;
+        let mut seen_separator = false;
+        while let Some(&(i, c)) = chars.peek() {
+            let is_separator = (c == ',' || c == '.') && !seen_separator && description[(i + 1)..].starts_with(|c: char| c.is_ascii_digit());
+            if !c.is_ascii_digit() && !is_separator {
+                break;
+            }
+            seen_separator |= is_separator;
+            end = i + 1;
+            previous = Some(c);
+            chars.next();
+        }
+
+        let rest = &description[end..];
+        let unit_start = end + (rest.len() - rest.trim_start().len());
+        let rest = &description[unit_start..];
+        let unit = UNITS.iter().find(|(symbol, _)| {
+            rest.get(..symbol.len()).is_some_and(|prefix| prefix.eq_ignore_ascii_case(symbol))
+                && !rest[symbol.len()..].starts_with(char::is_alphanumeric)
+        });
+        if let Some((symbol, unit)) = unit {
+            let amount = description[start..end].replace(',', ".").parse().expect("validated above");
+            let unit_end = unit_start + symbol.len();
+            let quantity = Quantity::new(amount, *unit).expect("digits are a finite number");
+            result.push((quantity, start..unit_end));
+            while chars.peek().is_some_and(|&(i, _)| i < unit_end) {
+                previous = chars.next().map(|(_, c)| c);
+            }
+        }
+    }
+    result
+}
+
+/// Removes the ranges from the description along with separators between them.
+pub(crate) fn strip(description: &str, ranges: impl IntoIterator<Item = Range<usize>>) -> String {
+    let is_separator = |text: &str| text.chars().all(|c| c.is_whitespace() || c == '/' || c == '+');
+
+    let mut merged = Vec::<Range<usize>>::new();
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if is_separator(&description[last.end..range.start]) => last.end = range.end,
+            _ => merged.push(range),
+        }
+    }
+
+    let mut result = String::with_capacity(description.len());
+    let mut position = 0;
+    for range in merged {
+        let before = description[position..range.start].trim_end();
+        result.push_str(before);
+        position = range.end;
+        let after = description[position..].trim_start();
+        if !result.is_empty() && after.starts_with(char::is_alphanumeric) {
+            result.push(' ');
+        }
+        position = description.len() - after.len();
+    }
+    result.push_str(&description[position..]);
+    // The quantity might have been followed by a comma, e.g. "300g, chicken"
+    let trimmed = result.trim_start_matches(|c: char| c == ',' || c.is_whitespace());
+    if trimmed.len() != result.len() {
+        result = trimmed.to_owned();
+    }
+    result
+}
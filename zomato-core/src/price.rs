@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Price parsed from the string displayed by Zomato.
+///
+/// Zomato displays prices in various formats depending on the country, e.g. "€7.20", "129 Kč" or
+/// "Rs. 250". The amount is stored as floating point number which is good enough for statistics
+/// but you should use the original string for display.
+///
+/// ```
+/// use zomato_core::Price;
+///
+/// let price = "€7.20".parse::<Price>().unwrap();
+/// assert_eq!(price.amount, 7.2);
+/// assert_eq!(price.currency, "€");
+///
+/// let price = "129,- Kč".parse::<Price>().unwrap();
+/// assert_eq!(price.amount, 129.0);
+/// assert_eq!(price.currency, "Kč");
+///
+/// let price = "-5 €".parse::<Price>().unwrap();
+/// assert_eq!(price.amount, -5.0);
+/// assert_eq!(price.currency, "€");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Price {
+    /// Numeric value of the price in the main unit of the currency (e.g. euros, not cents).
+    pub amount: f64,
+    /// Currency symbol or code as displayed, e.g. "€" or "Kč". Empty if missing.
+    pub currency: String,
+}
+
+/// Error returned when the price can't be parsed.
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("failed to parse price '{input}'")]
+pub struct ParsePriceError {
+    input: String,
+}
+
+impl FromStr for Price {
+    type Err = ParsePriceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_price(s).ok_or_else(|| ParsePriceError { input: s.to_owned() })
+    }
+}
+
+impl fmt::Display for Price {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.currency.is_empty() {
+            write!(f, "{:.2}", self.amount)
+        } else {
+            write!(f, "{:.2} {}", self.amount, self.currency)
+        }
+    }
+}
+
+/// Range of plausible prices of a dish.
+///
+/// Prices are typed by the restaurants and occasionally contain typos like "0 Kč" or "€720",
+/// which would make e.g. the cheapest lunch wrong. Prices which are not positive or above the
+/// maximum for their currency are therefore considered wrong and excluded from statistics computed
+/// by this crate and the `zomato` crate, see [`MenuItem::plausible_price`](crate::MenuItem::plausible_price).
+/// The default maximums are generous prices of a lunch in common currencies, prices in other
+/// currencies are limited only by [`default_max`](Self::default_max). The functions computing
+/// statistics accept the bounds and use [`PriceBounds::default`] unless given others.
+///
+/// ```
+/// use zomato_core::{Price, PriceBounds, PriceWarning};
+///
+/// let bounds = PriceBounds::default().max("€", 20.0);
+/// assert!(bounds.is_plausible(&"€7.20".parse::<Price>()?));
+/// assert_eq!(bounds.check(&"€72,00".parse::<Price>()?), Err(PriceWarning::TooHigh { max: 20.0 }));
+/// assert_eq!(bounds.check(&"0 Kč".parse::<Price>()?), Err(PriceWarning::NotPositive));
+/// assert_eq!(bounds.check(&"-5 €".parse::<Price>()?), Err(PriceWarning::NotPositive));
+/// assert_eq!(bounds.check(&"€-7.20".parse::<Price>()?), Err(PriceWarning::NotPositive));
+/// # Ok::<_, zomato_core::ParsePriceError>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct PriceBounds {
+    max: HashMap<String, f64>,
+    default_max: Option<f64>,
+}
+
+impl PriceBounds {
+    /// Creates bounds rejecting only prices which are not positive.
+    pub fn unbounded() -> Self {
+        PriceBounds {
+            max: HashMap::new(),
+            default_max: None,
+        }
+    }
+
+    /// Sets the maximum price in given currency, as displayed by Zomato.
+    pub fn max(mut self, currency: impl Into<String>, max: f64) -> Self {
+        self.max.insert(currency.into(), max);
+        self
+    }
+
+    /// Sets the maximum price in currencies without specific maximum.
+    pub fn default_max(mut self, max: f64) -> Self {
+        self.default_max = Some(max);
+        self
+    }
+
+    /// Checks if the price is plausible.
+    pub fn check(&self, price: &Price) -> Result<(), PriceWarning> {
+        if price.amount <= 0.0 {
+            return Err(PriceWarning::NotPositive);
+        }
+        match self.max.get(&price.currency).copied().or(self.default_max) {
+            Some(max) if price.amount > max => Err(PriceWarning::TooHigh { max }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns `true` if the price is within the bounds.
+    pub fn is_plausible(&self, price: &Price) -> bool {
+        self.check(price).is_ok()
+    }
+}
+
+impl Default for PriceBounds {
+    fn default() -> Self {
+        PriceBounds::unbounded()
+            .max("€", 100.0)
+            .max("$", 100.0)
+            .max("£", 100.0)
+            .max("Kč", 2500.0)
+            .max("zł", 400.0)
+            .max("Ft", 40000.0)
+            .max("Rs.", 10000.0)
+            .max("₹", 10000.0)
+    }
+}
+
+/// Reason why a price is considered wrong.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[non_exhaustive]
+pub enum PriceWarning {
+    /// The price is zero or negative.
+    #[error("the price is not positive")]
+    NotPositive,
+    /// The price is higher than the maximum for its currency.
+    #[error("the price is higher than {max}")]
+    TooHigh {
+        /// The maximum price in the currency.
+        max: f64,
+    },
+}
+
+fn parse_price(s: &str) -> Option<Price> {
+    let start = s.find(|c: char| c.is_ascii_digit())?;
+    let rest = &s[start..];
+    let mut end = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+    let mut amount = rest[..end].to_owned();
+    // Decimal separator is either dot or comma followed by one or two digits
+    if let Some(separator) = rest[end..].chars().next().filter(|c| *c == '.' || *c == ',') {
+        let decimals = &rest[(end + separator.len_utf8())..];
+        let decimals_len = decimals.find(|c: char| !c.is_ascii_digit()).unwrap_or(decimals.len());
+        if decimals_len > 0 && decimals_len <= 2 {
+            amount.push('.');
+            amount.push_str(&decimals[..decimals_len]);
+            end += separator.len_utf8() + decimals_len;
+        }
+    }
+    let amount = amount.parse::<f64>().ok()?;
+    let before = s[..start].trim();
+    // The minus sign is either before or after the currency, e.g. "-€5" or "€-5"
+    let (before, amount) = match before.strip_suffix(['-', '−']).or_else(|| before.strip_prefix(['-', '−'])) {
+        Some(before) => (before.trim(), -amount),
+        None => (before, amount),
+    };
+    let after = rest[end..].trim_start_matches(['.', ',', '-']).trim();
+    let currency = if before.is_empty() { after } else { before };
+    // Some restaurants append a slash or other noise, we only keep the first word.
+    let currency = currency.split_whitespace().next().unwrap_or("").to_owned();
+
+    Some(Price {
+        amount,
+        currency,
+    })
+}
@@ -0,0 +1,156 @@
+//! Similarity of texts used when matching dishes.
+//!
+//! Descriptions of the same dish often differ slightly between days, e.g. by a typo, missing
+//! diacritics or changed allergens. Features which need to recognize such dishes, like
+//! [fuzzy keywords](crate::MenuQuery::fuzzy) or merging similar dishes in weekly digests of the
+//! `zomato` crate, use
+//! the [global](global) [`Similarity`] backend, [`JaroWinkler`] by default. [`Trigrams`] is a
+//! faster alternative for long descriptions and custom backends can be installed using
+//! [`set_global`].
+//!
+//! ```
+//! use zomato_core::similarity::{self, JaroWinkler, Similarity, Trigrams};
+//!
+//! assert!(JaroWinkler.similarity("Bravčový rezeň", "Bravcovy rezen") > 0.85);
+//! assert_eq!(Trigrams.similarity("guláš", "guláš"), 1.0);
+//!
+//! similarity::set_global(Trigrams);
+//! assert_eq!(similarity::global().similarity("guláš", "GULÁŠ"), 1.0);
+//! # similarity::set_global(JaroWinkler);
+//! ```
+
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
+
+/// Measure of how similar two texts are.
+pub trait Similarity: Send + Sync {
+    /// Returns similarity of the texts between 0 (nothing in common) and 1 (equal).
+    ///
+    /// Implementations should ignore the case of the letters.
+    fn similarity(&self, a: &str, b: &str) -> f64;
+}
+
+impl<T: Similarity + ?Sized> Similarity for Arc<T> {
+    fn similarity(&self, a: &str, b: &str) -> f64 {
+        (**self).similarity(a, b)
+    }
+}
+
+impl<T: Similarity + ?Sized> Similarity for Box<T> {
+    fn similarity(&self, a: &str, b: &str) -> f64 {
+        (**self).similarity(a, b)
+    }
+}
+
+/// Jaro-Winkler similarity of the characters.
+///
+/// Accurate for short texts and tolerant to typos, but quadratic in the length of the texts.
+/// Texts sharing a prefix are considered more similar.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct JaroWinkler;
+
+impl Similarity for JaroWinkler {
+    fn similarity(&self, a: &str, b: &str) -> f64 {
+        /// How much the common prefix increases the similarity.
+        const PREFIX_SCALE: f64 = 0.1;
+        /// Longest prefix taken into account.
+        const MAX_PREFIX: usize = 4;
+
+        let a = a.to_lowercase().chars().collect::<Vec<_>>();
+        let b = b.to_lowercase().chars().collect::<Vec<_>>();
+        let jaro = jaro(&a, &b);
+        let prefix = a.iter().zip(&b).take(MAX_PREFIX).take_while(|(a, b)| a == b).count();
+        jaro + prefix as f64 * PREFIX_SCALE * (1.0 - jaro)
+    }
+}
+
+fn jaro(a: &[char], b: &[char]) -> f64 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let window = (a.len().max(b.len()) / 2).saturating_sub(1);
+    let mut b_matched = vec![false; b.len()];
+    let mut a_matches = Vec::new();
+    for (i, c) in a.iter().enumerate() {
+        let start = i.saturating_sub(window);
+        let end = (i + window + 1).min(b.len());
+        if let Some(j) = (start..end).find(|&j| !b_matched[j] && b[j] == *c) {
+            b_matched[j] = true;
+            a_matches.push(*c);
+        }
+    }
+    if a_matches.is_empty() {
+        return 0.0;
+    }
+
+    let b_matches = b.iter().zip(&b_matched).filter(|(_, matched)| **matched).map(|(c, _)| c);
+    let transpositions = a_matches.iter().zip(b_matches).filter(|(a, b)| a != b).count() / 2;
+    let matches = a_matches.len() as f64;
+    (matches / a.len() as f64 + matches / b.len() as f64 + (matches - transpositions as f64) / matches) / 3.0
+}
+
+/// Jaccard similarity of the sets of character trigrams.
+///
+/// Linear in the length of the texts and insensitive to the order of words, so it's suitable
+/// for long descriptions, but less tolerant to typos in short texts than [`JaroWinkler`].
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Trigrams;
+
+impl Similarity for Trigrams {
+    fn similarity(&self, a: &str, b: &str) -> f64 {
+        let a = trigrams(a);
+        let b = trigrams(b);
+        if a.is_empty() && b.is_empty() {
+            return 1.0;
+        }
+        a.intersection(&b).count() as f64 / a.union(&b).count() as f64
+    }
+}
+
+/// Returns the trigrams of the words padded with spaces, so that short words have some too.
+fn trigrams(text: &str) -> HashSet<[char; 3]> {
+    let mut trigrams = HashSet::new();
+    for word in text.to_lowercase().split_whitespace() {
+        let chars = std::iter::once(' ').chain(word.chars()).chain(std::iter::once(' ')).collect::<Vec<_>>();
+        trigrams.extend(chars.windows(3).map(|window| [window[0], window[1], window[2]]));
+    }
+    trigrams
+}
+
+static GLOBAL: RwLock<Option<Arc<dyn Similarity>>> = RwLock::new(None);
+
+/// Returns the backend used by the crate, [`JaroWinkler`] unless changed by [`set_global`].
+pub fn global() -> Arc<dyn Similarity> {
+    let global = GLOBAL.read().unwrap_or_else(|error| error.into_inner());
+    match &*global {
+        Some(similarity) => Arc::clone(similarity),
+        None => Arc::new(JaroWinkler),
+    }
+}
+
+/// Replaces the backend used by the crate.
+///
+/// This affects all threads, so it should be called at the start of the program.
+pub fn set_global<T: Similarity + 'static>(similarity: T) {
+    *GLOBAL.write().unwrap_or_else(|error| error.into_inner()) = Some(Arc::new(similarity));
+}
+
+/// Returns the highest similarity of the phrase to any run of the same number of words in the
+/// text.
+///
+/// This finds e.g. a slightly misspelled name of a dish in a longer description.
+pub(crate) fn best_phrase_match(similarity: &dyn Similarity, phrase: &str, text: &str) -> f64 {
+    let phrase_words = phrase.split_whitespace().count().max(1);
+    let words = text.split(|c: char| c.is_whitespace() || c == ',').filter(|word| !word.is_empty()).collect::<Vec<_>>();
+    if words.len() <= phrase_words {
+        return similarity.similarity(phrase, text);
+    }
+    words
+        .windows(phrase_words)
+        .map(|window| similarity.similarity(phrase, &window.join(" ")))
+        .fold(0.0, f64::max)
+}
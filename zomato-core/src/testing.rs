@@ -0,0 +1,7 @@
+//! Pages for testing code using this crate.
+
+/// Daily menu page of a restaurant with a single day containing three dishes.
+///
+/// The page is synthetic but has the same structure as real Zomato pages. The restaurant is called
+/// "U Zlatého Bažanta", the menu is for "Today" and contains a soup and two main courses.
+pub const FIXTURE: &str = include_str!("../fixtures/small.html");
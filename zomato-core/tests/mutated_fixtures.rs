@@ -0,0 +1,136 @@
+//! Parses the fixture modified the way Zomato might change its pages.
+
+use zomato_core::testing::FIXTURE;
+
+fn mutate(from: &str, to: &str) -> String {
+    assert!(FIXTURE.contains(from), "the fixture doesn't contain {}", from);
+    FIXTURE.replace(from, to)
+}
+
+#[test]
+fn missing_price() {
+    let html = mutate(r#",\"displayPrice\":\"€1.20\""#, "");
+    let menus = zomato_core::parse_daily_menu(&html).unwrap();
+    assert_eq!(menus[0].items.len(), 3);
+    assert_eq!(menus[0].items[0].price, "");
+    assert_eq!(menus[0].items[1].price, "€7.20");
+
+    let menus = zomato_core::parse_daily_menu_borrowed(&html).unwrap();
+    assert_eq!(menus[0].items[0].price, "");
+}
+
+#[test]
+fn renamed_price() {
+    let html = mutate(r#"\"displayPrice\":\"€1.20\""#, r#"\"price\":\"€1.20\""#);
+    let menus = zomato_core::parse_daily_menu(&html).unwrap();
+    assert_eq!(menus[0].items[0].price, "€1.20");
+}
+
+#[test]
+fn renamed_heading() {
+    let html = mutate(r#"\"timeHeading\""#, r#"\"heading\""#);
+    let menus = zomato_core::parse_daily_menu(&html).unwrap();
+    assert_eq!(menus[0].date, "Today");
+
+    let html = mutate(r#"\"timeHeading\""#, r#"\"dayLabel\""#);
+    let menus = zomato_core::parse_daily_menu(&html).unwrap();
+    assert_eq!(menus[0].date, "");
+    assert_eq!(menus[0].items.len(), 3);
+
+    let menus = zomato_core::parse_daily_menu_borrowed(&html).unwrap();
+    assert_eq!(menus[0].date, "");
+    assert_eq!(menus[0].items.len(), 3);
+}
+
+#[test]
+fn malformed_dish_is_skipped() {
+    let html = mutate(r#"\"name\":\"Hovädzí vývar s rezancami (1,3,9)\""#, r#"\"title\":\"Hovädzí vývar s rezancami (1,3,9)\""#);
+    let menus = zomato_core::parse_daily_menu(&html).unwrap();
+    assert_eq!(menus[0].items.len(), 2);
+    assert!(menus[0].items[0].description.contains("rezeň"));
+
+    let html = mutate(r#"\"name\":\"Vyprážaný syr, hranolky, tatárska omáčka (1,3,7)\""#, r#"\"name\":42"#);
+    let menus = zomato_core::parse_daily_menu(&html).unwrap();
+    assert_eq!(menus[0].items.len(), 2);
+
+    let menus = zomato_core::parse_daily_menu_borrowed(&html).unwrap();
+    assert_eq!(menus[0].items.len(), 2);
+}
+
+#[test]
+fn missing_daily_menu() {
+    let html = mutate(r#"\"SECTION_DAILY_MENU\""#, r#"\"SECTION_WEEKLY_MENU\""#);
+    let page = zomato_core::Parser::new().parse_page(&html).unwrap();
+    assert!(page.menus.is_empty());
+    assert_eq!(page.info.unwrap().name, "U Zlatého Bažanta");
+}
+
+#[test]
+fn missing_data_still_fails() {
+    let html = mutate("window.__PRELOADED_STATE__", "window.__STATE__");
+    assert!(zomato_core::parse_daily_menu(&html).is_err());
+}
+
+#[test]
+fn literal_object() {
+    let state = zomato_core::extract_preloaded_state(FIXTURE).unwrap();
+    let html = format!("<script>\nwindow.__PRELOADED_STATE__ = {};\n</script>", state);
+    let page = zomato_core::Parser::new().parse_page(&html).unwrap();
+    assert_eq!(page.extraction, zomato_core::Extraction::LiteralObject);
+    assert_eq!(page.menus[0].items.len(), 3);
+
+    let menus = zomato_core::parse_daily_menu_borrowed(&html).unwrap();
+    assert!(matches!(menus[0].items[1].price, std::borrow::Cow::Borrowed("€7.20")));
+
+    let outside_script = format!("<p>window.__PRELOADED_STATE__ = {};</p>", state);
+    assert!(zomato_core::parse_daily_menu(&outside_script).is_err());
+}
+
+#[test]
+fn unexpected_tags() {
+    let html = mutate(r#"\"tags\":[\"veg\"]"#, r#"\"tags\":{\"veg\":true}"#);
+    let menus = zomato_core::parse_daily_menu(&html).unwrap();
+    assert_eq!(menus[0].items.len(), 3);
+    assert!(menus[0].items[2].tags.is_empty());
+    assert_eq!(menus[0].items[1].tags, ["non-veg"]);
+}
+
+#[test]
+fn unexpected_validity() {
+    let html = mutate(r#"\"isCurrent\":true"#, r#"\"isCurrent\":\"yes\""#);
+    let html = html.replace(r#"\"startTime\":1792054800"#, r#"\"startTime\":null"#);
+    let menus = zomato_core::parse_daily_menu(&html).unwrap();
+    assert_eq!(menus[0].items.len(), 3);
+    assert!(!menus[0].is_current);
+    assert_eq!(menus[0].valid_from, None);
+    assert_eq!(menus[0].valid_to, Some(1792069200));
+}
+
+#[test]
+fn multiple_restaurants() {
+    let html = mutate(r#"]}}}}}")"#, r#"]}},\"99\":{\"sections\":{}}}}}")"#);
+    for _ in 0..10 {
+        let menus = zomato_core::parse_daily_menu(&html).unwrap();
+        assert_eq!(menus[0].items.len(), 3);
+        let menus = zomato_core::parse_daily_menu_borrowed(&html).unwrap();
+        assert_eq!(menus[0].items.len(), 3);
+    }
+}
+
+#[test]
+fn menus_sorted_by_time() {
+    let html = mutate("}]}]}}}}}", r#"}]},{\"timeHeading\":\"Yesterday\",\"startTime\":1791968400,\"dishes\":[]}]}}}}}"#);
+    let menus = zomato_core::parse_daily_menu(&html).unwrap();
+    assert_eq!(menus[0].date, "Yesterday");
+    assert_eq!(menus[1].date, "Today");
+    let menus = zomato_core::parse_daily_menu_borrowed(&html).unwrap();
+    assert_eq!(menus[0].date, "Yesterday");
+    assert_eq!(menus[1].date, "Today");
+    assert_eq!(menus[1].clone().into_owned().valid_from, Some(1792054800));
+
+    let html = html.replace(r#"\"startTime\":1791968400,"#, "");
+    let menus = zomato_core::parse_daily_menu(&html).unwrap();
+    assert_eq!(menus[0].date, "Today");
+    let menus = zomato_core::parse_daily_menu_borrowed(&html).unwrap();
+    assert_eq!(menus[0].date, "Today");
+}
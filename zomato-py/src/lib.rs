@@ -0,0 +1,100 @@
+//! Python bindings for the `zomato` crate.
+//!
+//! The module is built using `maturin` and exposes the fetching function as Python coroutine, so
+//! it can be awaited from `asyncio` code (including Jupyter notebooks).
+
+use pyo3::prelude::*;
+use pyo3::exceptions::PyRuntimeError;
+
+/// Food within daily menu.
+#[pyclass(name = "MenuItem", module = "zomato")]
+#[derive(Clone)]
+pub struct PyMenuItem {
+    /// Description of the food
+    #[pyo3(get)]
+    pub description: String,
+    /// Description of the food exactly as received from Zomato.
+    #[pyo3(get)]
+    pub raw_description: String,
+    /// Food price.
+    ///
+    /// Note that sometimes the price may be empty!
+    #[pyo3(get)]
+    pub price: String,
+    /// Numbers of allergen groups declared in the description.
+    #[pyo3(get)]
+    pub allergens: Vec<u8>,
+    /// The declaration of allergens as written in the description.
+    #[pyo3(get)]
+    pub raw_allergens: Option<String>,
+}
+
+#[pymethods]
+impl PyMenuItem {
+    fn __repr__(&self) -> String {
+        format!("MenuItem(description={:?}, price={:?}, allergens={:?})", self.description, self.price, self.allergens)
+    }
+}
+
+impl From<zomato::MenuItem> for PyMenuItem {
+    fn from(item: zomato::MenuItem) -> Self {
+        PyMenuItem {
+            description: item.description,
+            raw_description: item.raw_description,
+            price: item.price,
+            allergens: item.allergens,
+            raw_allergens: item.raw_allergens,
+        }
+    }
+}
+
+/// Daily menu of a restaurant.
+#[pyclass(name = "Menu", module = "zomato")]
+#[derive(Clone)]
+pub struct PyMenu {
+    /// Date of the menu.
+    #[pyo3(get)]
+    pub date: String,
+    /// Food items offered at this day.
+    #[pyo3(get)]
+    pub items: Vec<PyMenuItem>,
+}
+
+#[pymethods]
+impl PyMenu {
+    fn __repr__(&self) -> String {
+        format!("Menu(date={:?}, items=<{} items>)", self.date, self.items.len())
+    }
+}
+
+impl From<zomato::Menu> for PyMenu {
+    fn from(menu: zomato::Menu) -> Self {
+        PyMenu {
+            date: menu.date,
+            items: menu.items.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Fetches daily menu of given restaurant.
+///
+/// Returns an awaitable resolving to a list of `Menu` objects.
+#[pyfunction]
+fn get_daily_menu(py: Python<'_>, city: String, restaurant: String) -> PyResult<&PyAny> {
+    pyo3_asyncio::tokio::future_into_py(py, async move {
+        let menus = zomato::get_daily_menu(&city, &restaurant)
+            .await
+            .map_err(|error| PyRuntimeError::new_err(error.to_string()))?;
+
+        Ok(menus.into_iter().map(PyMenu::from).collect::<Vec<_>>())
+    })
+}
+
+#[pymodule]
+#[pyo3(name = "zomato")]
+fn zomato_py(_py: Python<'_>, module: &PyModule) -> PyResult<()> {
+    module.add_class::<PyMenuItem>()?;
+    module.add_class::<PyMenu>()?;
+    module.add_function(wrap_pyfunction!(get_daily_menu, module)?)?;
+    Ok(())
+}